@@ -1,255 +1,495 @@
-use crate::{span::Span, token::TokenKind};
-
-#[derive(Debug, Clone)]
-pub enum Statement {
-    ConstantDeclaration(String, Type, Expression, Span),
-    VariableDeclaration(String, Type, Expression, Span),
-    MutableDeclaration(String, Type, Expression, Span),
-    Namespace(String, Vec<Statement>, Span),
-    Import(String, String, Span),
-    Struct(String, Vec<Type>, Vec<String>, Vec<StructField>, Span),
-    Enum(String, Vec<EnumVariant>, Span),
-    TypedEnum(String, Type, Vec<EnumVariant>, Span),
-    Union(String, Vec<Type>, Span),
-    Function(String, Vec<(String, Type, bool, Span)>, Vec<Type>, Vec<Statement>, Span),
-    StructFunction(Type, String, Vec<(String, Type, bool, Span)>, Vec<Type>, Vec<Statement>, Span),
-    Return(Vec<Expression>, Span),
-    While(Expression, Vec<Statement>, Span),
-    If(Expression, Vec<Statement>, Vec<Statement>, Span),
-    Expression(Expression, Span),
-}
-
-#[derive(Debug, Clone)]
-pub enum Expression {
-    SelfLiteral(Span),
-    Identifier(String, Span),
-    Integer(i64, Span),
-    Char(char, Span),
-    String(String, Span),
-    StaticMemberAccess(Box<Expression>, Box<Expression>, Span),
-    MemberAccess(Box<Expression>, Box<Expression>, Span),
-    BinaryOperation(Box<Expression>, TokenKind, Box<Expression>, Span),
-    ArrayAccess(String, Box<Expression>, Span),
-    StructLiteral(String, Vec<(Option<String>, Expression, Span)>, Span),
-    AddressOf(Box<Expression>, Span),
-    Dereference(Box<Expression>, Span),
-    Try(Box<Expression>, Span),
-    Null(Span),
-}
-
-#[derive(Debug, Clone, PartialEq)]
-pub enum Type {
-    I8(Span), I16(Span), I32(Span), I64(Span),
-    U8(Span), U16(Span), U32(Span), U64(Span),
-    F32(Span), F64(Span),
-    Char(Span),
-    Bool(Span),
-    Void(Span),
-
-    Type(Span),
-
-    Array(Box<Type>, Span),
-    Pointer(Box<Type>, Span),
-    Optional(Box<Type>, Span),
-
-    VarArgs(Box<Option<Type>>, Span),
-
-    GenericInstance(String, Vec<Type>, Span),
-    Generic(String, Span),
-
-    Unknown(String, Span),
-    Struct(String, Span),
-    Enum(String, Span),
-    Union(String, Span),
-
-    AwaitingInference,
-}
-
-#[derive(Debug, Clone)]
-pub struct StructField {
-    pub name: String,
-    pub ty: Type,
-    pub span: Span,
-}
-
-#[derive(Debug, Clone)]
-pub enum EnumVariant {
-    Unit(String, Span),
-    Expression(String, Expression, Span),
-}
-
-impl Statement {
-    pub fn span(&self) -> Span {
-        match self {
-            Statement::ConstantDeclaration(_, _, _, s) => s.clone(),
-            Statement::VariableDeclaration(_, _, _, s) => s.clone(),
-            Statement::MutableDeclaration(_, _, _, s) => s.clone(),
-            Statement::Import(_, _, s) => s.clone(),
-            Statement::Namespace(_, _, s) => s.clone(),
-            Statement::Struct(_, _, _, _, s) => s.clone(),
-            Statement::Enum(_, _, s) => s.clone(),
-            Statement::TypedEnum(_, _, _, s) => s.clone(),
-            Statement::Union(_, _, s) => s.clone(),
-            Statement::Function(_, _, _, _, s) => s.clone(),
-            Statement::StructFunction(_, _, _, _, _, s) => s.clone(),
-            Statement::Return(_, s) => s.clone(),
-            Statement::While(_, _, s) => s.clone(),
-            Statement::If(_, _, _, s) => s.clone(),
-            Statement::Expression(_, s) => s.clone(),
-        }
-    }
-}
-
-impl Expression {
-    pub fn span(&self) -> Span {
-        match self {
-            Expression::SelfLiteral(s) => s.clone(),
-            Expression::Identifier(_, s) => s.clone(),
-            Expression::Integer(_, s) => s.clone(),
-            Expression::Char(_, s) => s.clone(),
-            Expression::String(_, s) => s.clone(),
-            Expression::StaticMemberAccess(_, _, s) => s.clone(),
-            Expression::MemberAccess(_, _, s) => s.clone(),
-            Expression::BinaryOperation(_, _, _, s) => s.clone(),
-            Expression::ArrayAccess(_, _, s) => s.clone(),
-            Expression::StructLiteral(_, _, s) => s.clone(),
-            Expression::AddressOf(_, s) => s.clone(),
-            Expression::Dereference(_, s) => s.clone(),
-            Expression::Try(_, s) => s.clone(),
-            Expression::Null(s) => s.clone(),
-        }
-    }
-
-    pub fn get_type(&self) -> Type {
-        match self {
-            Expression::Identifier(ident, s) => Type::Unknown(ident.clone(), s.clone()),
-            Expression::Integer(_, s) => Type::I64(s.clone()),
-            Expression::Char(_, s) => Type::Char(s.clone()),
-            Expression::String(_, s) => Type::Pointer(Box::new(Type::Char(s.clone())), s.clone()),
-            Expression::StaticMemberAccess(_, member, _) => member.get_type(),
-            Expression::MemberAccess(_, member, _) => member.get_type(),
-            Expression::StructLiteral(name, _, s) => Type::Unknown(name.clone(), s.clone()),
-            Expression::AddressOf(expr, s) => Type::Pointer(Box::new(expr.get_type()), s.clone()),
-            Expression::Dereference(expr, _) => match expr.get_type() {
-                Type::Pointer(ty, _) => *ty,
-                _ => unreachable!("Expression::get_type()"),
-            },
-            _ => unreachable!("Expression::get_type()"),
-        }
-    }
-}
-
-impl Type {
-    pub fn span(&self) -> Span {
-        match self {
-            Type::I8(s) => s.clone(),
-            Type::I16(s) => s.clone(),
-            Type::I32(s) => s.clone(),
-            Type::I64(s) => s.clone(),
-            Type::U8(s) => s.clone(),
-            Type::U16(s) => s.clone(),
-            Type::U32(s) => s.clone(),
-            Type::U64(s) => s.clone(),
-            Type::F32(s) => s.clone(),
-            Type::F64(s) => s.clone(),
-            Type::Char(s) => s.clone(),
-            Type::Bool(s) => s.clone(),
-            Type::Void(s) => s.clone(),
-            Type::Type(s) => s.clone(),
-            Type::Array(_, s) => s.clone(),
-            Type::Pointer(_, s) => s.clone(),
-            Type::Optional(_, s) => s.clone(),
-            Type::VarArgs(_, s) => s.clone(),
-            Type::GenericInstance(_, _, s) => s.clone(),
-            Type::Generic(_, s) => s.clone(),
-            Type::Unknown(_, s) => s.clone(),
-            Type::Struct(_, s) => s.clone(),
-            Type::Enum(_, s) => s.clone(),
-            Type::Union(_, s) => s.clone(),
-            Type::AwaitingInference => unreachable!(),
-        }
-    }
-
-    pub fn name(&self) -> String {
-        match self {
-            Type::I8(_) => "i8".to_string(),
-            Type::I16(_) => "i16".to_string(),
-            Type::I32(_) => "i32".to_string(),
-            Type::I64(_) => "i64".to_string(),
-            Type::U8(_) => "u8".to_string(),
-            Type::U16(_) => "u16".to_string(),
-            Type::U32(_) => "u32".to_string(),
-            Type::U64(_) => "u64".to_string(),
-            Type::F32(_) => "f32".to_string(),
-            Type::F64(_) => "f64".to_string(),
-            Type::Char(_) => "char".to_string(),
-            Type::Bool(_) => "bool".to_string(),
-            Type::Void(_) => "void".to_string(),
-            Type::Type(_) => "type".to_string(),
-            Type::Array(ty, _) => format!("{}[]", ty.name()),
-            Type::Pointer(ty, _) => format!("{}*", ty.name()),
-            Type::Optional(ty, _) => format!("{}?", ty.name()),
-            Type::VarArgs(ty, _) => {
-                if let Some(ty) = *ty.clone() {
-                    format!("...{}", ty.name())
-                } else {
-                    "...".to_string()
-                }
-            }
-            Type::GenericInstance(name, types, _) => format!("{}({})", name.clone(), types.iter().map(|t| t.name()).collect::<Vec<String>>().join(", ")),
-            Type::Generic(name, _) => format!("${}", name.clone()),
-            Type::Unknown(name, _) => format!("unknown {}", name.clone()),
-            Type::Struct(name, _) => format!("struct {}", name.clone()),
-            Type::Enum(name, _) => format!("enum {}", name.clone()),
-            Type::Union(name, _) => format!("union {}", name.clone()),
-            Type::AwaitingInference => unreachable!(),
-        }
-    }
-
-    pub fn equals(&self, other: Type) -> bool {
-        match self {
-            Type::I8(_) => matches!(other, Type::I8(_)),
-            Type::I16(_) => matches!(other, Type::I16(_)),
-            Type::I32(_) => matches!(other, Type::I32(_)),
-            Type::I64(_) => matches!(other, Type::I64(_)),
-            Type::U8(_) => matches!(other, Type::U8(_)),
-            Type::U16(_) => matches!(other, Type::U16(_)),
-            Type::U32(_) => matches!(other, Type::U32(_)),
-            Type::U64(_) => matches!(other, Type::U64(_)),
-            Type::F32(_) => matches!(other, Type::F32(_)),
-            Type::F64(_) => matches!(other, Type::F64(_)),
-            Type::Char(_) => matches!(other, Type::Char(_)),
-            Type::Bool(_) => matches!(other, Type::Bool(_)),
-            Type::Void(_) => matches!(other, Type::Void(_)),
-            Type::Array(ty, _) => {
-                if let Type::Array(other_ty, _) = other {
-                    ty.equals(*other_ty)
-                } else {
-                    false
-                }
-            }
-            Type::Pointer(ty, _) => {
-                if let Type::Pointer(other_ty, _) = other {
-                    ty.equals(*other_ty)
-                } else {
-                    false
-                }
-            }
-            Type::Optional(ty, _) => {
-                if let Type::Optional(other_ty, _) = other {
-                    ty.equals(*other_ty)
-                } else {
-                    false
-                }
-            }
-            Type::Unknown(name, _) => {
-                if let Type::Unknown(other_name, _) = other {
-                    name.clone() == other_name
-                } else {
-                    false
-                }
-            }
-            _ => false,
-        }
-    }
+use crate::{span::Span, token::TokenKind};
+
+#[derive(Debug, Clone)]
+pub enum Statement {
+    ConstantDeclaration(String, Type, Expression, Span),
+    VariableDeclaration(String, Type, Expression, Span),
+    MutableDeclaration(String, Type, Expression, Span),
+    Destructuring(Vec<(String, bool, Span)>, Expression, Span),
+    Namespace(String, Vec<Statement>, Span),
+    Import(String, String, Span),
+    Struct(String, Vec<Type>, Vec<String>, Vec<StructField>, Span),
+    Enum(String, Vec<EnumVariant>, Span),
+    TypedEnum(String, Type, Vec<EnumVariant>, Span),
+    ErrorSet(String, Vec<(String, Span)>, Span),
+    Union(String, Vec<Type>, Span),
+    Function(String, Vec<(String, Type, bool, Span)>, Vec<Type>, Vec<Statement>, Span),
+    StructFunction(Type, String, Vec<(String, Type, bool, Span)>, Vec<Type>, Vec<Statement>, Span),
+    Return(Vec<Expression>, Span),
+    While(Expression, Option<(String, Span)>, Vec<Statement>, Span),
+    For((String, Span), Expression, Vec<Statement>, Span),
+    Break(Option<String>, Span),
+    Continue(Option<String>, Span),
+    If(Expression, Option<(String, Span)>, Vec<Statement>, Vec<Statement>, Span),
+    Match(Expression, Vec<(Pattern, Vec<Statement>, Span)>, Span),
+    Block(Vec<Statement>, Span),
+    Defer(Box<Statement>, Span),
+    Expression(Expression, Span),
+    Documented(String, Box<Statement>, Span),
+    Attribute(String, Vec<String>, Box<Statement>, Span),
+    Labeled(String, Box<Statement>, Span),
+    Error(Span),
+}
+
+#[derive(Debug, Clone)]
+pub enum Expression {
+    SelfLiteral(Span),
+    Identifier(String, Span),
+    Integer(u64, Option<Type>, Span),
+    Float(f64, Option<Type>, Span),
+    Bool(bool, Span),
+    Char(char, Span),
+    String(String, Span),
+    StaticMemberAccess(Box<Expression>, Box<Expression>, Span),
+    MemberAccess(Box<Expression>, Box<Expression>, Span),
+    BinaryOperation(Box<Expression>, TokenKind, Box<Expression>, Span),
+    UnaryOperation(TokenKind, Box<Expression>, Span),
+    ArrayAccess(Box<Expression>, Box<Expression>, Span),
+    ArrayLiteral(Vec<Expression>, Span),
+    Slice(Box<Expression>, Option<Box<Expression>>, Option<Box<Expression>>, Span),
+    Call(Box<Expression>, Vec<Expression>, Span),
+    StructLiteral(String, Vec<Type>, Vec<(Option<String>, Expression, Span)>, Span),
+    AddressOf(Box<Expression>, Span),
+    Dereference(Box<Expression>, Span),
+    Try(Box<Expression>, Span),
+    Catch(Box<Expression>, Option<(String, Span)>, Box<Expression>, Span),
+    Orelse(Box<Expression>, Box<Expression>, Span),
+    Unwrap(Box<Expression>, Span),
+    Match(Box<Expression>, Vec<(Pattern, Expression, Span)>, Span),
+    Range(Box<Expression>, Box<Expression>, Span),
+    Null(Span),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Type {
+    I8(Span), I16(Span), I32(Span), I64(Span),
+    U8(Span), U16(Span), U32(Span), U64(Span),
+    F32(Span), F64(Span),
+    Char(Span),
+    Str(Span),
+    Bool(Span),
+    Void(Span),
+
+    Type(Span),
+
+    Array(Box<Type>, Span),
+    FixedArray(Box<Type>, usize, Span),
+    Pointer(Box<Type>, Span),
+    Optional(Box<Type>, Span),
+    ErrorUnion(Box<Type>, Box<Type>, Span),
+
+    VarArgs(Box<Option<Type>>, Span),
+
+    GenericInstance(String, Vec<Type>, Span),
+    Generic(String, Span),
+
+    Unknown(String, Span),
+    Struct(String, Span),
+    Enum(String, Span),
+    Union(String, Span),
+    ErrorSet(Option<String>, Span),
+
+    AwaitingInference,
+}
+
+#[derive(Debug, Clone)]
+pub struct StructField {
+    pub name: String,
+    pub ty: Type,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub enum EnumVariant {
+    Unit(String, Span),
+    Expression(String, Expression, Span),
+    Payload(String, Vec<StructField>, Span),
+}
+
+#[derive(Debug, Clone)]
+pub enum Pattern {
+    Variant(String, Vec<(String, Span)>, Span),
+    Wildcard(Span),
+}
+
+impl Statement {
+    pub fn span(&self) -> Span {
+        match self {
+            Statement::ConstantDeclaration(_, _, _, s) => s.clone(),
+            Statement::VariableDeclaration(_, _, _, s) => s.clone(),
+            Statement::MutableDeclaration(_, _, _, s) => s.clone(),
+            Statement::Destructuring(_, _, s) => s.clone(),
+            Statement::Import(_, _, s) => s.clone(),
+            Statement::Namespace(_, _, s) => s.clone(),
+            Statement::Struct(_, _, _, _, s) => s.clone(),
+            Statement::Enum(_, _, s) => s.clone(),
+            Statement::TypedEnum(_, _, _, s) => s.clone(),
+            Statement::ErrorSet(_, _, s) => s.clone(),
+            Statement::Union(_, _, s) => s.clone(),
+            Statement::Function(_, _, _, _, s) => s.clone(),
+            Statement::StructFunction(_, _, _, _, _, s) => s.clone(),
+            Statement::Return(_, s) => s.clone(),
+            Statement::While(_, _, _, s) => s.clone(),
+            Statement::For(_, _, _, s) => s.clone(),
+            Statement::Break(_, s) => s.clone(),
+            Statement::Continue(_, s) => s.clone(),
+            Statement::If(_, _, _, _, s) => s.clone(),
+            Statement::Match(_, _, s) => s.clone(),
+            Statement::Block(_, s) => s.clone(),
+            Statement::Defer(_, s) => s.clone(),
+            Statement::Expression(_, s) => s.clone(),
+            Statement::Documented(_, _, s) => s.clone(),
+            Statement::Attribute(_, _, _, s) => s.clone(),
+            Statement::Labeled(_, _, s) => s.clone(),
+            Statement::Error(s) => s.clone(),
+        }
+    }
+}
+
+impl Expression {
+    pub fn span(&self) -> Span {
+        match self {
+            Expression::SelfLiteral(s) => s.clone(),
+            Expression::Identifier(_, s) => s.clone(),
+            Expression::Integer(_, _, s) => s.clone(),
+            Expression::Float(_, _, s) => s.clone(),
+            Expression::Bool(_, s) => s.clone(),
+            Expression::Char(_, s) => s.clone(),
+            Expression::String(_, s) => s.clone(),
+            Expression::StaticMemberAccess(_, _, s) => s.clone(),
+            Expression::MemberAccess(_, _, s) => s.clone(),
+            Expression::BinaryOperation(_, _, _, s) => s.clone(),
+            Expression::UnaryOperation(_, _, s) => s.clone(),
+            Expression::ArrayAccess(_, _, s) => s.clone(),
+            Expression::ArrayLiteral(_, s) => s.clone(),
+            Expression::Slice(_, _, _, s) => s.clone(),
+            Expression::Call(_, _, s) => s.clone(),
+            Expression::StructLiteral(_, _, _, s) => s.clone(),
+            Expression::AddressOf(_, s) => s.clone(),
+            Expression::Dereference(_, s) => s.clone(),
+            Expression::Try(_, s) => s.clone(),
+            Expression::Catch(_, _, _, s) => s.clone(),
+            Expression::Orelse(_, _, s) => s.clone(),
+            Expression::Unwrap(_, s) => s.clone(),
+            Expression::Match(_, _, s) => s.clone(),
+            Expression::Range(_, _, s) => s.clone(),
+            Expression::Null(s) => s.clone(),
+        }
+    }
+
+    // The expressions this one is made of, in the order they are evaluated.
+    pub fn operands(&self) -> Vec<&Expression> {
+        match self {
+            Expression::BinaryOperation(left, _, right, _)
+            | Expression::Range(left, right, _)
+            | Expression::ArrayAccess(left, right, _) => vec![left, right],
+            Expression::Slice(value, start, end, _) => std::iter::once(&**value).chain(start.as_deref()).chain(end.as_deref()).collect(),
+            Expression::ArrayLiteral(elements, _) => elements.iter().collect(),
+            Expression::UnaryOperation(_, value, _)
+            | Expression::MemberAccess(value, _, _)
+            | Expression::AddressOf(value, _)
+            | Expression::Dereference(value, _)
+            | Expression::Try(value, _)
+            | Expression::Unwrap(value, _) => vec![value],
+            Expression::Catch(value, _, fallback, _) | Expression::Orelse(value, fallback, _) => vec![value, fallback],
+            Expression::Call(_, arguments, _) => arguments.iter().collect(),
+            Expression::StructLiteral(_, _, fields, _) => fields.iter().map(|(_, value, _)| value).collect(),
+            Expression::Match(value, arms, _) => std::iter::once(&**value).chain(arms.iter().map(|(_, arm, _)| arm)).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    // The span of the last expression in this one, which tells apart nested expressions that start at the same
+    // token, like `a.b` and `a.b.c`.
+    pub fn last_span(&self) -> Span {
+        match self {
+            Expression::StaticMemberAccess(_, last, _)
+            | Expression::MemberAccess(_, last, _)
+            | Expression::BinaryOperation(_, _, last, _)
+            | Expression::UnaryOperation(_, last, _)
+            | Expression::ArrayAccess(_, last, _)
+            | Expression::AddressOf(last, _)
+            | Expression::Dereference(last, _)
+            | Expression::Try(last, _)
+            | Expression::Catch(_, _, last, _)
+            | Expression::Orelse(_, last, _)
+            | Expression::Range(_, last, _) => last.last_span(),
+            Expression::Slice(object, start, end, _) => end.as_deref().or(start.as_deref()).unwrap_or(object).last_span(),
+            Expression::Call(callee, arguments, _) => arguments.last().unwrap_or(callee).last_span(),
+            expression => expression.span(),
+        }
+    }
+
+    pub fn get_type(&self) -> Type {
+        match self {
+            Expression::Identifier(ident, s) => Type::Unknown(ident.clone(), s.clone()),
+            Expression::Integer(_, suffix, s) => suffix.clone().unwrap_or(Type::I64(s.clone())),
+            Expression::Float(_, suffix, s) => suffix.clone().unwrap_or(Type::F64(s.clone())),
+            Expression::Bool(_, s) => Type::Bool(s.clone()),
+            Expression::Char(_, s) => Type::Char(s.clone()),
+            Expression::String(_, s) => Type::Str(s.clone()),
+            Expression::StaticMemberAccess(_, member, _) => member.get_type(),
+            Expression::MemberAccess(_, member, _) => member.get_type(),
+            Expression::StructLiteral(name, _, _, s) => Type::Unknown(name.clone(), s.clone()),
+            Expression::AddressOf(expr, s) => Type::Pointer(Box::new(expr.get_type()), s.clone()),
+            Expression::Dereference(expr, _) => match expr.get_type() {
+                Type::Pointer(ty, _) => *ty,
+                _ => unreachable!("Expression::get_type()"),
+            },
+            _ => unreachable!("Expression::get_type()"),
+        }
+    }
+
+    // The names this condition proves are not null when it evaluates to `holds`, like `x` for `x != null`.
+    pub fn narrowed(&self, holds: bool) -> Vec<String> {
+        match self {
+            Expression::BinaryOperation(left, operator @ (TokenKind::EqualEqual | TokenKind::BangEqual), right, _) => {
+                let name: &String = match (&**left, &**right) {
+                    (Expression::Identifier(name, _), Expression::Null(_)) | (Expression::Null(_), Expression::Identifier(name, _)) => name,
+                    _ => return Vec::new(),
+                };
+                if (*operator == TokenKind::BangEqual) == holds { vec![name.clone()] } else { Vec::new() }
+            }
+            Expression::BinaryOperation(left, TokenKind::AmpersandAmpersand, right, _) if holds => {
+                left.narrowed(true).into_iter().chain(right.narrowed(true)).collect()
+            }
+            Expression::BinaryOperation(left, TokenKind::PipePipe, right, _) if !holds => {
+                left.narrowed(false).into_iter().chain(right.narrowed(false)).collect()
+            }
+            Expression::UnaryOperation(TokenKind::Bang, value, _) => value.narrowed(!holds),
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl Pattern {
+    pub fn span(&self) -> Span {
+        match self {
+            Pattern::Variant(_, _, s) => s.clone(),
+            Pattern::Wildcard(s) => s.clone(),
+        }
+    }
+}
+
+impl Type {
+    pub fn span(&self) -> Span {
+        match self {
+            Type::I8(s) => s.clone(),
+            Type::I16(s) => s.clone(),
+            Type::I32(s) => s.clone(),
+            Type::I64(s) => s.clone(),
+            Type::U8(s) => s.clone(),
+            Type::U16(s) => s.clone(),
+            Type::U32(s) => s.clone(),
+            Type::U64(s) => s.clone(),
+            Type::F32(s) => s.clone(),
+            Type::F64(s) => s.clone(),
+            Type::Char(s) => s.clone(),
+            Type::Str(s) => s.clone(),
+            Type::Bool(s) => s.clone(),
+            Type::Void(s) => s.clone(),
+            Type::Type(s) => s.clone(),
+            Type::Array(_, s) => s.clone(),
+            Type::FixedArray(_, _, s) => s.clone(),
+            Type::Pointer(_, s) => s.clone(),
+            Type::Optional(_, s) => s.clone(),
+            Type::ErrorUnion(_, _, s) => s.clone(),
+            Type::VarArgs(_, s) => s.clone(),
+            Type::GenericInstance(_, _, s) => s.clone(),
+            Type::Generic(_, s) => s.clone(),
+            Type::Unknown(_, s) => s.clone(),
+            Type::Struct(_, s) => s.clone(),
+            Type::Enum(_, s) => s.clone(),
+            Type::Union(_, s) => s.clone(),
+            Type::ErrorSet(_, s) => s.clone(),
+            Type::AwaitingInference => unreachable!(),
+        }
+    }
+
+    // The type as it is written in source, for diagnostics.
+    pub fn name(&self) -> String {
+        match self {
+            Type::I8(_) => "i8".to_string(),
+            Type::I16(_) => "i16".to_string(),
+            Type::I32(_) => "i32".to_string(),
+            Type::I64(_) => "i64".to_string(),
+            Type::U8(_) => "u8".to_string(),
+            Type::U16(_) => "u16".to_string(),
+            Type::U32(_) => "u32".to_string(),
+            Type::U64(_) => "u64".to_string(),
+            Type::F32(_) => "f32".to_string(),
+            Type::F64(_) => "f64".to_string(),
+            Type::Char(_) => "char".to_string(),
+            Type::Str(_) => "str".to_string(),
+            Type::Bool(_) => "bool".to_string(),
+            Type::Void(_) => "void".to_string(),
+            Type::Type(_) => "type".to_string(),
+            Type::Array(ty, _) => format!("[]{}", ty.name()),
+            Type::FixedArray(ty, length, _) => format!("[{}]{}", length, ty.name()),
+            Type::Pointer(ty, _) => format!("*{}", ty.name()),
+            Type::Optional(ty, _) => format!("?{}", ty.name()),
+            Type::ErrorUnion(set, ty, _) => match &**set {
+                Type::ErrorSet(None, _) => format!("!{}", ty.name()),
+                set => format!("{}!{}", set.name(), ty.name()),
+            },
+            Type::VarArgs(ty, _) => match &**ty {
+                Some(ty) => format!("{}...", ty.name()),
+                None => "...".to_string(),
+            },
+            Type::GenericInstance(name, types, _) => format!("{}<{}>", name, types.iter().map(|t| t.name()).collect::<Vec<String>>().join(", ")),
+            Type::Generic(name, _) => format!("${}", name),
+            Type::Unknown(name, _)
+            | Type::Struct(name, _)
+            | Type::Enum(name, _)
+            | Type::Union(name, _)
+            | Type::ErrorSet(Some(name), _) => name.clone(),
+            Type::ErrorSet(None, _) => "error".to_string(),
+            Type::AwaitingInference => "_".to_string(),
+        }
+    }
+
+    pub fn equals(&self, other: Type) -> bool {
+        match self {
+            Type::I8(_) => matches!(other, Type::I8(_)),
+            Type::I16(_) => matches!(other, Type::I16(_)),
+            Type::I32(_) => matches!(other, Type::I32(_)),
+            Type::I64(_) => matches!(other, Type::I64(_)),
+            Type::U8(_) => matches!(other, Type::U8(_)),
+            Type::U16(_) => matches!(other, Type::U16(_)),
+            Type::U32(_) => matches!(other, Type::U32(_)),
+            Type::U64(_) => matches!(other, Type::U64(_)),
+            Type::F32(_) => matches!(other, Type::F32(_)),
+            Type::F64(_) => matches!(other, Type::F64(_)),
+            Type::Char(_) => matches!(other, Type::Char(_)),
+            Type::Str(_) => matches!(other, Type::Str(_)),
+            Type::Bool(_) => matches!(other, Type::Bool(_)),
+            Type::Void(_) => matches!(other, Type::Void(_)),
+            Type::Array(ty, _) => {
+                if let Type::Array(other_ty, _) = other {
+                    ty.equals(*other_ty)
+                } else {
+                    false
+                }
+            }
+            Type::FixedArray(ty, length, _) => {
+                if let Type::FixedArray(other_ty, other_length, _) = other {
+                    *length == other_length && ty.equals(*other_ty)
+                } else {
+                    false
+                }
+            }
+            Type::Pointer(ty, _) => {
+                if let Type::Pointer(other_ty, _) = other {
+                    ty.equals(*other_ty)
+                } else {
+                    false
+                }
+            }
+            Type::Optional(ty, _) => {
+                if let Type::Optional(other_ty, _) = other {
+                    ty.equals(*other_ty)
+                } else {
+                    false
+                }
+            }
+            Type::Unknown(name, _) => {
+                if let Type::Unknown(other_name, _) = other {
+                    name.clone() == other_name
+                } else {
+                    false
+                }
+            }
+            Type::ErrorUnion(set, ty, _) => {
+                if let Type::ErrorUnion(other_set, other_ty, _) = other {
+                    set.equals(*other_set) && ty.equals(*other_ty)
+                } else {
+                    false
+                }
+            }
+            Type::ErrorSet(name, _) => matches!(other, Type::ErrorSet(other_name, _) if *name == other_name),
+            Type::Type(_) => matches!(other, Type::Type(_)),
+            Type::Struct(name, _) => matches!(other, Type::Struct(other_name, _) if *name == other_name),
+            Type::Enum(name, _) => matches!(other, Type::Enum(other_name, _) if *name == other_name),
+            Type::Union(name, _) => matches!(other, Type::Union(other_name, _) if *name == other_name),
+            Type::Generic(name, _) => matches!(other, Type::Generic(other_name, _) if *name == other_name),
+            Type::GenericInstance(name, types, _) => {
+                if let Type::GenericInstance(other_name, other_types, _) = other {
+                    name.clone() == other_name
+                        && types.len() == other_types.len()
+                        && types.iter().zip(other_types).all(|(ty, other_ty)| ty.equals(other_ty))
+                } else {
+                    false
+                }
+            }
+            _ => false,
+        }
+    }
+
+    pub fn is_integer(&self) -> bool {
+        matches!(self,
+            Type::I8(_) | Type::I16(_) | Type::I32(_) | Type::I64(_)
+            | Type::U8(_) | Type::U16(_) | Type::U32(_) | Type::U64(_))
+    }
+
+    pub fn is_float(&self) -> bool {
+        matches!(self, Type::F32(_) | Type::F64(_))
+    }
+
+    pub fn is_numeric(&self) -> bool {
+        self.is_integer() || self.is_float() || matches!(self, Type::Char(_))
+    }
+
+    pub fn is_pointer(&self) -> bool {
+        matches!(self, Type::Pointer(_, _))
+    }
+
+    // The distinct generic parameters (`$T`) the type mentions, in order of appearance.
+    pub fn generics(&self) -> Vec<Type> {
+        let mut generics: Vec<Type> = Vec::new();
+        self.collect_generics(&mut generics);
+        generics
+    }
+    fn collect_generics(&self, generics: &mut Vec<Type>) {
+        match self {
+            Type::Generic(_, _) if !generics.iter().any(|generic| generic.equals(self.clone())) => generics.push(self.clone()),
+            Type::Array(ty, _) | Type::FixedArray(ty, _, _) | Type::Pointer(ty, _) | Type::Optional(ty, _) | Type::ErrorUnion(_, ty, _) => ty.collect_generics(generics),
+            Type::VarArgs(ty, _) => {
+                if let Some(ty) = &**ty {
+                    ty.collect_generics(generics);
+                }
+            }
+            Type::GenericInstance(_, types, _) => {
+                for ty in types {
+                    ty.collect_generics(generics);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn substitute(&self, generics: &[Type], arguments: &[Type]) -> Type {
+        match self {
+            Type::Generic(name, _) => {
+                for (generic, argument) in generics.iter().zip(arguments) {
+                    if let Type::Generic(generic_name, _) = generic {
+                        if generic_name == name {
+                            return argument.clone();
+                        }
+                    }
+                }
+                self.clone()
+            }
+            Type::Array(ty, s) => Type::Array(Box::new(ty.substitute(generics, arguments)), s.clone()),
+            Type::FixedArray(ty, length, s) => Type::FixedArray(Box::new(ty.substitute(generics, arguments)), *length, s.clone()),
+            Type::Pointer(ty, s) => Type::Pointer(Box::new(ty.substitute(generics, arguments)), s.clone()),
+            Type::Optional(ty, s) => Type::Optional(Box::new(ty.substitute(generics, arguments)), s.clone()),
+            Type::ErrorUnion(set, ty, s) => Type::ErrorUnion(set.clone(), Box::new(ty.substitute(generics, arguments)), s.clone()),
+            Type::GenericInstance(name, types, s) => Type::GenericInstance(
+                name.clone(),
+                types.iter().map(|ty| ty.substitute(generics, arguments)).collect(),
+                s.clone(),
+            ),
+            _ => self.clone(),
+        }
+    }
 }
\ No newline at end of file
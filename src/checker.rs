@@ -0,0 +1,2442 @@
+use std::collections::{HashMap, HashSet};
+use std::mem::{discriminant, Discriminant};
+
+use crate::ast::{Statement, Expression, Type, StructField, EnumVariant, Pattern};
+use crate::error::{BlazeError, ErrorCode};
+use crate::lint::{Lint, Level, LintLevels, Lints};
+use crate::mangle::mangle_arguments;
+use crate::scope::{Binding, Scope, Signature};
+use crate::span::{FileId, Span};
+use crate::token::TokenKind;
+
+const MAX_INSTANCES: usize = 1024;
+const MAX_INSTANCE_TYPE_SIZE: usize = 64;
+
+type PatternBindings = Vec<(String, Type, Span)>;
+type ExpressionKey = (String, Span, Span, Discriminant<Expression>);
+
+// A generic function or method, checked and emitted once per instance.
+#[derive(Debug, Clone)]
+struct Template {
+    path: Vec<String>,
+    name: String,
+    lint_levels: LintLevels,
+    signature: Signature,
+    parameters: Vec<(String, Type, bool, Span)>,
+    body: Vec<Statement>,
+    span: Span,
+}
+
+// The types of the checked expressions, which the backend lowers them with. The instances of a generic function
+// share the spans of its body, so an expression is keyed by the function it is in as well.
+#[derive(Debug, Clone, Default)]
+pub struct Types {
+    expressions: HashMap<ExpressionKey, Type>,
+    narrowed: HashSet<ExpressionKey>,
+}
+
+impl Types {
+    pub fn get(&self, function: &str, expression: &Expression) -> Option<&Type> {
+        self.expressions.get(&key(function, expression))
+    }
+    // Whether a name reads an optional that a condition has checked against `null`, so its type is that of the value.
+    pub fn is_narrowed(&self, function: &str, expression: &Expression) -> bool {
+        self.narrowed.contains(&key(function, expression))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Checker {
+    pub statements: Vec<Statement>,
+    pub errors: Vec<BlazeError>,
+    pub warnings: Vec<BlazeError>,
+    pub lints: Lints,
+    pub root: Scope,
+    pub types: Types,
+    // The file of a program, which has to declare `main`. Object files are linked into programs declaring it elsewhere.
+    pub program: Option<FileId>,
+    path: Vec<String>,
+    function: String,
+    scope: Scope,
+    returns: Vec<Type>,
+    loops: Vec<Option<String>>,
+    deferring: bool,
+    lint_levels: LintLevels,
+    imports: HashMap<String, (Span, bool, LintLevels)>,
+    templates: HashMap<String, Template>,
+    instances: Vec<(String, Vec<Type>)>,
+    substitution: (Vec<Type>, Vec<Type>),
+    // The spans of the `i64` types given to names declared with an integer literal without a suffix, with the literal.
+    defaulted: HashMap<Span, Span>,
+}
+
+impl Checker {
+    pub fn new(statements: Vec<Statement>) -> Checker {
+        Checker {
+            statements,
+            errors: Vec::new(),
+            warnings: Vec::new(),
+            lints: Lints::new(),
+            root: Scope::new(None),
+            types: Types::default(),
+            program: None,
+            path: Vec::new(),
+            function: String::new(),
+            scope: Scope::new(None),
+            returns: Vec::new(),
+            loops: Vec::new(),
+            deferring: false,
+            lint_levels: Vec::new(),
+            imports: HashMap::new(),
+            templates: HashMap::new(),
+            instances: Vec::new(),
+            substitution: (Vec::new(), Vec::new()),
+            defaulted: HashMap::new(),
+        }
+    }
+
+    pub fn check(&mut self) -> Result<Vec<Statement>, Vec<BlazeError>> {
+        let statements: Vec<Statement> = self.statements.clone();
+        let mut root: Scope = Scope::new(None);
+        self.declare(&statements, &mut root);
+        self.root = root;
+        if self.errors.is_empty() {
+            self.resolve(&statements);
+        }
+        if !self.errors.is_empty() {
+            return Err(self.errors.clone());
+        }
+
+        let mut statements: Vec<Statement> = self.check_declarations(&statements);
+        self.check_instances(&mut statements);
+        self.report_imports();
+        if let Some(file) = self.program.filter(|_| !self.root.functions.contains_key("main")) {
+            self.errors.push(BlazeError::TypeError(ErrorCode::MissingMain, "the program has no 'main' function".to_string(), Span { file, start: 0, end: 0 })
+                .with_help("a program starts at 'main :: fn() -> i32 { ... }', or pass '-c' to build an object file"));
+        }
+        if self.errors.is_empty() {
+            Ok(statements)
+        } else {
+            Err(self.errors.clone())
+        }
+    }
+
+    fn declare(&mut self, statements: &[Statement], scope: &mut Scope) {
+        for statement in statements {
+            if let Err(error) = self.declare_statement(statement, scope) {
+                self.errors.push(error);
+            }
+        }
+    }
+    fn declare_statement(&mut self, statement: &Statement, scope: &mut Scope) -> Result<(), BlazeError> {
+        let (name, span) = match statement {
+            Statement::Documented(_, statement, _) => return self.declare_statement(statement, scope),
+            // An extern function is one of the C library, which has no body and keeps its name in C.
+            Statement::Attribute(name, arguments, statement, span) if name == "extern" => {
+                if !arguments.is_empty() {
+                    return Err(BlazeError::TypeError(ErrorCode::UnknownAttribute, "'extern' takes no arguments".to_string(), span.clone()));
+                }
+                if !matches!(**statement, Statement::Function(..)) {
+                    return Err(BlazeError::TypeError(ErrorCode::UnknownAttribute, "only functions can be 'extern'".to_string(), span.clone()));
+                }
+                return self.declare_statement(statement, scope);
+            }
+            Statement::Attribute(name, arguments, statement, span) => {
+                let length: usize = self.push_lint_levels(name, arguments, span)?;
+                let result: Result<(), BlazeError> = self.declare_statement(statement, scope);
+                self.lint_levels.truncate(length);
+                return result;
+            }
+            Statement::Namespace(name, _, span)
+            | Statement::Struct(name, _, _, _, span)
+            | Statement::Enum(name, _, span)
+            | Statement::TypedEnum(name, _, _, span)
+            | Statement::ErrorSet(name, _, span)
+            | Statement::Union(name, _, span)
+            | Statement::Function(name, _, _, _, span)
+            | Statement::ConstantDeclaration(name, _, _, span)
+            | Statement::VariableDeclaration(name, _, _, span)
+            | Statement::MutableDeclaration(name, _, _, span)
+            | Statement::Import(name, _, span) => (name, span),
+            Statement::StructFunction(..) | Statement::Error(..) => return Ok(()),
+            _ => return Err(BlazeError::TypeError(ErrorCode::MisplacedDeclaration, "expected a declaration at the top level".to_string(), statement.span())),
+        };
+        // A top-level module is loaded as a namespace of the same name, which its import may alias.
+        let same_as_module: bool = matches!(statement, Statement::Import(name, module, _) if scope.path.is_empty() && name == module);
+        if scope.contains(name) && !same_as_module {
+            return Err(BlazeError::TypeError(ErrorCode::DuplicateDefinition, format!("'{}' is already defined", name), span.clone()));
+        }
+
+        match statement {
+            Statement::Namespace(name, statements, _) => {
+                let mut namespace: Scope = Scope::new(None);
+                namespace.path = scope.path.clone();
+                namespace.path.push(name.clone());
+                self.declare(statements, &mut namespace);
+                scope.namespaces.insert(name.clone(), namespace);
+            }
+            Statement::Import(name, module, span) => {
+                self.imports.insert(scope.qualify(name), (span.clone(), false, self.lint_levels.clone()));
+                scope.aliases.insert(name.clone(), module.clone());
+            }
+            Statement::Struct(name, generics, _, fields, _) => {
+                check_fields(fields)?;
+                scope.structs.insert(name.clone(), (generics.clone(), fields.clone()));
+            }
+            Statement::Enum(name, variants, _) => {
+                check_variants(variants)?;
+                scope.enums.insert(name.clone(), (None, variants.clone()));
+            }
+            Statement::TypedEnum(name, ty, variants, _) => {
+                check_variants(variants)?;
+                scope.enums.insert(name.clone(), (Some(ty.clone()), variants.clone()));
+            }
+            Statement::ErrorSet(name, errors, _) => {
+                for (index, (error, error_span)) in errors.iter().enumerate() {
+                    if let Some((_, other_span)) = errors[..index].iter().find(|(other, _)| other == error) {
+                        return Err(BlazeError::TypeError(ErrorCode::DuplicateDefinition, format!("error '{}' is already declared", error), error_span.clone())
+                            .with_label(other_span.clone(), "first declared here"));
+                    }
+                }
+                scope.errors.insert(name.clone(), errors.clone());
+            }
+            Statement::Union(name, types, _) => {
+                scope.unions.insert(name.clone(), types.clone());
+            }
+            Statement::Function(name, parameters, returns, _, _) => {
+                let parameters: Vec<(String, Type)> = parameters.iter()
+                    .map(|(name, ty, _, _)| (name.clone(), ty.clone()))
+                    .collect();
+                scope.functions.insert(name.clone(), (parameters, returns.clone()));
+            }
+            Statement::ConstantDeclaration(name, ty, value, _) => {
+                scope.consts.insert(name.clone(), (ty.clone(), value.clone()));
+            }
+            Statement::VariableDeclaration(name, ty, value, _) => {
+                scope.variables.insert(name.clone(), (ty.clone(), value.clone()));
+            }
+            Statement::MutableDeclaration(name, ty, value, _) => {
+                scope.mutables.insert(name.clone(), (ty.clone(), value.clone()));
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn resolve(&mut self, statements: &[Statement]) {
+        for statement in statements {
+            if let Err(error) = self.resolve_statement(statement) {
+                self.errors.push(error);
+            }
+        }
+    }
+    fn resolve_statement(&mut self, statement: &Statement) -> Result<(), BlazeError> {
+        match statement {
+            Statement::Documented(_, statement, _) => self.resolve_statement(statement)?,
+            Statement::Attribute(name, _, statement, span) if name == "extern" => {
+                self.resolve_statement(statement)?;
+                if let Statement::Function(name, _, _, _, _) = &**statement {
+                    let (parameters, returns) = &self.current().functions[name];
+                    if !function_generics(parameters, returns).is_empty() {
+                        return Err(BlazeError::TypeError(ErrorCode::UnknownAttribute, "an 'extern' function cannot be generic".to_string(), span.clone()));
+                    }
+                }
+            }
+            Statement::Attribute(name, arguments, statement, span) => {
+                // A bad attribute was already reported while declaring, and the statement left undeclared.
+                if let Ok(length) = self.push_lint_levels(name, arguments, span) {
+                    let result: Result<(), BlazeError> = self.resolve_statement(statement);
+                    self.lint_levels.truncate(length);
+                    result?;
+                }
+            }
+            Statement::Namespace(name, statements, _) => {
+                self.path.push(name.clone());
+                self.resolve(statements);
+                self.path.pop();
+            }
+            Statement::Struct(name, generics, _, fields, _) => {
+                let mut resolved: Vec<StructField> = Vec::new();
+                for field in fields {
+                    resolved.push(StructField {
+                        name: field.name.clone(),
+                        ty: self.resolve_type(&field.ty)?,
+                        span: field.span.clone(),
+                    });
+                }
+                self.current_mut().structs.insert(name.clone(), (generics.clone(), resolved));
+            }
+            Statement::Enum(name, variants, _) => {
+                let mut resolved: Vec<EnumVariant> = Vec::new();
+                for variant in variants {
+                    resolved.push(match variant {
+                        EnumVariant::Payload(variant_name, fields, variant_span) => {
+                            let mut fields: Vec<StructField> = fields.clone();
+                            for field in &mut fields {
+                                field.ty = self.resolve_type(&field.ty)?;
+                            }
+                            EnumVariant::Payload(variant_name.clone(), fields, variant_span.clone())
+                        }
+                        variant => variant.clone(),
+                    });
+                }
+                self.current_mut().enums.insert(name.clone(), (None, resolved));
+            }
+            Statement::TypedEnum(name, ty, variants, _) => {
+                let ty: Type = self.resolve_type(ty)?;
+                if !ty.is_integer() {
+                    return Err(BlazeError::TypeError(ErrorCode::InvalidType, format!("enum type must be an integer type, found '{}'", ty.name()), ty.span()));
+                }
+                self.current_mut().enums.insert(name.clone(), (Some(ty), variants.clone()));
+            }
+            Statement::Union(name, types, _) => {
+                let mut resolved: Vec<Type> = Vec::new();
+                for ty in types {
+                    resolved.push(self.resolve_type(ty)?);
+                }
+                self.current_mut().unions.insert(name.clone(), resolved);
+            }
+            Statement::Function(name, parameters, returns, body, span) => {
+                let signature_parameters: Vec<(String, Type)> = parameters.iter()
+                    .map(|(name, ty, _, _)| (name.clone(), ty.clone()))
+                    .collect();
+                let signature: Signature = self.resolve_signature(&signature_parameters, returns)?;
+                if !function_generics(&signature.0, &signature.1).is_empty() {
+                    let qualified: String = self.current().qualify(name);
+                    self.declare_template(qualified, name, &signature, parameters.clone(), body, span);
+                }
+                self.current_mut().functions.insert(name.clone(), signature);
+            }
+            Statement::StructFunction(self_type, name, parameters, returns, body, span) => {
+                let self_type: Type = self.resolve_type(self_type)?;
+                let owner: String = method_owner(&self_type, span)?;
+                let mut all_parameters: Vec<(String, Type)> = vec![("self".to_string(), self_type.clone())];
+                all_parameters.extend(parameters.iter().map(|(name, ty, _, _)| (name.clone(), ty.clone())));
+                let signature: Signature = self.resolve_signature(&all_parameters, returns)?;
+
+                let (owner_path, owner_name) = split_qualified(&owner);
+                let key: String = format!("{}::{}", owner_name, name);
+                if !function_generics(&signature.0, &signature.1).is_empty() {
+                    let mut parameters: Vec<(String, Type, bool, Span)> = parameters.clone();
+                    parameters.insert(0, ("self".to_string(), self_type, false, span.clone()));
+                    self.declare_template(format!("{}::{}", owner, name), &key, &signature, parameters, body, span);
+                }
+                let namespace: &mut Scope = self.namespace_mut(&owner_path);
+                if namespace.functions.contains_key(&key) {
+                    return Err(BlazeError::TypeError(ErrorCode::DuplicateDefinition, format!("'{}' is already defined", key), span.clone()));
+                }
+                namespace.functions.insert(key, signature);
+            }
+            Statement::ConstantDeclaration(name, ty, value, span)
+            | Statement::VariableDeclaration(name, ty, value, span)
+            | Statement::MutableDeclaration(name, ty, value, span) => {
+                let (value, ty) = self.check_global(name, ty, value, span)?;
+                let scope: &mut Scope = self.current_mut();
+                match statement {
+                    Statement::ConstantDeclaration(..) => scope.consts.insert(name.clone(), (ty, value)),
+                    Statement::VariableDeclaration(..) => scope.variables.insert(name.clone(), (ty, value)),
+                    _ => scope.mutables.insert(name.clone(), (ty, value)),
+                };
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+    fn resolve_signature(&mut self, parameters: &[(String, Type)], returns: &[Type]) -> Result<Signature, BlazeError> {
+        let mut resolved_parameters: Vec<(String, Type)> = Vec::new();
+        for (name, ty) in parameters {
+            resolved_parameters.push((name.clone(), self.resolve_type(ty)?));
+        }
+        let mut resolved_returns: Vec<Type> = Vec::new();
+        for ty in returns {
+            resolved_returns.push(self.resolve_type(ty)?);
+        }
+        Ok((resolved_parameters, resolved_returns))
+    }
+    fn check_global(&mut self, name: &str, ty: &Type, value: &Expression, span: &Span) -> Result<(Expression, Type), BlazeError> {
+        self.function = self.current().qualify(name);
+        self.scope = Scope::new(None);
+        self.scope.path = self.path.clone();
+        self.check_declaration(name, ty, value, span)
+    }
+
+    fn resolve_type(&mut self, ty: &Type) -> Result<Type, BlazeError> {
+        match ty {
+            Type::Unknown(name, span) => {
+                let (prefix, last) = split_qualified(name);
+                if let Some(alias) = prefix.first() {
+                    self.use_import(alias);
+                }
+                for depth in (0..=self.path.len()).rev() {
+                    let mut path: Vec<String> = self.path[..depth].to_vec();
+                    path.extend(prefix.iter().cloned());
+                    let namespace: &Scope = match self.namespace(&path) {
+                        Some(namespace) => namespace,
+                        None => continue,
+                    };
+                    if let Some((generics, _)) = namespace.structs.get(&last) {
+                        if !generics.is_empty() {
+                            return Err(BlazeError::TypeError(ErrorCode::GenericArguments, format!("missing generic arguments for '{}'", name), span.clone()));
+                        }
+                    }
+                    if let Some(ty) = namespace.get_type(last.clone(), span.clone()) {
+                        return Ok(ty);
+                    }
+                }
+                Err(BlazeError::TypeError(ErrorCode::UnknownType, format!("unknown type '{}'", name), span.clone()))
+            }
+            Type::GenericInstance(name, arguments, span) => {
+                let qualified: String = match self.resolve_type(&Type::Unknown(name.clone(), span.clone())) {
+                    Ok(Type::Struct(qualified, _)) => qualified,
+                    Ok(_) => return Err(BlazeError::TypeError(ErrorCode::GenericArguments, format!("'{}' is not a generic struct", name), span.clone())),
+                    // Only a generic struct named without arguments fails this way.
+                    Err(BlazeError::TypeError(ErrorCode::GenericArguments, _, _)) => {
+                        self.qualify_struct(name).unwrap()
+                    }
+                    Err(error) => return Err(error),
+                };
+                let generics: usize = self.find_struct(&qualified).map(|(generics, _)| generics.len()).unwrap_or(0);
+                if generics != arguments.len() {
+                    return Err(BlazeError::TypeError(
+                        ErrorCode::GenericArguments,
+                        format!("'{}' expects {} generic arguments, but got {}", name, generics, arguments.len()),
+                        span.clone(),
+                    ));
+                }
+                let mut resolved: Vec<Type> = Vec::new();
+                for argument in arguments {
+                    resolved.push(self.resolve_type(argument)?);
+                }
+                Ok(Type::GenericInstance(qualified, resolved, span.clone()))
+            }
+            Type::Array(ty, span) => Ok(Type::Array(Box::new(self.resolve_type(ty)?), span.clone())),
+            Type::FixedArray(ty, length, span) => Ok(Type::FixedArray(Box::new(self.resolve_type(ty)?), *length, span.clone())),
+            Type::Pointer(ty, span) => Ok(Type::Pointer(Box::new(self.resolve_type(ty)?), span.clone())),
+            Type::Optional(ty, span) => Ok(Type::Optional(Box::new(self.resolve_type(ty)?), span.clone())),
+            Type::ErrorUnion(set, ty, span) => {
+                let set: Type = match self.resolve_type(set)? {
+                    set @ Type::ErrorSet(_, _) => set,
+                    other => return Err(BlazeError::TypeError(ErrorCode::InvalidType, format!("expected an error set before '!', found '{}'", other.name()), set.span())),
+                };
+                Ok(Type::ErrorUnion(Box::new(set), Box::new(self.resolve_type(ty)?), span.clone()))
+            }
+            Type::VarArgs(ty, span) => match *ty.clone() {
+                Some(ty) => Ok(Type::VarArgs(Box::new(Some(self.resolve_type(&ty)?)), span.clone())),
+                None => Ok(Type::VarArgs(Box::new(None), span.clone())),
+            },
+            Type::Generic(_, _) => Ok(ty.substitute(&self.substitution.0, &self.substitution.1)),
+            ty => Ok(ty.clone()),
+        }
+    }
+
+    fn namespace(&self, path: &[String]) -> Option<&Scope> {
+        let mut scope: &Scope = &self.root;
+        for segment in path {
+            scope = self.child(scope, segment)?;
+        }
+        Some(scope)
+    }
+    // Finds a nested namespace or the module an import alias refers to.
+    fn child<'a>(&'a self, scope: &'a Scope, name: &str) -> Option<&'a Scope> {
+        match scope.namespaces.get(name) {
+            Some(namespace) => Some(namespace),
+            None => self.root.namespaces.get(scope.aliases.get(name)?),
+        }
+    }
+    fn namespace_mut(&mut self, path: &[String]) -> &mut Scope {
+        let mut scope: &mut Scope = &mut self.root;
+        for segment in path {
+            scope = scope.namespaces.get_mut(segment).unwrap();
+        }
+        scope
+    }
+    fn current(&self) -> &Scope {
+        self.namespace(&self.path).unwrap()
+    }
+    fn current_mut(&mut self) -> &mut Scope {
+        let path: Vec<String> = self.path.clone();
+        self.namespace_mut(&path)
+    }
+    fn qualify_struct(&self, name: &str) -> Option<String> {
+        let (prefix, last) = split_qualified(name);
+        (0..=self.path.len()).rev()
+            .filter_map(|depth| {
+                let mut path: Vec<String> = self.path[..depth].to_vec();
+                path.extend(prefix.iter().cloned());
+                self.namespace(&path)
+            })
+            .find(|namespace| namespace.structs.contains_key(&last))
+            .map(|namespace| namespace.qualify(&last))
+    }
+    fn find_struct(&self, qualified: &str) -> Option<&(Vec<Type>, Vec<StructField>)> {
+        let (path, name) = split_qualified(qualified);
+        self.namespace(&path)?.structs.get(&name)
+    }
+    fn fields_of(&self, ty: &Type) -> Option<Vec<StructField>> {
+        match ty {
+            Type::Struct(name, _) => Some(self.find_struct(name)?.1.clone()),
+            Type::GenericInstance(name, arguments, _) => {
+                let (generics, fields) = self.find_struct(name)?;
+                Some(fields.iter().map(|field| StructField {
+                    name: field.name.clone(),
+                    ty: field.ty.substitute(generics, arguments),
+                    span: field.span.clone(),
+                }).collect())
+            }
+            _ => None,
+        }
+    }
+
+    fn check_declarations(&mut self, statements: &[Statement]) -> Vec<Statement> {
+        let mut checked: Vec<Statement> = Vec::new();
+        for statement in statements {
+            match self.check_declaration_statement(statement) {
+                Ok(statement) => checked.push(statement),
+                Err(error) => self.errors.push(error),
+            }
+        }
+        checked
+    }
+    fn check_declaration_statement(&mut self, statement: &Statement) -> Result<Statement, BlazeError> {
+        match statement {
+            Statement::Documented(documentation, statement, span) => {
+                let statement: Statement = self.check_declaration_statement(statement)?;
+                Ok(Statement::Documented(documentation.clone(), Box::new(statement), span.clone()))
+            }
+            Statement::Attribute(name, arguments, statement, span) if name == "extern" => match &**statement {
+                // There is no body to check, only the parameters to give the types resolved while declaring.
+                Statement::Function(function, parameters, _, _, function_span) => {
+                    let (signature_parameters, returns) = self.current().functions[function].clone();
+                    let parameters: Vec<(String, Type, bool, Span)> = parameters.iter().zip(signature_parameters)
+                        .map(|((name, _, comptime, span), (_, ty))| (name.clone(), ty, *comptime, span.clone()))
+                        .collect();
+                    let function: Statement = Statement::Function(function.clone(), parameters, returns, Vec::new(), function_span.clone());
+                    Ok(Statement::Attribute(name.clone(), arguments.clone(), Box::new(function), span.clone()))
+                }
+                statement => Ok(statement.clone()),
+            },
+            Statement::Attribute(name, arguments, statement, span) => {
+                let length: usize = self.push_lint_levels(name, arguments, span)?;
+                let statement: Result<Statement, BlazeError> = self.check_declaration_statement(statement);
+                self.lint_levels.truncate(length);
+                Ok(Statement::Attribute(name.clone(), arguments.clone(), Box::new(statement?), span.clone()))
+            }
+            Statement::Namespace(name, statements, span) => {
+                self.path.push(name.clone());
+                let statements: Vec<Statement> = self.check_declarations(statements);
+                self.path.pop();
+                Ok(Statement::Namespace(name.clone(), statements, span.clone()))
+            }
+            Statement::Struct(name, generics, inherits, _, span) => {
+                let fields: Vec<StructField> = self.current().structs[name].1.clone();
+                Ok(Statement::Struct(name.clone(), generics.clone(), inherits.clone(), fields, span.clone()))
+            }
+            Statement::Enum(name, _, span) => {
+                let variants: Vec<EnumVariant> = self.current().enums[name].1.clone();
+                Ok(Statement::Enum(name.clone(), variants, span.clone()))
+            }
+            Statement::TypedEnum(name, _, variants, span) => {
+                let ty: Type = self.current().enums[name].0.clone().unwrap();
+                self.function = self.current().qualify(name);
+                self.scope = Scope::new(None);
+                self.scope.path = self.path.clone();
+                let mut checked: Vec<EnumVariant> = Vec::new();
+                for variant in variants {
+                    match variant {
+                        EnumVariant::Expression(variant_name, value, variant_span) => {
+                            let (value, value_type) = self.check_expression(value, Some(&ty))?;
+                            self.expect_type(&ty, &value_type, &value.span())?;
+                            checked.push(EnumVariant::Expression(variant_name.clone(), value, variant_span.clone()));
+                        }
+                        variant => checked.push(variant.clone()),
+                    }
+                }
+                Ok(Statement::TypedEnum(name.clone(), ty, checked, span.clone()))
+            }
+            Statement::Union(name, _, span) => {
+                let types: Vec<Type> = self.current().unions[name].clone();
+                Ok(Statement::Union(name.clone(), types, span.clone()))
+            }
+            Statement::Function(name, parameters, _, body, span) => {
+                let (signature_parameters, returns) = self.current().functions[name].clone();
+                // Generic functions are checked once per instance, see `check_instances`.
+                let body: Vec<Statement> = if function_generics(&signature_parameters, &returns).is_empty() {
+                    let function: String = self.current().qualify(name);
+                    self.check_function(function, &signature_parameters, parameters, &returns, body)
+                } else {
+                    body.clone()
+                };
+                let parameters: Vec<(String, Type, bool, Span)> = parameters.iter().zip(signature_parameters)
+                    .map(|((name, _, comptime, span), (_, ty))| (name.clone(), ty, *comptime, span.clone()))
+                    .collect();
+                Ok(Statement::Function(name.clone(), parameters, returns, body, span.clone()))
+            }
+            Statement::StructFunction(self_type, name, parameters, _, body, span) => {
+                let self_type: Type = self.resolve_type(self_type)?;
+                let owner: String = method_owner(&self_type, span)?;
+                let (owner_path, owner_name) = split_qualified(&owner);
+                let (signature_parameters, returns) = self.namespace(&owner_path).unwrap()
+                    .functions[&format!("{}::{}", owner_name, name)].clone();
+                let mut all_parameters: Vec<(String, Type, bool, Span)> = vec![("self".to_string(), self_type.clone(), false, span.clone())];
+                all_parameters.extend(parameters.iter().cloned());
+                let body: Vec<Statement> = if function_generics(&signature_parameters, &returns).is_empty() {
+                    self.check_function(format!("{}::{}", owner, name), &signature_parameters, &all_parameters, &returns, body)
+                } else {
+                    body.clone()
+                };
+                let parameters: Vec<(String, Type, bool, Span)> = parameters.iter().zip(signature_parameters.into_iter().skip(1))
+                    .map(|((name, _, comptime, span), (_, ty))| (name.clone(), ty, *comptime, span.clone()))
+                    .collect();
+                Ok(Statement::StructFunction(self_type, name.clone(), parameters, returns, body, span.clone()))
+            }
+            Statement::ConstantDeclaration(name, _, _, span)
+            | Statement::VariableDeclaration(name, _, _, span)
+            | Statement::MutableDeclaration(name, _, _, span) => {
+                let scope: &Scope = self.current();
+                let (ty, value) = scope.consts.get(name)
+                    .or_else(|| scope.variables.get(name))
+                    .or_else(|| scope.mutables.get(name))
+                    .cloned()
+                    .unwrap();
+                Ok(match statement {
+                    Statement::ConstantDeclaration(..) => Statement::ConstantDeclaration(name.clone(), ty, value, span.clone()),
+                    Statement::VariableDeclaration(..) => Statement::VariableDeclaration(name.clone(), ty, value, span.clone()),
+                    _ => Statement::MutableDeclaration(name.clone(), ty, value, span.clone()),
+                })
+            }
+            statement => Ok(statement.clone()),
+        }
+    }
+    fn check_function(&mut self, function: String, signature: &[(String, Type)], parameters: &[(String, Type, bool, Span)], returns: &[Type], body: &[Statement]) -> Vec<Statement> {
+        self.function = function;
+        self.scope = Scope::new(None);
+        self.scope.path = self.path.clone();
+        for ((name, ty), (_, _, comptime, span)) in signature.iter().zip(parameters) {
+            self.scope.parameters.insert(name.clone(), (ty.clone(), *comptime, span.clone()));
+            if !*comptime && name != "self" {
+                self.declare_binding(name, span, true, false);
+            }
+        }
+        self.returns = returns.iter().filter(|ty| !matches!(ty, Type::Void(_))).cloned().collect();
+        self.loops.clear();
+        let body: Vec<Statement> = self.check_block(body);
+        self.returns.clear();
+        self.report_bindings();
+        body
+    }
+
+    // Checks the body of every generic function instance requested so far, with its generics substituted,
+    // and adds it next to the generic function. Instances can request further instances.
+    fn check_instances(&mut self, statements: &mut Vec<Statement>) {
+        let mut next: usize = 0;
+        while next < self.instances.len() {
+            let (template_name, arguments) = self.instances[next].clone();
+            next += 1;
+            let template: Template = self.templates[&template_name].clone();
+            self.path = template.path.clone();
+            self.lint_levels = template.lint_levels.clone();
+            let (signature_parameters, returns) = template.signature.clone();
+            let generics: Vec<Type> = function_generics(&signature_parameters, &returns);
+            let signature_parameters: Vec<(String, Type)> = signature_parameters.iter()
+                .map(|(name, ty)| (name.clone(), ty.substitute(&generics, &arguments)))
+                .collect();
+            let returns: Vec<Type> = returns.iter().map(|ty| ty.substitute(&generics, &arguments)).collect();
+
+            let errors: usize = self.errors.len();
+            let name: String = instance_name(&template, &arguments);
+            let function: String = template.path.iter().cloned().chain([name.clone()]).collect::<Vec<String>>().join("::");
+            self.substitution = (generics.clone(), arguments.clone());
+            let body: Vec<Statement> = self.check_function(function, &signature_parameters, &template.parameters, &returns, &template.body);
+            self.substitution = (Vec::new(), Vec::new());
+            let bindings: String = generics.iter().zip(&arguments)
+                .map(|(generic, argument)| format!("{} = {}", generic.name(), argument.name()))
+                .collect::<Vec<String>>()
+                .join(", ");
+            for error in &mut self.errors[errors..] {
+                *error = error.clone().with_note(&format!("in '{}' instantiated with {}", template_name, bindings));
+            }
+
+            let parameters: Vec<(String, Type, bool, Span)> = template.parameters.iter().zip(signature_parameters)
+                .map(|((name, _, comptime, span), (_, ty))| (name.clone(), ty, *comptime, span.clone()))
+                .collect();
+            let instance: Statement = Statement::Function(name, parameters, returns, body, template.span.clone());
+            insert_statement(statements, &template.path, instance);
+        }
+        self.path.clear();
+        self.lint_levels.clear();
+    }
+    fn declare_template(&mut self, qualified: String, name: &str, signature: &Signature, parameters: Vec<(String, Type, bool, Span)>, body: &[Statement], span: &Span) {
+        self.templates.insert(qualified, Template {
+            path: self.path.clone(),
+            name: name.to_string(),
+            lint_levels: self.lint_levels.clone(),
+            signature: signature.clone(),
+            parameters,
+            body: body.to_vec(),
+            span: span.clone(),
+        });
+    }
+    // Returns the name of the instance of a generic function, queueing it to be checked if it is new.
+    fn instantiate(&mut self, name: &str, arguments: Vec<Type>, span: &Span) -> Result<String, BlazeError> {
+        let template: &Template = &self.templates[name];
+        let mut segments: Vec<String> = template.path.clone();
+        segments.push(instance_name(template, &arguments));
+        let instance: String = segments.join("::");
+        let existing: bool = self.instances.iter().any(|(other, other_arguments)| {
+            other == name && other_arguments.iter().zip(&arguments).all(|(other, argument)| other.equals(argument.clone()))
+        });
+        if existing {
+            return Ok(instance);
+        }
+        if self.instances.len() >= MAX_INSTANCES || arguments.iter().map(type_size).sum::<usize>() > MAX_INSTANCE_TYPE_SIZE {
+            return Err(BlazeError::TypeError(ErrorCode::RecursiveInstance, format!("too many generic instances, does '{}' instantiate itself recursively?", name), span.clone()));
+        }
+        self.instances.push((name.to_string(), arguments));
+        Ok(instance)
+    }
+
+    fn check_block(&mut self, statements: &[Statement]) -> Vec<Statement> {
+        let mut checked: Vec<Statement> = Vec::new();
+        let mut returned: Option<(&str, Span)> = None;
+        let mut reported: bool = false;
+        for statement in statements {
+            if let Some((keyword, return_span)) = &returned {
+                if !reported && !matches!(statement, Statement::Error(_)) {
+                    let warning: BlazeError = BlazeError::Warning(Lint::UnreachableCode, "unreachable statement".to_string(), statement.span())
+                        .with_label(return_span.clone(), &format!("any code following this {} is unreachable", keyword));
+                    self.warn(Lint::UnreachableCode, &self.lint_levels.clone(), warning);
+                    reported = true;
+                }
+            }
+            match statement {
+                Statement::Return(_, span) => { returned.get_or_insert(("return", span.clone())); }
+                Statement::Break(_, span) => { returned.get_or_insert(("break", span.clone())); }
+                Statement::Continue(_, span) => { returned.get_or_insert(("continue", span.clone())); }
+                _ => {}
+            }
+            match self.check_statement(statement) {
+                Ok(statement) => checked.push(statement),
+                Err(error) => self.errors.push(error),
+            }
+        }
+        checked
+    }
+    fn check_nested_block(&mut self, statements: &[Statement]) -> Vec<Statement> {
+        self.scope.push();
+        let checked: Vec<Statement> = self.check_block(statements);
+        self.report_bindings();
+        self.scope.pop();
+        checked
+    }
+    fn check_statement(&mut self, statement: &Statement) -> Result<Statement, BlazeError> {
+        match statement {
+            Statement::Documented(documentation, statement, span) => {
+                let statement: Statement = self.check_statement(statement)?;
+                Ok(Statement::Documented(documentation.clone(), Box::new(statement), span.clone()))
+            }
+            Statement::Attribute(name, arguments, statement, span) => {
+                let length: usize = self.push_lint_levels(name, arguments, span)?;
+                let statement: Result<Statement, BlazeError> = self.check_statement(statement);
+                self.lint_levels.truncate(length);
+                Ok(Statement::Attribute(name.clone(), arguments.clone(), Box::new(statement?), span.clone()))
+            }
+            Statement::ConstantDeclaration(name, ty, value, span)
+            | Statement::VariableDeclaration(name, ty, value, span)
+            | Statement::MutableDeclaration(name, ty, value, span) => {
+                let mutable: bool = matches!(statement, Statement::MutableDeclaration(..));
+                let (value, ty) = match self.check_declaration(name, ty, value, span) {
+                    Ok(checked) => checked,
+                    // The name is still declared, with its type if it has one, so that its uses are not reported too.
+                    Err(error) => {
+                        let ty: Type = self.resolve_type(ty).unwrap_or(Type::AwaitingInference);
+                        self.declare_binding(name, span, false, mutable);
+                        if let Some(binding) = self.scope.binding_mut(name) {
+                            binding.used = true;
+                            binding.mutated = true;
+                        }
+                        let entry: (Type, Expression) = (ty, value.clone());
+                        match statement {
+                            Statement::ConstantDeclaration(..) => self.scope.consts.insert(name.clone(), entry),
+                            Statement::VariableDeclaration(..) => self.scope.variables.insert(name.clone(), entry),
+                            _ => self.scope.mutables.insert(name.clone(), entry),
+                        };
+                        return Err(error);
+                    }
+                };
+                let entry: (Type, Expression) = (ty.clone(), value.clone());
+                self.declare_binding(name, span, false, mutable);
+                Ok(match statement {
+                    Statement::ConstantDeclaration(..) => {
+                        self.scope.consts.insert(name.clone(), entry);
+                        Statement::ConstantDeclaration(name.clone(), ty, value, span.clone())
+                    }
+                    Statement::VariableDeclaration(..) => {
+                        self.scope.variables.insert(name.clone(), entry);
+                        Statement::VariableDeclaration(name.clone(), ty, value, span.clone())
+                    }
+                    _ => {
+                        self.scope.mutables.insert(name.clone(), entry);
+                        Statement::MutableDeclaration(name.clone(), ty, value, span.clone())
+                    }
+                })
+            }
+            Statement::Return(_, span) if self.deferring => {
+                Err(BlazeError::TypeError(ErrorCode::InvalidJump, "cannot 'return' from a deferred statement".to_string(), span.clone()))
+            }
+            Statement::Destructuring(names, value, span) => {
+                let (value, types) = self.check_values(value)?;
+                if types.len() != names.len() {
+                    return Err(BlazeError::TypeError(
+                        ErrorCode::ValueCount,
+                        format!("expected {} values to destructure, but got {}", names.len(), types.len()),
+                        value.span(),
+                    ).with_label(span.clone(), &format!("{} names are declared here", names.len())));
+                }
+                for ((name, mutable, name_span), ty) in names.iter().zip(types) {
+                    if name == "_" {
+                        continue;
+                    }
+                    self.declare_binding(name, name_span, false, *mutable);
+                    let entry: (Type, Expression) = (ty, Expression::Identifier(name.clone(), name_span.clone()));
+                    match mutable {
+                        true => self.scope.mutables.insert(name.clone(), entry),
+                        false => self.scope.variables.insert(name.clone(), entry),
+                    };
+                }
+                Ok(Statement::Destructuring(names.clone(), value, span.clone()))
+            }
+            // A function returning several values can return the values of a call that returns the same types.
+            Statement::Return(values, span) if self.returns.len() > 1 && matches!(values.as_slice(), [Expression::Call(..)]) => {
+                let (value, types) = self.check_values(&values[0])?;
+                if types.len() != self.returns.len() {
+                    return Err(BlazeError::TypeError(
+                        ErrorCode::ValueCount,
+                        format!("expected {} return values, but got {}", self.returns.len(), types.len()),
+                        value.span(),
+                    ));
+                }
+                for (expected, ty) in self.returns.clone().iter().zip(types) {
+                    self.expect_type(expected, &ty, &value.span())
+                        .map_err(|error| error.with_label(expected.span(), "expected because of this return type"))?;
+                }
+                Ok(Statement::Return(vec![value], span.clone()))
+            }
+            Statement::Return(values, span) => {
+                let succeeds: bool = values.is_empty() && matches!(self.returns.as_slice(), [Type::ErrorUnion(_, payload, _)] if matches!(**payload, Type::Void(_)));
+                if values.len() != self.returns.len() && !succeeds {
+                    return Err(BlazeError::TypeError(
+                        ErrorCode::ValueCount,
+                        format!("expected {} return values, but got {}", self.returns.len(), values.len()),
+                        span.clone(),
+                    ));
+                }
+                let mut checked: Vec<Expression> = Vec::new();
+                for (value, expected) in values.iter().zip(self.returns.clone()) {
+                    let (value, ty) = self.check_expression(value, Some(&expected))?;
+                    self.expect_type(&expected, &ty, &value.span())
+                        .map_err(|error| error.with_label(expected.span(), "expected because of this return type"))?;
+                    checked.push(value);
+                }
+                Ok(Statement::Return(checked, span.clone()))
+            }
+            Statement::While(..) | Statement::For(..) => self.check_loop(None, statement),
+            Statement::Labeled(label, statement, span) => {
+                if self.loops.contains(&Some(label.clone())) {
+                    return Err(BlazeError::TypeError(ErrorCode::DuplicateDefinition, format!("label '{}' is already used by an enclosing loop", label), span.clone()));
+                }
+                let statement: Statement = self.check_loop(Some(label.clone()), statement)?;
+                Ok(Statement::Labeled(label.clone(), Box::new(statement), span.clone()))
+            }
+            Statement::Break(label, span) | Statement::Continue(label, span) => {
+                let keyword: &str = if let Statement::Break(..) = statement { "break" } else { "continue" };
+                if self.loops.is_empty() && self.deferring {
+                    return Err(BlazeError::TypeError(ErrorCode::InvalidJump, format!("cannot '{}' out of a deferred statement", keyword), span.clone()));
+                }
+                if self.loops.is_empty() {
+                    return Err(BlazeError::TypeError(ErrorCode::InvalidJump, format!("'{}' outside of a loop", keyword), span.clone()));
+                }
+                if let Some(label) = label {
+                    if !self.loops.contains(&Some(label.clone())) {
+                        return Err(BlazeError::TypeError(ErrorCode::InvalidJump, format!("undefined label '{}'", label), span.clone())
+                            .with_help(&format!("'{}' can only name a loop it is inside of", keyword)));
+                    }
+                }
+                Ok(statement.clone())
+            }
+            Statement::If(condition, capture, then_body, else_body, span) => {
+                let (narrowed, otherwise) = (condition.narrowed(true), condition.narrowed(false));
+                let (condition, captured) = self.check_captured_condition(condition, capture, "if")?;
+                self.scope.push();
+                self.declare_pattern_bindings(captured);
+                self.narrow(&narrowed, span);
+                let then_body: Vec<Statement> = self.check_block(then_body);
+                self.report_bindings();
+                self.scope.pop();
+                self.scope.push();
+                self.narrow(&otherwise, span);
+                let else_body: Vec<Statement> = self.check_block(else_body);
+                self.report_bindings();
+                self.scope.pop();
+                Ok(Statement::If(condition, capture.clone(), then_body, else_body, span.clone()))
+            }
+            Statement::Match(value, arms, span) => {
+                let (value, enum_name, variants) = self.check_match_value(value)?;
+                let mut checked: Vec<(Pattern, Vec<Statement>, Span)> = Vec::new();
+                let mut covered: Vec<String> = Vec::new();
+                for (pattern, body, arm_span) in arms {
+                    let (pattern, bindings) = self.check_pattern(pattern, &enum_name, &variants, &mut covered)?;
+                    self.scope.push();
+                    self.declare_pattern_bindings(bindings);
+                    let body: Vec<Statement> = self.check_block(body);
+                    self.report_bindings();
+                    self.scope.pop();
+                    checked.push((pattern, body, arm_span.clone()));
+                }
+                check_exhaustive(&enum_name, &variants, &covered, span)?;
+                Ok(Statement::Match(value, checked, span.clone()))
+            }
+            Statement::Block(body, span) => Ok(Statement::Block(self.check_nested_block(body), span.clone())),
+            // A deferred statement runs when its block exits, so it cannot leave that block itself.
+            Statement::Defer(deferred, span) => {
+                if matches!(**deferred, Statement::ConstantDeclaration(..) | Statement::VariableDeclaration(..) | Statement::MutableDeclaration(..)) {
+                    return Err(BlazeError::TypeError(ErrorCode::MisplacedDeclaration, "expected a statement to defer, found a declaration".to_string(), deferred.span()));
+                }
+                let loops: Vec<Option<String>> = std::mem::take(&mut self.loops);
+                let deferring: bool = std::mem::replace(&mut self.deferring, true);
+                let deferred: Vec<Statement> = self.check_nested_block(std::slice::from_ref(&**deferred));
+                self.deferring = deferring;
+                self.loops = loops;
+                Ok(Statement::Defer(Box::new(deferred.into_iter().next().unwrap_or(Statement::Error(span.clone()))), span.clone()))
+            }
+            Statement::Expression(expression, span) => {
+                let (expression, types) = self.check_values(expression)?;
+                if types.iter().any(|ty| matches!(ty, Type::ErrorUnion(_, _, _))) {
+                    return Err(BlazeError::TypeError(ErrorCode::UnhandledError, "the error from this call is ignored".to_string(), expression.span())
+                        .with_help("handle it with 'try' or 'catch'"));
+                }
+                Ok(Statement::Expression(expression, span.clone()))
+            }
+            Statement::Error(span) => Ok(Statement::Error(span.clone())),
+            _ => Err(BlazeError::TypeError(ErrorCode::MisplacedDeclaration, "declarations are only allowed at the top level or in a namespace".to_string(), statement.span())),
+        }
+    }
+    // Checks the condition of an `if` or `while`, which with a capture like `|v|` is an optional whose value it binds.
+    fn check_captured_condition(&mut self, condition: &Expression, capture: &Option<(String, Span)>, keyword: &str) -> Result<(Expression, PatternBindings), BlazeError> {
+        match capture {
+            Some((name, capture_span)) => {
+                let (condition, ty) = self.check_expression(condition, None)?;
+                let ty: Type = unwrapped(&ty, &format!("to unwrap in '{}'", keyword), &condition.span())?;
+                Ok((condition, vec![(name.clone(), ty, capture_span.clone())]))
+            }
+            None => Ok((self.check_condition(condition)?, Vec::new())),
+        }
+    }
+    // Lets the optionals a condition has checked against `null` be used as their values.
+    fn narrow(&mut self, names: &[String], span: &Span) {
+        for name in names {
+            if let Ok((_, Type::Optional(ty, _))) = self.lookup_value(name, span) {
+                self.scope.narrowed.insert(name.clone(), *ty);
+            }
+        }
+    }
+    fn check_loop(&mut self, label: Option<String>, statement: &Statement) -> Result<Statement, BlazeError> {
+        match statement {
+            Statement::While(condition, capture, body, span) => {
+                let narrowed: Vec<String> = condition.narrowed(true);
+                let (condition, captured) = self.check_captured_condition(condition, capture, "while")?;
+                self.loops.push(label);
+                self.scope.push();
+                self.declare_pattern_bindings(captured);
+                self.narrow(&narrowed, span);
+                let body: Vec<Statement> = self.check_block(body);
+                self.report_bindings();
+                self.scope.pop();
+                self.loops.pop();
+                Ok(Statement::While(condition, capture.clone(), body, span.clone()))
+            }
+            Statement::For((name, name_span), iterable, body, span) => {
+                let (iterable, ty) = match iterable {
+                    Expression::Range(start, end, range_span) => {
+                        let (start, start_type, end, end_type) = self.check_operands(start, end, None)?;
+                        if !start_type.is_integer() {
+                            return Err(BlazeError::TypeError(ErrorCode::MismatchedTypes, format!("expected an integer range, found '{}'", start_type.name()), start.span()));
+                        }
+                        self.expect_type(&start_type, &end_type, &end.span())?;
+                        (Expression::Range(Box::new(start), Box::new(end), range_span.clone()), start_type)
+                    }
+                    iterable => match self.check_expression(iterable, None)? {
+                        (iterable, Type::Array(element, _) | Type::FixedArray(element, _, _)) => (iterable, *element),
+                        (iterable, Type::Str(span)) => (iterable, Type::Char(span)),
+                        (iterable, ty) => return Err(BlazeError::TypeError(ErrorCode::InvalidOperand, format!("cannot iterate over a value of type '{}'", ty.name()), iterable.span())
+                            .with_help("'for' loops over a range like '0..n' or an array")),
+                    },
+                };
+                self.loops.push(label);
+                self.scope.push();
+                if name != "_" {
+                    self.declare_binding(name, name_span, false, false);
+                    self.scope.variables.insert(name.clone(), (ty, Expression::Identifier(name.clone(), name_span.clone())));
+                }
+                let body: Vec<Statement> = self.check_nested_block(body);
+                self.report_bindings();
+                self.scope.pop();
+                self.loops.pop();
+                Ok(Statement::For((name.clone(), name_span.clone()), iterable, body, span.clone()))
+            }
+            _ => unreachable!("Checker::check_loop()"),
+        }
+    }
+    // Applies a `#allow(...)`, `#warn(...)` or `#deny(...)` attribute until the caller truncates back to the returned length.
+    fn push_lint_levels(&mut self, name: &str, arguments: &[String], span: &Span) -> Result<usize, BlazeError> {
+        let level: Level = Level::parse(name)
+            .ok_or_else(|| BlazeError::TypeError(ErrorCode::UnknownAttribute, format!("unknown attribute '{}'", name), span.clone()))?;
+        let mut levels: LintLevels = Vec::new();
+        for argument in arguments {
+            let lints: Vec<Lint> = Lint::parse(argument)
+                .ok_or_else(|| BlazeError::TypeError(ErrorCode::UnknownAttribute, format!("unknown lint '{}'", argument), span.clone()))?;
+            levels.extend(lints.into_iter().map(|lint| (lint, level)));
+        }
+        let length: usize = self.lint_levels.len();
+        self.lint_levels.extend(levels);
+        Ok(length)
+    }
+    fn warn(&mut self, lint: Lint, levels: &[(Lint, Level)], warning: BlazeError) {
+        let level: Level = levels.iter().rev()
+            .find(|(other, _)| *other == lint)
+            .map(|(_, level)| *level)
+            .unwrap_or_else(|| self.lints.level(lint));
+        match level {
+            Level::Allow => {}
+            // Generic functions are checked once per instance, so their warnings can repeat.
+            Level::Warn if self.warnings.iter().any(|other| other.message() == warning.message() && other.span() == warning.span()) => {}
+            Level::Warn => self.warnings.push(warning),
+            Level::Deny => self.errors.push(warning.denied(lint)),
+        }
+    }
+    fn declare_binding(&mut self, name: &str, span: &Span, parameter: bool, mutable: bool) {
+        let outer: Option<Span> = match &*self.scope.parent {
+            Some(parent) => parent.binding(name).map(|binding| binding.span.clone()),
+            None => None,
+        }.or_else(|| self.scope.bindings.get(name).filter(|binding| binding.parameter).map(|binding| binding.span.clone()));
+        if let Some(outer) = outer.filter(|_| !parameter && !name.starts_with('_')) {
+            let warning: BlazeError = BlazeError::Warning(Lint::Shadowing, format!("'{}' shadows a binding from an outer scope", name), span.clone())
+                .with_label(outer, "previously declared here");
+            self.warn(Lint::Shadowing, &self.lint_levels.clone(), warning);
+        }
+        let binding: Binding = Binding {
+            span: span.clone(),
+            parameter,
+            mutable,
+            used: false,
+            mutated: false,
+            lints: self.lint_levels.clone(),
+        };
+        if let Some(previous) = self.scope.bindings.insert(name.to_string(), binding) {
+            self.report_binding(name, previous);
+        }
+    }
+    fn report_bindings(&mut self) {
+        let mut bindings: Vec<(String, Binding)> = std::mem::take(&mut self.scope.bindings).into_iter().collect();
+        bindings.sort_by_key(|(_, binding)| binding.span.start);
+        for (name, binding) in bindings {
+            self.report_binding(&name, binding);
+        }
+    }
+    fn report_binding(&mut self, name: &str, binding: Binding) {
+        if name.starts_with('_') {
+            return;
+        }
+        if !binding.used {
+            let (lint, kind) = if binding.parameter {
+                (Lint::UnusedParameters, "parameter")
+            } else {
+                (Lint::UnusedVariables, "variable")
+            };
+            let warning: BlazeError = BlazeError::Warning(lint, format!("unused {} '{}'", kind, name), binding.span.clone())
+                .with_suggestion(binding.span.clone(), &format!("_{}", name), "if this is intentional, prefix it with an underscore");
+            self.warn(lint, &binding.lints, warning);
+        } else if binding.mutable && !binding.mutated {
+            let warning: BlazeError = BlazeError::Warning(Lint::UnusedMut, format!("'{}' is declared 'mut' but never mutated", name), binding.span.clone())
+                .with_help("remove 'mut'");
+            self.warn(Lint::UnusedMut, &binding.lints, warning);
+        }
+    }
+    fn report_imports(&mut self) {
+        let mut imports: Vec<(String, (Span, bool, LintLevels))> = std::mem::take(&mut self.imports).into_iter().collect();
+        imports.sort_by_key(|(_, (span, _, _))| (span.file, span.start));
+        for (name, (span, used, levels)) in imports {
+            let (_, alias) = split_qualified(&name);
+            if !used && !alias.starts_with('_') {
+                let warning: BlazeError = BlazeError::Warning(Lint::UnusedImports, format!("unused import '{}'", alias), span);
+                self.warn(Lint::UnusedImports, &levels, warning);
+            }
+        }
+    }
+    fn use_binding(&mut self, name: &str) {
+        if let Some(binding) = self.scope.binding_mut(name) {
+            binding.used = true;
+        }
+    }
+    // Marks the variable at the root of a place expression as mutated, e.g. `p` in `p.x = 1`.
+    fn mutate_binding(&mut self, target: &Expression) {
+        match target {
+            Expression::Identifier(name, _) => {
+                if let Some(binding) = self.scope.binding_mut(name) {
+                    binding.mutated = true;
+                }
+            }
+            Expression::MemberAccess(object, _, _) | Expression::ArrayAccess(object, _, _) => self.mutate_binding(object),
+            _ => {}
+        }
+    }
+    // Marks the import an `alias::member` path goes through as used.
+    fn use_import(&mut self, alias: &str) {
+        for depth in (0..=self.path.len()).rev() {
+            let mut path: Vec<String> = self.path[..depth].to_vec();
+            path.push(alias.to_string());
+            if let Some((_, used, _)) = self.imports.get_mut(&path.join("::")) {
+                *used = true;
+                return;
+            }
+        }
+    }
+
+    fn check_declaration(&mut self, name: &str, ty: &Type, value: &Expression, span: &Span) -> Result<(Expression, Type), BlazeError> {
+        let expected: Option<Type> = if *ty == Type::AwaitingInference {
+            None
+        } else {
+            Some(self.resolve_type(ty)?)
+        };
+        let (value, value_type) = self.check_expression(value, expected.as_ref())?;
+        match expected {
+            Some(expected) => {
+                self.expect_type(&expected, &value_type, &value.span())
+                    .map_err(|error| error.with_label(ty.span(), "expected due to this type"))?;
+                Ok((value, expected))
+            }
+            None => match value_type {
+                Type::Void(_) => Err(BlazeError::TypeError(ErrorCode::VoidValue, format!("cannot declare '{}' with a value of type 'void'", name), span.clone())),
+                Type::Pointer(ty, _) if matches!(*ty, Type::Void(_)) && matches!(value, Expression::Null(_)) => {
+                    Err(BlazeError::TypeError(ErrorCode::CannotInfer, format!("cannot infer the type of '{}' from 'null'", name), span.clone()))
+                }
+                ty => {
+                    if matches!(ty, Type::I64(_)) && is_unsuffixed_integer(&value) {
+                        self.defaulted.insert(ty.span(), value.last_span());
+                    }
+                    Ok((value, ty))
+                }
+            },
+        }
+    }
+    fn check_condition(&mut self, condition: &Expression) -> Result<Expression, BlazeError> {
+        let span: Span = condition.span();
+        let (condition, ty) = self.check_expression(condition, Some(&Type::Bool(span.clone())))?;
+        self.expect_type(&Type::Bool(span.clone()), &ty, &span)?;
+        Ok(condition)
+    }
+
+    fn check_expression(&mut self, expression: &Expression, expected: Option<&Type>) -> Result<(Expression, Type), BlazeError> {
+        let (expression, ty) = match self.infer_expression(expression, expected) {
+            Ok(checked) => checked,
+            // Using a value without a type, like a local whose declaration has errors, is not reported again.
+            Err(_) if expression.operands().into_iter().any(|operand| matches!(self.types.get(&self.function, operand), Some(Type::AwaitingInference))) => {
+                (expression.clone(), Type::AwaitingInference)
+            }
+            Err(error) => return Err(error),
+        };
+        self.record(&expression, &ty);
+        Ok((expression, ty))
+    }
+    fn record(&mut self, expression: &Expression, ty: &Type) {
+        self.types.expressions.insert(key(&self.function, expression), ty.clone());
+    }
+    fn infer_expression(&mut self, expression: &Expression, expected: Option<&Type>) -> Result<(Expression, Type), BlazeError> {
+        // A value stored in an error union is checked against its payload, except for calls that may return the union itself.
+        // The same goes for optionals, except where `null` can take the place of the value.
+        let expected: Option<&Type> = match expected {
+            Some(Type::ErrorUnion(_, payload, _)) if !matches!(expression, Expression::Call(..)) => Some(payload),
+            Some(Type::Optional(ty, _)) if !matches!(expression, Expression::Call(..) | Expression::Null(_) | Expression::Match(..) | Expression::Catch(..)) => Some(ty),
+            expected => expected,
+        };
+        match expression {
+            Expression::SelfLiteral(span) => match self.scope.locate("self".to_string(), span.clone()) {
+                Some((ty, _)) => Ok((expression.clone(), ty)),
+                None => Err(BlazeError::TypeError(ErrorCode::SelfOutsideMethod, "'self' is only available in methods".to_string(), span.clone())),
+            },
+            Expression::Identifier(name, span) => {
+                self.use_binding(name);
+                let (qualified, ty) = self.lookup_value(name, span)?;
+                let checked: Expression = Expression::Identifier(qualified, span.clone());
+                if self.scope.is_narrowed(name) {
+                    self.types.narrowed.insert(key(&self.function, &checked));
+                }
+                Ok((checked, ty))
+            }
+            Expression::Integer(value, suffix, span) => {
+                let ty: Type = check_integer(*value, suffix.as_ref(), expected, false, span)?;
+                Ok((expression.clone(), ty))
+            }
+            Expression::Float(value, suffix, span) => {
+                let ty: Type = match suffix {
+                    Some(ty) => ty.clone(),
+                    None => match expected {
+                        Some(ty) if ty.is_float() => with_span(ty, span),
+                        _ => Type::F64(span.clone()),
+                    },
+                };
+                let limit: f64 = if matches!(ty, Type::F32(_)) { f32::MAX as f64 } else { f64::MAX };
+                if !value.is_finite() || value.abs() > limit {
+                    return Err(BlazeError::TypeError(ErrorCode::LiteralOutOfRange, format!("float literal does not fit in '{}'", ty.name()), span.clone()));
+                }
+                Ok((expression.clone(), ty))
+            }
+            Expression::Bool(_, span) => Ok((expression.clone(), Type::Bool(span.clone()))),
+            Expression::Char(_, span) => Ok((expression.clone(), Type::Char(span.clone()))),
+            // A string literal where a C string is expected is that C string, which a 'str' value is not.
+            Expression::String(_, span) => match expected {
+                Some(ty @ Type::Pointer(inner, _)) if matches!(**inner, Type::Char(_)) => Ok((expression.clone(), with_span(ty, span))),
+                _ => Ok((expression.clone(), Type::Str(span.clone()))),
+            },
+            Expression::StaticMemberAccess(left, right, span) => {
+                if let Ok(segments) = static_path(left) {
+                    self.use_import(&segments[0]);
+                }
+                if let Expression::StructLiteral(name, _, fields, _) = &**right {
+                    let name: String = format!("{}::{}", static_path(left)?.join("::"), name);
+                    return self.check_struct_literal(&name, fields, expected, span);
+                }
+                let segments: Vec<String> = static_path(expression)?;
+                let (qualified, ty) = self.lookup_static(&segments, span)?;
+                Ok((Expression::Identifier(qualified, span.clone()), ty))
+            }
+            Expression::MemberAccess(object, member, span) => {
+                let field: String = match &**member {
+                    Expression::Identifier(field, _) => field.clone(),
+                    _ => return Err(BlazeError::TypeError(ErrorCode::ExpectedName, "expected a field name".to_string(), member.span())),
+                };
+                let (object, object_type) = self.check_expression(object, None)?;
+                if let Type::Optional(_, _) = object_type {
+                    return Err(not_narrowed(&format!("access field '{}' on", field), &object_type, &object, span));
+                }
+                let target: &Type = if let Type::Pointer(ty, _) = &object_type { ty } else { &object_type };
+                let ty: Type = match target {
+                    Type::Array(_, _) | Type::FixedArray(_, _, _) | Type::Str(_) if field == "len" => Type::U64(span.clone()),
+                    _ => self.field_type(&object_type, &field, span)?,
+                };
+                Ok((Expression::MemberAccess(Box::new(object), member.clone(), span.clone()), ty))
+            }
+            Expression::BinaryOperation(left, operator, right, span) => self.check_binary_operation(left, operator, right, expected, span),
+            Expression::UnaryOperation(operator, value, span) => self.check_unary_operation(operator, value, expected, span),
+            Expression::ArrayAccess(object, index, span) => {
+                let (object, object_type) = self.check_expression(object, None)?;
+                let element: Type = match &object_type {
+                    Type::Array(ty, _) | Type::FixedArray(ty, _, _) | Type::Pointer(ty, _) => *ty.clone(),
+                    Type::Str(_) => Type::Char(span.clone()),
+                    Type::Optional(_, _) => return Err(not_narrowed("index into", &object_type, &object, span)),
+                    ty => return Err(BlazeError::TypeError(ErrorCode::InvalidOperand, format!("cannot index into a value of type '{}'", ty.name()), span.clone())),
+                };
+                let index: Expression = self.check_index(index, "index", fixed_length(&object_type), false)?;
+                Ok((Expression::ArrayAccess(Box::new(object), Box::new(index), span.clone()), element))
+            }
+            Expression::Slice(object, start, end, span) => {
+                let (object, object_type) = self.check_expression(object, None)?;
+                let sliced: Type = match &object_type {
+                    Type::Array(ty, _) | Type::FixedArray(ty, _, _) => Type::Array(ty.clone(), span.clone()),
+                    Type::Str(_) => Type::Str(span.clone()),
+                    Type::Optional(_, _) => return Err(not_narrowed("slice", &object_type, &object, span)),
+                    ty => return Err(BlazeError::TypeError(ErrorCode::InvalidOperand, format!("cannot slice a value of type '{}'", ty.name()), span.clone())
+                        .with_help("only arrays, slices and strings can be sliced")),
+                };
+                let length: Option<usize> = fixed_length(&object_type);
+                let start: Option<Box<Expression>> = match start {
+                    Some(start) => Some(Box::new(self.check_index(start, "slice start", length, true)?)),
+                    None => None,
+                };
+                let end: Option<Box<Expression>> = match end {
+                    Some(end) => Some(Box::new(self.check_index(end, "slice end", length, true)?)),
+                    None => None,
+                };
+                if let (Some(Expression::Integer(first, _, _)), Some(Expression::Integer(last, _, last_span))) = (start.as_deref(), end.as_deref()) {
+                    if first > last {
+                        return Err(BlazeError::TypeError(ErrorCode::IndexOutOfBounds, format!("slice start {} is greater than its end {}", first, last), last_span.clone()));
+                    }
+                }
+                Ok((Expression::Slice(Box::new(object), start, end, span.clone()), sliced))
+            }
+            Expression::ArrayLiteral(elements, span) => {
+                let mut element: Option<Type> = None;
+                if let Some(ty @ Type::FixedArray(expected_element, length, _)) = expected {
+                    if *length != elements.len() {
+                        return Err(BlazeError::TypeError(
+                            ErrorCode::MismatchedTypes,
+                            format!("expected {} elements for '{}', but the array literal has {}", length, ty.name(), elements.len()),
+                            span.clone(),
+                        ));
+                    }
+                    element = Some(*expected_element.clone());
+                }
+                if elements.is_empty() {
+                    return Err(BlazeError::TypeError(ErrorCode::CannotInfer, "an array literal needs at least one element".to_string(), span.clone()));
+                }
+                let mut checked: Vec<Expression> = Vec::new();
+                for value in elements {
+                    let (value, ty) = self.check_expression(value, element.as_ref())?;
+                    match &element {
+                        Some(element) => self.expect_type(element, &ty, &value.span())?,
+                        None => element = Some(ty),
+                    }
+                    checked.push(value);
+                }
+                let ty: Type = Type::FixedArray(Box::new(element.unwrap()), checked.len(), span.clone());
+                Ok((Expression::ArrayLiteral(checked, span.clone()), ty))
+            }
+            Expression::Call(callee, arguments, span) => self.check_call(callee, arguments, expected, span),
+            Expression::StructLiteral(name, _, fields, span) => self.check_struct_literal(name, fields, expected, span),
+            Expression::AddressOf(value, span) => {
+                let inner: Option<Type> = match expected {
+                    Some(Type::Pointer(ty, _)) => Some(*ty.clone()),
+                    _ => None,
+                };
+                self.mutate_binding(value);
+                let (value, ty) = self.check_expression(value, inner.as_ref())?;
+                Ok((Expression::AddressOf(Box::new(value), span.clone()), Type::Pointer(Box::new(ty), span.clone())))
+            }
+            Expression::Dereference(value, span) => {
+                let (value, ty) = self.check_expression(value, None)?;
+                match ty {
+                    Type::Optional(_, _) => Err(not_narrowed("dereference", &ty, &value, span)),
+                    Type::Pointer(ty, _) if !matches!(*ty, Type::Void(_)) => Ok((Expression::Dereference(Box::new(value), span.clone()), *ty)),
+                    ty => Err(BlazeError::TypeError(ErrorCode::InvalidOperand, format!("cannot dereference a value of type '{}'", ty.name()), span.clone())),
+                }
+            }
+            Expression::Try(_, span) if self.deferring => {
+                Err(BlazeError::TypeError(ErrorCode::InvalidJump, "cannot use 'try' in a deferred statement".to_string(), span.clone()))
+            }
+            Expression::Try(value, span) => {
+                let hint: Option<Type> = expected.map(|ty| any_error(ty, span));
+                let (value, ty) = self.check_expression(value, hint.as_ref())?;
+                let (set, payload) = error_union(&ty, "after 'try'", &value.span())?;
+                match self.returns.as_slice() {
+                    [Type::ErrorUnion(returned, _, _)] if assignable(returned, &set) => {}
+                    [Type::ErrorUnion(returned, _, return_span)] => {
+                        return Err(BlazeError::TypeError(
+                            ErrorCode::InvalidTry,
+                            format!("'try' cannot return '{}' from a function that returns '{}'", set.name(), returned.name()),
+                            span.clone(),
+                        ).with_label(return_span.clone(), "expected because of this return type"));
+                    }
+                    _ => {
+                        return Err(BlazeError::TypeError(ErrorCode::InvalidTry, "'try' can only be used in a function that returns an error union".to_string(), span.clone())
+                            .with_help("change the return type to '!T'"));
+                    }
+                }
+                Ok((Expression::Try(Box::new(value), span.clone()), payload))
+            }
+            Expression::Catch(value, capture, fallback, span) => {
+                let hint: Option<Type> = expected.map(|ty| any_error(ty, span));
+                let (value, ty) = self.check_expression(value, hint.as_ref())?;
+                let (set, payload) = error_union(&ty, "before 'catch'", &value.span())?;
+                self.scope.push();
+                if let Some((name, capture_span)) = capture {
+                    self.declare_pattern_bindings(vec![(name.clone(), set, capture_span.clone())]);
+                }
+                let result: Result<(Expression, Type), BlazeError> = self.check_expression(fallback, Some(&payload));
+                self.report_bindings();
+                self.scope.pop();
+                let (fallback, fallback_type) = result?;
+                self.expect_type(&payload, &fallback_type, &fallback.span())?;
+                Ok((Expression::Catch(Box::new(value), capture.clone(), Box::new(fallback), span.clone()), payload))
+            }
+            Expression::Match(value, arms, span) => {
+                let (value, enum_name, variants) = self.check_match_value(value)?;
+                let mut checked: Vec<(Pattern, Expression, Span)> = Vec::new();
+                let mut covered: Vec<String> = Vec::new();
+                let mut ty: Option<Type> = None;
+                for (pattern, arm, arm_span) in arms {
+                    let (pattern, bindings) = self.check_pattern(pattern, &enum_name, &variants, &mut covered)?;
+                    self.scope.push();
+                    self.declare_pattern_bindings(bindings);
+                    let result: Result<(Expression, Type), BlazeError> = self.check_expression(arm, ty.as_ref().or(expected));
+                    self.report_bindings();
+                    self.scope.pop();
+                    let (arm, arm_type) = result?;
+                    match &ty {
+                        Some(ty) => self.expect_type(ty, &arm_type, &arm.span())?,
+                        None => ty = Some(arm_type),
+                    }
+                    checked.push((pattern, arm, arm_span.clone()));
+                }
+                check_exhaustive(&enum_name, &variants, &covered, span)?;
+                let ty: Type = ty.ok_or_else(|| BlazeError::TypeError(ErrorCode::NonExhaustiveMatch, "a 'match' expression needs at least one arm".to_string(), span.clone()))?;
+                Ok((Expression::Match(Box::new(value), checked, span.clone()), ty))
+            }
+            Expression::Range(_, _, span) => Err(BlazeError::TypeError(ErrorCode::MisplacedRange, "ranges are only allowed in 'for' loops".to_string(), span.clone())),
+            Expression::Orelse(value, fallback, span) => {
+                let hint: Option<Type> = expected.map(|ty| Type::Optional(Box::new(ty.clone()), span.clone()));
+                let (value, ty) = self.check_expression(value, hint.as_ref())?;
+                let ty: Type = unwrapped(&ty, "before 'orelse'", &value.span())?;
+                let (fallback, fallback_type) = self.check_expression(fallback, Some(&ty))?;
+                self.expect_type(&ty, &fallback_type, &fallback.span())?;
+                Ok((Expression::Orelse(Box::new(value), Box::new(fallback), span.clone()), ty))
+            }
+            Expression::Unwrap(value, span) => {
+                let hint: Option<Type> = expected.map(|ty| Type::Optional(Box::new(ty.clone()), span.clone()));
+                let (value, ty) = self.check_expression(value, hint.as_ref())?;
+                let ty: Type = unwrapped(&ty, "before '.?'", &value.span())?;
+                Ok((Expression::Unwrap(Box::new(value), span.clone()), ty))
+            }
+            Expression::Null(span) => match expected {
+                Some(ty @ Type::Optional(_, _)) => Ok((expression.clone(), with_span(ty, span))),
+                Some(ty) => Err(BlazeError::TypeError(ErrorCode::MismatchedTypes, format!("'null' is not a value of the non-optional type '{}'", ty.name()), span.clone())
+                    .with_help("only optional types, like '?*T', can be null")),
+                None => Ok((expression.clone(), Type::Pointer(Box::new(Type::Void(span.clone())), span.clone()))),
+            },
+        }
+    }
+    fn check_binary_operation(&mut self, left: &Expression, operator: &TokenKind, right: &Expression, expected: Option<&Type>, span: &Span) -> Result<(Expression, Type), BlazeError> {
+        let build = |left: Expression, right: Expression| {
+            Expression::BinaryOperation(Box::new(left), operator.clone(), Box::new(right), span.clone())
+        };
+        match operator {
+            TokenKind::Equal
+            | TokenKind::PlusEquals | TokenKind::MinusEquals
+            | TokenKind::AsteriskEquals | TokenKind::SlashEquals | TokenKind::PercentEquals
+            | TokenKind::AmpersandEquals | TokenKind::PipeEquals | TokenKind::CaretEquals
+            | TokenKind::LessLessEquals | TokenKind::GreaterGreaterEquals => {
+                self.check_assignable(left)?;
+                // Assigning to an optional that a condition has narrowed may store `null` again, so the value is
+                // checked against its declared type and the name is no longer narrowed after the assignment.
+                let narrowed: Option<&String> = match left {
+                    Expression::Identifier(name, _) if *operator == TokenKind::Equal && self.scope.is_narrowed(name) => Some(name),
+                    _ => None,
+                };
+                let (left, mut left_type) = self.check_expression(left, None)?;
+                if narrowed.is_some() {
+                    left_type = Type::Optional(Box::new(left_type), left.span());
+                    self.types.narrowed.remove(&key(&self.function, &left));
+                    self.record(&left, &left_type);
+                }
+                if let Expression::ArrayAccess(object, _, _) = &left {
+                    if let Some(Type::Str(_)) = self.types.get(&self.function, object) {
+                        return Err(BlazeError::TypeError(ErrorCode::InvalidAssignment, "cannot assign to a character of a 'str'".to_string(), left.span())
+                            .with_help("strings are read-only, so copy it into a '[]char' buffer to change it"));
+                    }
+                }
+                let is_shift: bool = matches!(operator, TokenKind::LessLessEquals | TokenKind::GreaterGreaterEquals);
+                let (right, right_type) = self.check_expression(right, if is_shift { None } else { Some(&left_type) })?;
+                let is_offset: bool = left_type.is_pointer() && right_type.is_integer()
+                    && matches!(operator, TokenKind::PlusEquals | TokenKind::MinusEquals);
+                let valid: bool = match operator {
+                    TokenKind::Equal => true,
+                    TokenKind::PlusEquals | TokenKind::MinusEquals => left_type.is_numeric() || is_offset,
+                    TokenKind::AsteriskEquals | TokenKind::SlashEquals | TokenKind::PercentEquals => left_type.is_numeric(),
+                    _ => left_type.is_integer() && (!is_shift || right_type.is_integer()),
+                };
+                if !valid {
+                    return Err(BlazeError::TypeError(ErrorCode::InvalidOperand, format!("cannot apply '{}' to a value of type '{}'", operator.spelling(), left_type.name()), span.clone()));
+                }
+                if !is_offset && !is_shift {
+                    self.expect_type(&left_type, &right_type, &right.span())?;
+                }
+                if let Some(name) = narrowed {
+                    self.scope.unnarrow(name);
+                }
+                Ok((build(left, right), left_type))
+            }
+            TokenKind::Plus | TokenKind::Minus | TokenKind::Asterisk | TokenKind::Slash | TokenKind::Percent => {
+                let expected: Option<&Type> = expected.filter(|ty| ty.is_numeric());
+                let (left, left_type, right, right_type) = self.check_operands(left, right, expected)?;
+                if left_type.is_pointer() && right_type.is_integer() && matches!(operator, TokenKind::Plus | TokenKind::Minus) {
+                    return Ok((build(left, right), left_type));
+                }
+                if !left_type.is_numeric() {
+                    return Err(BlazeError::TypeError(ErrorCode::InvalidOperand, format!("cannot apply '{}' to a value of type '{}'", operator.spelling(), left_type.name()), span.clone()));
+                }
+                self.expect_type(&left_type, &right_type, &right.span())?;
+                Ok((build(left, right), left_type))
+            }
+            TokenKind::EqualEqual | TokenKind::BangEqual
+            | TokenKind::Less | TokenKind::LessEqual
+            | TokenKind::Greater | TokenKind::GreaterEqual => {
+                let (left, left_type, right, right_type) = self.check_operands(left, right, None)?;
+                if let Type::ErrorUnion(_, _, _) = left_type {
+                    return Err(BlazeError::TypeError(ErrorCode::InvalidOperand, format!("cannot compare values of '{}'", left_type.name()), span.clone())
+                        .with_help("handle the error with 'try' or 'catch' first"));
+                }
+                let null: bool = matches!(left, Expression::Null(_)) || matches!(right, Expression::Null(_));
+                let optional: Option<&Type> = [&left_type, &right_type].into_iter()
+                    .find(|ty| matches!(ty, Type::Optional(inner, _) if !inner.is_pointer()));
+                if let (Some(optional), false) = (optional, null) {
+                    return Err(BlazeError::TypeError(ErrorCode::InvalidOperand, format!("cannot compare optional '{}' with a value", optional.name()), span.clone())
+                        .with_help("check it against 'null' or unwrap it first"));
+                }
+                if let Type::Str(_) = left_type {
+                    return Err(BlazeError::TypeError(ErrorCode::InvalidOperand, "cannot compare strings with operators".to_string(), span.clone())
+                        .with_help("use 'equals' or 'compare' from the \"std/string\" module"));
+                }
+                if self.is_tagged(&left_type) {
+                    return Err(BlazeError::TypeError(ErrorCode::InvalidOperand, format!("cannot compare values of '{}', its variants have payloads", left_type.name()), span.clone())
+                        .with_help("use 'match' to check which variant a value is"));
+                }
+                if !assignable(&left_type, &right_type) && !assignable(&right_type, &left_type) {
+                    return Err(BlazeError::TypeError(
+                        ErrorCode::InvalidOperand,
+                        format!("cannot compare '{}' with '{}'", left_type.name(), right_type.name()),
+                        span.clone(),
+                    ));
+                }
+                Ok((build(left, right), Type::Bool(span.clone())))
+            }
+            TokenKind::AmpersandAmpersand | TokenKind::PipePipe => {
+                let bool_type: Type = Type::Bool(span.clone());
+                let narrowed: Vec<String> = left.narrowed(*operator == TokenKind::AmpersandAmpersand);
+                let (left, left_type) = self.check_expression(left, Some(&bool_type))?;
+                self.expect_type(&bool_type, &left_type, &left.span())?;
+                self.scope.push();
+                self.narrow(&narrowed, span);
+                let right: Result<(Expression, Type), BlazeError> = self.check_expression(right, Some(&bool_type));
+                self.scope.pop();
+                let (right, right_type) = right?;
+                self.expect_type(&bool_type, &right_type, &right.span())?;
+                Ok((build(left, right), bool_type))
+            }
+            TokenKind::Ampersand | TokenKind::Pipe | TokenKind::Caret => {
+                let expected: Option<&Type> = expected.filter(|ty| ty.is_integer());
+                let (left, left_type, right, right_type) = self.check_operands(left, right, expected)?;
+                if !left_type.is_integer() {
+                    return Err(BlazeError::TypeError(ErrorCode::InvalidOperand, format!("cannot apply '{}' to a value of type '{}'", operator.spelling(), left_type.name()), span.clone()));
+                }
+                self.expect_type(&left_type, &right_type, &right.span())?;
+                Ok((build(left, right), left_type))
+            }
+            TokenKind::LessLess | TokenKind::GreaterGreater => {
+                let (left, left_type) = self.check_expression(left, expected.filter(|ty| ty.is_integer()))?;
+                let (right, right_type) = self.check_expression(right, None)?;
+                if !left_type.is_integer() || !right_type.is_integer() {
+                    return Err(BlazeError::TypeError(
+                        ErrorCode::InvalidOperand,
+                        format!("cannot shift a value of type '{}' by '{}'", left_type.name(), right_type.name()),
+                        span.clone(),
+                    ));
+                }
+                Ok((build(left, right), left_type))
+            }
+            _ => Err(BlazeError::TypeError(ErrorCode::InvalidOperand, format!("unsupported operator '{}'", operator.spelling()), span.clone())),
+        }
+    }
+    fn check_unary_operation(&mut self, operator: &TokenKind, value: &Expression, expected: Option<&Type>, span: &Span) -> Result<(Expression, Type), BlazeError> {
+        if let (TokenKind::Minus, Expression::Integer(integer, suffix, integer_span)) = (operator, value) {
+            let ty: Type = check_integer(*integer, suffix.as_ref(), expected, true, integer_span)?;
+            return Ok((Expression::UnaryOperation(operator.clone(), Box::new(value.clone()), span.clone()), ty));
+        }
+        let (value, ty) = match operator {
+            TokenKind::Bang => self.check_expression(value, Some(&Type::Bool(span.clone())))?,
+            _ => self.check_expression(value, expected.filter(|ty| ty.is_numeric()))?,
+        };
+        let valid: bool = match operator {
+            TokenKind::Bang => matches!(ty, Type::Bool(_)),
+            TokenKind::Minus => ty.is_integer() || ty.is_float(),
+            TokenKind::Tilde => ty.is_integer(),
+            _ => false,
+        };
+        if !valid {
+            return Err(BlazeError::TypeError(ErrorCode::InvalidOperand, format!("cannot apply '{}' to a value of type '{}'", operator.spelling(), ty.name()), span.clone()));
+        }
+        Ok((Expression::UnaryOperation(operator.clone(), Box::new(value), span.clone()), ty))
+    }
+    fn check_operands(&mut self, left: &Expression, right: &Expression, expected: Option<&Type>) -> Result<(Expression, Type, Expression, Type), BlazeError> {
+        if is_literal(left) && !is_literal(right) {
+            let (right, right_type) = self.check_expression(right, expected)?;
+            let (left, left_type) = self.check_expression(left, Some(&right_type))?;
+            Ok((left, left_type, right, right_type))
+        } else {
+            let (left, left_type) = self.check_expression(left, expected)?;
+            let (right, right_type) = self.check_expression(right, Some(&left_type))?;
+            Ok((left, left_type, right, right_type))
+        }
+    }
+    // Checks an array index or slice bound. For a fixed-size array, a literal one also has to be in bounds, where a
+    // slice bound may be the length itself.
+    fn check_index(&mut self, index: &Expression, position: &str, length: Option<usize>, inclusive: bool) -> Result<Expression, BlazeError> {
+        let (index, index_type) = self.check_expression(index, Some(&Type::U64(index.span())))?;
+        if !index_type.is_integer() {
+            return Err(BlazeError::TypeError(ErrorCode::MismatchedTypes, format!("array {} must be an integer, found '{}'", position, index_type.name()), index.span()));
+        }
+        if let (Some(length), Expression::Integer(value, _, span)) = (length, &index) {
+            if *value > length as u64 || *value == length as u64 && !inclusive {
+                return Err(BlazeError::TypeError(ErrorCode::IndexOutOfBounds, format!("{} {} is out of bounds for an array of length {}", position, value, length), span.clone()));
+            }
+        }
+        Ok(index)
+    }
+    fn check_assignable(&mut self, target: &Expression) -> Result<(), BlazeError> {
+        self.mutate_binding(target);
+        match target {
+            Expression::Identifier(name, span) => {
+                let mutable: Option<bool> = self.scope.is_mutable(name).or_else(|| {
+                    (0..=self.path.len()).rev()
+                        .filter_map(|depth| self.namespace(&self.path[..depth]))
+                        .find_map(|namespace| namespace.is_mutable(name))
+                });
+                match mutable {
+                    Some(false) => Err(BlazeError::TypeError(ErrorCode::InvalidAssignment, format!("cannot assign to immutable '{}'", name), span.clone())
+                        .with_help(&format!("declare '{}' with 'mut' to make it mutable", name))),
+                    _ => Ok(()),
+                }
+            }
+            // A global of a namespace or module, like `counter::count`.
+            Expression::StaticMemberAccess(_, _, span) => {
+                let segments: Vec<String> = static_path(target)?;
+                let (name, parents) = segments.split_last().unwrap();
+                let mutable: Option<bool> = (0..=self.path.len()).rev().find_map(|depth| {
+                    let mut path: Vec<String> = self.path[..depth].to_vec();
+                    path.extend(parents.iter().cloned());
+                    self.namespace(&path).and_then(|namespace| namespace.is_mutable(name))
+                });
+                match mutable {
+                    Some(false) => Err(BlazeError::TypeError(ErrorCode::InvalidAssignment, format!("cannot assign to immutable '{}'", segments.join("::")), span.clone())
+                        .with_help(&format!("declare '{}' with 'mut' to make it mutable", name))),
+                    _ => Ok(()),
+                }
+            }
+            Expression::MemberAccess(_, _, _) | Expression::ArrayAccess(_, _, _) | Expression::Dereference(_, _) => Ok(()),
+            _ => Err(BlazeError::TypeError(ErrorCode::InvalidAssignment, "invalid assignment target".to_string(), target.span())),
+        }
+    }
+    // Checks a value that may be a call returning any number of values, where the caller receives all of them.
+    fn check_values(&mut self, value: &Expression) -> Result<(Expression, Vec<Type>), BlazeError> {
+        match value {
+            Expression::Call(callee, arguments, span) => self.check_call_values(callee, arguments, None, span),
+            value => {
+                let (value, ty) = self.check_expression(value, None)?;
+                Ok((value, vec![ty]))
+            }
+        }
+    }
+    fn check_call(&mut self, callee: &Expression, arguments: &[Expression], result: Option<&Type>, span: &Span) -> Result<(Expression, Type), BlazeError> {
+        let (call, returns) = self.check_call_values(callee, arguments, result, span)?;
+        match returns.as_slice() {
+            [] => Ok((call, Type::Void(span.clone()))),
+            [ty] => Ok((call, ty.clone())),
+            _ => {
+                let callee: &Expression = match callee {
+                    Expression::MemberAccess(_, method, _) => method,
+                    callee => callee,
+                };
+                let name: String = static_path(callee).map(|segments| segments.join("::")).unwrap_or_default();
+                Err(BlazeError::TypeError(ErrorCode::ValueCount, format!("'{}' returns {} values, which cannot be used as one value", name, returns.len()), span.clone())
+                    .with_help(&format!("receive them with a declaration like '{} := ...;'", value_names(returns.len()))))
+            }
+        }
+    }
+    fn check_call_values(&mut self, callee: &Expression, arguments: &[Expression], result: Option<&Type>, span: &Span) -> Result<(Expression, Vec<Type>), BlazeError> {
+        let mut checked: Vec<Expression> = Vec::new();
+        let mut receiver_type: Option<Type> = None;
+        let (name, (parameters, returns)) = match callee {
+            Expression::MemberAccess(object, member, _) => {
+                let method: String = match &**member {
+                    Expression::Identifier(method, _) => method.clone(),
+                    _ => return Err(BlazeError::TypeError(ErrorCode::ExpectedName, "expected a method name".to_string(), member.span())),
+                };
+                let (object, object_type) = self.check_expression(object, None)?;
+                if let Type::Optional(_, _) = object_type {
+                    return Err(not_narrowed(&format!("call '{}' on", method), &object_type, &object, span));
+                }
+                let (name, signature) = self.lookup_method(&object_type, &method, span)?;
+                let receiver: Expression = match (&signature.0[0].1, &object_type) {
+                    (Type::Pointer(_, _), Type::Pointer(_, _)) => object,
+                    (Type::Pointer(_, _), _) => {
+                        self.mutate_binding(&object);
+                        Expression::AddressOf(Box::new(object.clone()), object.span())
+                    }
+                    (_, Type::Pointer(_, _)) => Expression::Dereference(Box::new(object.clone()), object.span()),
+                    _ => object,
+                };
+                let target: Type = match object_type {
+                    Type::Pointer(ty, _) => *ty,
+                    ty => ty,
+                };
+                let ty: Type = match &signature.0[0].1 {
+                    Type::Pointer(_, pointer_span) => Type::Pointer(Box::new(target), pointer_span.clone()),
+                    _ => target,
+                };
+                self.record(&receiver, &ty);
+                receiver_type = Some(ty);
+                checked.push(receiver);
+                (name, signature)
+            }
+            Expression::Identifier(name, name_span) => {
+                if self.scope.locate(name.clone(), name_span.clone()).is_some() {
+                    return Err(BlazeError::TypeError(ErrorCode::NotCallable, format!("'{}' is not a function", name), name_span.clone()));
+                }
+                (0..=self.path.len()).rev()
+                    .filter_map(|depth| self.namespace(&self.path[..depth]))
+                    .find_map(|namespace| namespace.functions.get(name).map(|signature| (namespace.qualify(name), signature.clone())))
+                    .ok_or_else(|| BlazeError::TypeError(ErrorCode::UndefinedName, format!("undefined function '{}'", name), name_span.clone()))?
+            }
+            Expression::StaticMemberAccess(_, _, callee_span) => {
+                let segments: Vec<String> = static_path(callee)?;
+                self.use_import(&segments[0]);
+                match self.lookup_static_function(&segments) {
+                    Some(function) => function,
+                    None => match self.lookup_variant(&segments) {
+                        Some((enum_name, variant)) => {
+                            return self.check_variant_literal(&enum_name, &variant, arguments, callee_span, span).map(|(value, ty)| (value, vec![ty]));
+                        }
+                        None => return Err(BlazeError::TypeError(ErrorCode::UndefinedName, format!("undefined function '{}'", segments.join("::")), callee_span.clone())),
+                    },
+                }
+            }
+            _ => return Err(BlazeError::TypeError(ErrorCode::NotCallable, "expression is not callable".to_string(), callee.span())),
+        };
+
+        let variadic: Option<Option<Type>> = match parameters.last() {
+            Some((_, Type::VarArgs(ty, _))) => Some(*ty.clone()),
+            _ => None,
+        };
+        let generics: Vec<Type> = function_generics(&parameters, &returns);
+        let mut bound: Vec<Option<Type>> = vec![None; generics.len()];
+        if let Some(receiver_type) = &receiver_type {
+            unify(&parameters[0].1, receiver_type, &generics, &mut bound);
+        }
+        // The type the result is expected to have decides the type of literal arguments, like in `x: i32 = id(4);`,
+        // and of generics that only appear in the return type.
+        let mut hints: Vec<Option<Type>> = vec![None; generics.len()];
+        let values: Vec<&Type> = returns.iter().filter(|ty| !matches!(ty, Type::Void(_))).collect();
+        if let ([returns], Some(result)) = (values.as_slice(), result) {
+            match (returns, result) {
+                (Type::ErrorUnion(_, returns, _), Type::ErrorUnion(_, result, _)) => unify(returns, result, &generics, &mut hints),
+                _ => unify(returns, result, &generics, &mut hints),
+            };
+        }
+        let fixed: usize = parameters.len() - variadic.is_some() as usize;
+        let given: usize = checked.len() + arguments.len();
+        if given < fixed || (variadic.is_none() && given > fixed) {
+            let skipped: usize = checked.len();
+            return Err(BlazeError::TypeError(
+                ErrorCode::ArgumentCount,
+                format!(
+                    "'{}' expects {}{} arguments, but got {}",
+                    name,
+                    if variadic.is_some() { "at least " } else { "" },
+                    fixed - skipped,
+                    given - skipped,
+                ),
+                span.clone(),
+            ).with_note(&format!(
+                "'{}' is declared as 'fn({})'",
+                name,
+                parameters[skipped..].iter().map(|(name, ty)| format!("{}: {}", name, ty.name())).collect::<Vec<String>>().join(", "),
+            )));
+        }
+
+        for argument in arguments {
+            let expected: Option<Type> = match parameters.get(checked.len()) {
+                Some((_, Type::VarArgs(ty, _))) => *ty.clone(),
+                Some((_, ty)) => Some(ty.clone()),
+                None => variadic.clone().flatten(),
+            };
+            let expected: Option<Type> = expected.map(|ty| bind(&ty, &generics, &bound));
+            if let Some(parameter) = expected.as_ref().filter(|ty| !ty.generics().is_empty()) {
+                let hint: Option<Type> = Some(bind(parameter, &generics, &hints))
+                    .filter(|ty| is_literal(argument) && ty.generics().is_empty());
+                let (argument, ty) = self.check_expression(argument, hint.as_ref())?;
+                if !unify(parameter, &ty, &generics, &mut bound) {
+                    return Err(BlazeError::TypeError(
+                        ErrorCode::MismatchedTypes,
+                        format!("mismatched types: expected '{}', found '{}'", parameter.name(), ty.name()),
+                        argument.span(),
+                    ));
+                }
+                checked.push(argument);
+                continue;
+            }
+            let (argument, ty) = self.check_expression(argument, expected.as_ref())?;
+            match &expected {
+                Some(expected) => self.expect_type(expected, &ty, &argument.span())?,
+                None if matches!(ty, Type::Void(_)) => {
+                    return Err(BlazeError::TypeError(ErrorCode::VoidValue, "cannot pass a value of type 'void'".to_string(), argument.span()));
+                }
+                None => {}
+            }
+            checked.push(argument);
+        }
+
+        let mut name: String = name;
+        let mut returns: Vec<Type> = returns;
+        if !generics.is_empty() {
+            let mut arguments: Vec<Type> = Vec::new();
+            for ((generic, argument), hint) in generics.iter().zip(bound).zip(hints) {
+                arguments.push(argument.or(hint).ok_or_else(|| {
+                    BlazeError::TypeError(ErrorCode::GenericArguments, format!("cannot infer '{}' in this call to '{}'", generic.name(), name), span.clone())
+                })?);
+            }
+            returns = returns.iter().map(|ty| ty.substitute(&generics, &arguments)).collect();
+            name = self.instantiate(&name, arguments, span)?;
+        }
+        let returns: Vec<Type> = returns.into_iter().filter(|ty| !matches!(ty, Type::Void(_))).collect();
+        Ok((Expression::Call(Box::new(Expression::Identifier(name, callee.span())), checked, span.clone()), returns))
+    }
+    fn check_struct_literal(&mut self, name: &str, fields: &[(Option<String>, Expression, Span)], expected: Option<&Type>, span: &Span) -> Result<(Expression, Type), BlazeError> {
+        let ty: Type = match self.resolve_type(&Type::Unknown(name.to_string(), span.clone())) {
+            Ok(ty) => ty,
+            Err(error) => match expected {
+                Some(Type::GenericInstance(qualified, arguments, _)) if split_qualified(qualified).1 == split_qualified(name).1 => {
+                    Type::GenericInstance(qualified.clone(), arguments.clone(), span.clone())
+                }
+                _ if error.message().starts_with("missing generic arguments") => self.infer_struct_literal(name, fields, span)?,
+                _ => return Err(error),
+            },
+        };
+        let declared: Vec<StructField> = match self.fields_of(&ty) {
+            Some(fields) => fields,
+            None => return Err(BlazeError::TypeError(ErrorCode::InvalidStructLiteral, format!("'{}' is not a struct", name), span.clone())),
+        };
+        if fields.len() > declared.len() {
+            return Err(BlazeError::TypeError(
+                ErrorCode::InvalidStructLiteral,
+                format!("'{}' has {} fields, but {} were given", name, declared.len(), fields.len()),
+                span.clone(),
+            ));
+        }
+
+        let mut initialized: Vec<(String, Span)> = Vec::new();
+        let mut checked: Vec<(Option<String>, Expression, Span)> = Vec::new();
+        for (index, (field_name, value, field_span)) in fields.iter().enumerate() {
+            let field: &StructField = match field_name {
+                Some(field_name) => declared.iter().find(|field| &field.name == field_name)
+                    .ok_or_else(|| BlazeError::TypeError(ErrorCode::UnknownMember, format!("'{}' has no field named '{}'", name, field_name), field_span.clone()))?,
+                None => &declared[index],
+            };
+            if let Some((_, first)) = initialized.iter().find(|(name, _)| name == &field.name) {
+                return Err(BlazeError::TypeError(ErrorCode::InvalidStructLiteral, format!("field '{}' is initialized more than once", field.name), field_span.clone())
+                    .with_label(first.clone(), "first initialized here"));
+            }
+            initialized.push((field.name.clone(), field_span.clone()));
+
+            let (value, value_type) = self.check_expression(value, Some(&field.ty))?;
+            let is_member: bool = match &field.ty {
+                Type::Union(union_name, _) => {
+                    let (path, union_name) = split_qualified(union_name);
+                    self.namespace(&path)
+                        .and_then(|namespace| namespace.unions.get(&union_name))
+                        .is_some_and(|members| members.iter().any(|member| member.equals(value_type.clone())))
+                }
+                _ => false,
+            };
+            if !is_member {
+                self.expect_type(&field.ty, &value_type, &value.span())?;
+            }
+            checked.push((field_name.clone(), value, field_span.clone()));
+        }
+        if let Some(missing) = declared.iter().find(|field| !initialized.iter().any(|(name, _)| name == &field.name)) {
+            return Err(BlazeError::TypeError(ErrorCode::InvalidStructLiteral, format!("missing field '{}' in '{}'", missing.name, name), span.clone())
+                .with_label(missing.span.clone(), "field declared here"));
+        }
+
+        let (qualified, arguments) = match &ty {
+            Type::Struct(qualified, _) => (qualified.clone(), Vec::new()),
+            Type::GenericInstance(qualified, arguments, _) => (qualified.clone(), arguments.clone()),
+            _ => (name.to_string(), Vec::new()),
+        };
+        Ok((Expression::StructLiteral(qualified, arguments, checked, span.clone()), ty))
+    }
+    // Infers the generic arguments of a literal written without them, like `Box{ 5 }`, from its field values.
+    fn infer_struct_literal(&mut self, name: &str, fields: &[(Option<String>, Expression, Span)], span: &Span) -> Result<Type, BlazeError> {
+        let qualified: String = self.qualify_struct(name).unwrap();
+        let (generics, declared) = self.find_struct(&qualified).cloned().unwrap();
+        let mut bound: Vec<Option<Type>> = vec![None; generics.len()];
+        for (index, (field_name, value, _)) in fields.iter().enumerate() {
+            let field: Option<&StructField> = match field_name {
+                Some(field_name) => declared.iter().find(|field| &field.name == field_name),
+                None => declared.get(index),
+            };
+            if let Some(field) = field.filter(|field| !field.ty.generics().is_empty()) {
+                let (_, value_type) = self.check_expression(value, None)?;
+                unify(&field.ty, &value_type, &generics, &mut bound);
+            }
+        }
+        let mut arguments: Vec<Type> = Vec::new();
+        for (generic, argument) in generics.iter().zip(bound) {
+            arguments.push(argument.ok_or_else(|| {
+                BlazeError::TypeError(ErrorCode::GenericArguments, format!("cannot infer '{}' for '{}'", generic.name(), name), span.clone())
+                    .with_help(&format!("annotate the type of the value, like 'value: {}<...> = ...'", name))
+            })?);
+        }
+        Ok(Type::GenericInstance(qualified, arguments, span.clone()))
+    }
+
+    fn lookup_method(&self, ty: &Type, method: &str, span: &Span) -> Result<(String, Signature), BlazeError> {
+        let target: &Type = match ty {
+            Type::Pointer(ty, _) => ty,
+            ty => ty,
+        };
+        if let Ok(owner) = method_owner(target, span) {
+            let (path, owner_name) = split_qualified(&owner);
+            let key: String = format!("{}::{}", owner_name, method);
+            if let Some(signature) = self.namespace(&path).and_then(|namespace| namespace.functions.get(&key)) {
+                return Ok((format!("{}::{}", owner, method), signature.clone()));
+            }
+        }
+        Err(BlazeError::TypeError(ErrorCode::UnknownMember, format!("'{}' has no method named '{}'", target.name(), method), span.clone()))
+    }
+    fn lookup_static_function(&self, segments: &[String]) -> Option<(String, Signature)> {
+        let (last, parents) = segments.split_last()?;
+        for depth in (0..=self.path.len()).rev() {
+            let mut path: Vec<String> = self.path[..depth].to_vec();
+            path.extend(parents.iter().cloned());
+            if let Some(namespace) = self.namespace(&path) {
+                if let Some(signature) = namespace.functions.get(last) {
+                    return Some((namespace.qualify(last), signature.clone()));
+                }
+            }
+            if let Some((owner, namespace_path)) = parents.split_last() {
+                let mut path: Vec<String> = self.path[..depth].to_vec();
+                path.extend(namespace_path.iter().cloned());
+                let key: String = format!("{}::{}", owner, last);
+                if let Some(namespace) = self.namespace(&path) {
+                    if let Some(signature) = namespace.functions.get(&key) {
+                        return Some((namespace.qualify(&key), signature.clone()));
+                    }
+                }
+            }
+        }
+        None
+    }
+    fn lookup_variant(&self, segments: &[String]) -> Option<(String, EnumVariant)> {
+        let (last, parents) = segments.split_last()?;
+        let (enum_name, namespace_path) = parents.split_last()?;
+        for depth in (0..=self.path.len()).rev() {
+            let mut path: Vec<String> = self.path[..depth].to_vec();
+            path.extend(namespace_path.iter().cloned());
+            let namespace: &Scope = match self.namespace(&path) {
+                Some(namespace) => namespace,
+                None => continue,
+            };
+            if let Some((_, variants)) = namespace.enums.get(enum_name) {
+                let variant: &EnumVariant = variants.iter().find(|variant| variant_name(variant) == last)?;
+                return Some((namespace.qualify(enum_name), variant.clone()));
+            }
+        }
+        None
+    }
+    fn find_enum(&self, qualified: &str) -> Option<&(Option<Type>, Vec<EnumVariant>)> {
+        let (path, name) = split_qualified(qualified);
+        self.namespace(&path)?.enums.get(&name)
+    }
+    fn is_tagged(&self, ty: &Type) -> bool {
+        match ty {
+            Type::Enum(name, _) => self.find_enum(name).is_some_and(|(_, variants)| is_tagged(variants)),
+            _ => false,
+        }
+    }
+    fn check_variant_literal(&mut self, enum_name: &str, variant: &EnumVariant, arguments: &[Expression], callee_span: &Span, span: &Span) -> Result<(Expression, Type), BlazeError> {
+        let (_, short_name) = split_qualified(enum_name);
+        let name: String = format!("{}::{}", short_name, variant_name(variant));
+        let fields: &[StructField] = match variant {
+            EnumVariant::Payload(_, fields, _) => fields,
+            _ => return Err(BlazeError::TypeError(ErrorCode::ArgumentCount, format!("'{}' has no payload", name), span.clone())
+                .with_help(&format!("use '{}' without parentheses", name))),
+        };
+        if arguments.len() != fields.len() {
+            return Err(BlazeError::TypeError(ErrorCode::ArgumentCount, format!("'{}' expects {} values, but got {}", name, fields.len(), arguments.len()), span.clone())
+                .with_label(variant_span(variant), "variant declared here"));
+        }
+        let mut checked: Vec<Expression> = Vec::new();
+        for (argument, field) in arguments.iter().zip(fields) {
+            let (argument, ty) = self.check_expression(argument, Some(&field.ty))?;
+            self.expect_type(&field.ty, &ty, &argument.span())?;
+            checked.push(argument);
+        }
+        let callee: Expression = Expression::Identifier(format!("{}::{}", enum_name, variant_name(variant)), callee_span.clone());
+        Ok((Expression::Call(Box::new(callee), checked, span.clone()), Type::Enum(enum_name.to_string(), span.clone())))
+    }
+    // Checks the value of a `match`, which must be an enum or a pointer to one.
+    fn check_match_value(&mut self, value: &Expression) -> Result<(Expression, String, Vec<EnumVariant>), BlazeError> {
+        let (value, ty) = self.check_expression(value, None)?;
+        let (value, ty) = match ty {
+            Type::Pointer(inner, _) if matches!(*inner, Type::Enum(_, _)) => (Expression::Dereference(Box::new(value.clone()), value.span()), *inner),
+            ty => (value, ty),
+        };
+        self.record(&value, &ty);
+        let enum_name: String = match ty {
+            Type::Enum(name, _) => name,
+            ty => return Err(BlazeError::TypeError(ErrorCode::InvalidOperand, format!("cannot match on a value of type '{}'", ty.name()), value.span())
+                .with_help("'match' checks which variant of an enum a value is")),
+        };
+        let variants: Vec<EnumVariant> = self.find_enum(&enum_name).map(|(_, variants)| variants.clone()).unwrap_or_default();
+        Ok((value, enum_name, variants))
+    }
+    // Resolves a pattern to the variant it names, returning the pattern with the variant's qualified name and the
+    // bindings it introduces. Patterns that can never match are reported as unreachable.
+    fn check_pattern(&mut self, pattern: &Pattern, enum_name: &str, variants: &[EnumVariant], covered: &mut Vec<String>) -> Result<(Pattern, PatternBindings), BlazeError> {
+        let unreachable: bool = covered.iter().any(|name| name == "_");
+        let (path, bindings, span) = match pattern {
+            Pattern::Wildcard(span) => {
+                if unreachable || variants.iter().all(|variant| covered.iter().any(|name| name == variant_name(variant))) {
+                    self.warn_unreachable_pattern(span);
+                }
+                covered.push("_".to_string());
+                return Ok((pattern.clone(), Vec::new()));
+            }
+            Pattern::Variant(path, bindings, span) => (path, bindings, span),
+        };
+        let (_, short_name) = split_qualified(enum_name);
+        let (prefix, last) = split_qualified(path);
+        if prefix.is_empty() {
+            return Err(BlazeError::TypeError(ErrorCode::InvalidPattern, format!("expected a variant of '{}'", short_name), span.clone())
+                .with_suggestion(span.clone(), &format!("{}::{}", short_name, last), "name the enum too"));
+        }
+        match self.resolve_type(&Type::Unknown(prefix.join("::"), span.clone()))? {
+            Type::Enum(name, _) if name == enum_name => {}
+            ty => return Err(BlazeError::TypeError(
+                ErrorCode::MismatchedTypes,
+                format!("mismatched types: expected a variant of '{}', found '{}'", short_name, ty.name()),
+                span.clone(),
+            )),
+        }
+        let variant: &EnumVariant = variants.iter().find(|variant| variant_name(variant) == last)
+            .ok_or_else(|| BlazeError::TypeError(ErrorCode::UnknownMember, format!("enum '{}' has no variant named '{}'", short_name, last), span.clone()))?;
+        let fields: &[StructField] = match variant {
+            EnumVariant::Payload(_, fields, _) => fields,
+            _ => &[],
+        };
+        if !bindings.is_empty() && bindings.len() != fields.len() {
+            return Err(BlazeError::TypeError(
+                ErrorCode::ArgumentCount,
+                format!("'{}::{}' has {} payload field{}, but the pattern binds {}", short_name, last, fields.len(), if fields.len() == 1 { "" } else { "s" }, bindings.len()),
+                span.clone(),
+            ).with_label(variant_span(variant), "variant declared here"));
+        }
+        if unreachable || covered.contains(&last) {
+            self.warn_unreachable_pattern(span);
+        }
+        covered.push(last.clone());
+        let typed: PatternBindings = bindings.iter().zip(fields)
+            .map(|((name, span), field)| (name.clone(), field.ty.clone(), span.clone()))
+            .collect();
+        Ok((Pattern::Variant(format!("{}::{}", enum_name, last), bindings.clone(), span.clone()), typed))
+    }
+    fn warn_unreachable_pattern(&mut self, span: &Span) {
+        let warning: BlazeError = BlazeError::Warning(Lint::UnreachableCode, "unreachable pattern".to_string(), span.clone())
+            .with_help("an earlier arm already matches every value this one does");
+        self.warn(Lint::UnreachableCode, &self.lint_levels.clone(), warning);
+    }
+    fn declare_pattern_bindings(&mut self, bindings: PatternBindings) {
+        for (name, ty, span) in bindings {
+            if name == "_" {
+                continue;
+            }
+            self.declare_binding(&name, &span, false, false);
+            self.scope.variables.insert(name.clone(), (ty, Expression::Identifier(name, span)));
+        }
+    }
+    // Returns the qualified name of a value, which is the name itself for a local, and its type. An optional that a
+    // condition has checked against `null` has the type of its value.
+    fn lookup_value(&self, name: &str, span: &Span) -> Result<(String, Type), BlazeError> {
+        let local: Option<Type> = self.scope.locate(name.to_string(), span.clone()).map(|(ty, _)| ty);
+        if let Some(ty) = local.clone().filter(|_| self.scope.is_mutable(name).is_some()) {
+            return Ok((name.to_string(), ty));
+        }
+        for depth in (0..=self.path.len()).rev() {
+            if let Some(namespace) = self.namespace(&self.path[..depth]) {
+                if let Some(ty) = self.global_type(namespace, name, span)? {
+                    return Ok((namespace.qualify(name), local.unwrap_or(ty)));
+                }
+            }
+        }
+        Err(BlazeError::TypeError(ErrorCode::UndefinedName, format!("undefined name '{}'", name), span.clone()))
+    }
+    fn lookup_static(&self, segments: &[String], span: &Span) -> Result<(String, Type), BlazeError> {
+        let qualified: String = segments.join("::");
+        for depth in (0..=self.path.len()).rev() {
+            let mut namespace: &Scope = match self.namespace(&self.path[..depth]) {
+                Some(namespace) => namespace,
+                None => continue,
+            };
+            let (last, parents) = segments.split_last().unwrap();
+            let mut found: bool = true;
+            for (index, segment) in parents.iter().enumerate() {
+                if let Some(child) = self.child(namespace, segment) {
+                    namespace = child;
+                } else if let Some(errors) = namespace.errors.get(segment) {
+                    if index + 1 != parents.len() {
+                        found = false;
+                        break;
+                    }
+                    if !errors.iter().any(|(error, _)| error == last) {
+                        return Err(BlazeError::TypeError(ErrorCode::UnknownMember, format!("error set '{}' has no error named '{}'", segment, last), span.clone()));
+                    }
+                    let set: String = namespace.qualify(segment);
+                    return Ok((format!("{}::{}", set, last), Type::ErrorSet(Some(set), span.clone())));
+                } else if let Some((_, variants)) = namespace.enums.get(segment) {
+                    if index + 1 != parents.len() {
+                        found = false;
+                        break;
+                    }
+                    match variants.iter().find(|variant| variant_name(variant) == last) {
+                        None => return Err(BlazeError::TypeError(ErrorCode::UnknownMember, format!("enum '{}' has no variant named '{}'", segment, last), span.clone())),
+                        Some(EnumVariant::Payload(_, fields, _)) => {
+                            let placeholders: Vec<&str> = fields.iter().map(|field| field.name.as_str()).collect();
+                            return Err(BlazeError::TypeError(ErrorCode::ArgumentCount, format!("'{}::{}' has a payload", segment, last), span.clone())
+                                .with_help(&format!("construct it with '{}::{}({})'", segment, last, placeholders.join(", "))));
+                        }
+                        Some(_) => {}
+                    }
+                    let enum_name: String = namespace.qualify(segment);
+                    return Ok((format!("{}::{}", enum_name, last), Type::Enum(enum_name, span.clone())));
+                } else {
+                    found = false;
+                    break;
+                }
+            }
+            if found {
+                if let Some(ty) = self.global_type(namespace, last, span)? {
+                    return Ok((namespace.qualify(last), ty));
+                }
+            }
+        }
+        Err(BlazeError::TypeError(ErrorCode::UndefinedName, format!("undefined name '{}'", qualified), span.clone()))
+    }
+    fn global_type(&self, namespace: &Scope, name: &str, span: &Span) -> Result<Option<Type>, BlazeError> {
+        if let Some((ty, _)) = namespace.consts.get(name)
+            .or_else(|| namespace.variables.get(name))
+            .or_else(|| namespace.mutables.get(name)) {
+            if *ty == Type::AwaitingInference {
+                return Err(BlazeError::TypeError(ErrorCode::CannotInfer, format!("'{}' is used before its type is known", name), span.clone()));
+            }
+            return Ok(Some(ty.clone()));
+        }
+        if namespace.functions.contains_key(name) {
+            return Err(BlazeError::TypeError(ErrorCode::NotAValue, format!("'{}' is a function, not a value", name), span.clone()));
+        }
+        if namespace.structs.contains_key(name) || namespace.enums.contains_key(name) || namespace.unions.contains_key(name) || namespace.errors.contains_key(name) {
+            return Err(BlazeError::TypeError(ErrorCode::NotAValue, format!("'{}' is a type, not a value", name), span.clone()));
+        }
+        Ok(None)
+    }
+    fn field_type(&self, ty: &Type, field: &str, span: &Span) -> Result<Type, BlazeError> {
+        let target: Type = match ty {
+            Type::Pointer(ty, _) => *ty.clone(),
+            ty => ty.clone(),
+        };
+        match self.fields_of(&target) {
+            Some(fields) => fields.into_iter()
+                .find(|f| f.name == field)
+                .map(|f| f.ty)
+                .ok_or_else(|| BlazeError::TypeError(ErrorCode::UnknownMember, format!("'{}' has no field named '{}'", target.name(), field), span.clone())),
+            None => Err(BlazeError::TypeError(ErrorCode::UnknownMember, format!("cannot access field '{}' on a value of type '{}'", field, ty.name()), span.clone())),
+        }
+    }
+    fn expect_type(&self, expected: &Type, found: &Type, span: &Span) -> Result<(), BlazeError> {
+        if assignable(expected, found) {
+            return Ok(());
+        }
+        let error: BlazeError = BlazeError::TypeError(
+            ErrorCode::MismatchedTypes,
+            format!("mismatched types: expected '{}', found '{}'", expected.name(), found.name()),
+            span.clone(),
+        );
+        // A literal only takes the type it is used as where it is written, so a name declared with one is an `i64`.
+        match self.defaulted.get(&found.span()) {
+            Some(literal) if expected.is_integer() => Err(error
+                .with_label(literal.clone(), "this literal has no suffix, so it is an 'i64'")
+                .with_suggestion(
+                    Span { start: literal.end, ..literal.clone() },
+                    &expected.name(),
+                    &format!("give the literal the type '{}'", expected.name()),
+                )),
+            _ => Err(error),
+        }
+    }
+}
+
+fn key(function: &str, expression: &Expression) -> ExpressionKey {
+    (function.to_string(), expression.span(), expression.last_span(), discriminant(expression))
+}
+
+fn assignable(target: &Type, value: &Type) -> bool {
+    if target.equals(value.clone()) {
+        return true;
+    }
+    match (target, value) {
+        // A local declared with a value that has errors has no type, and is not reported again.
+        (Type::AwaitingInference, _) | (_, Type::AwaitingInference) => true,
+        (Type::Pointer(_, _) | Type::Optional(_, _), Type::Pointer(inner, _)) if matches!(**inner, Type::Void(_)) => true,
+        (Type::Pointer(inner, _), Type::Pointer(_, _)) if matches!(**inner, Type::Void(_)) => true,
+        (Type::Pointer(target, _), Type::Array(value, _)) => target.equals(*value.clone()),
+        (Type::Str(_), Type::Array(target, _)) => matches!(**target, Type::Char(_)),
+        (Type::Optional(target, _), value) => assignable(target, value),
+        (Type::ErrorUnion(set, payload, _), Type::ErrorUnion(value_set, value_payload, _)) => assignable(set, value_set) && payload.equals(*value_payload.clone()),
+        (Type::ErrorUnion(set, _, _), Type::ErrorSet(_, _)) => assignable(set, value),
+        (Type::ErrorUnion(_, payload, _), value) => assignable(payload, value),
+        (Type::ErrorSet(None, _), Type::ErrorSet(_, _)) => true,
+        _ => false,
+    }
+}
+
+fn function_generics(parameters: &[(String, Type)], returns: &[Type]) -> Vec<Type> {
+    let mut generics: Vec<Type> = Vec::new();
+    for ty in parameters.iter().map(|(_, ty)| ty).chain(returns) {
+        for generic in ty.generics() {
+            if !generics.iter().any(|other| other.equals(generic.clone())) {
+                generics.push(generic);
+            }
+        }
+    }
+    generics
+}
+
+// A `$` cannot be part of a name in the source, so instances cannot clash with the functions declared there.
+fn instance_name(template: &Template, arguments: &[Type]) -> String {
+    format!("{}${}", template.name, mangle_arguments(arguments))
+}
+
+fn type_size(ty: &Type) -> usize {
+    match ty {
+        Type::Array(ty, _) | Type::FixedArray(ty, _, _) | Type::Pointer(ty, _) | Type::Optional(ty, _) => 1 + type_size(ty),
+        Type::GenericInstance(_, types, _) => 1 + types.iter().map(type_size).sum::<usize>(),
+        _ => 1,
+    }
+}
+
+// Substitutes the generics that are already bound, leaving the others in place.
+fn bind(ty: &Type, generics: &[Type], bound: &[Option<Type>]) -> Type {
+    let (generics, arguments): (Vec<Type>, Vec<Type>) = generics.iter().zip(bound)
+        .filter_map(|(generic, argument)| Some((generic.clone(), argument.clone()?)))
+        .unzip();
+    ty.substitute(&generics, &arguments)
+}
+
+// Matches a parameter type against the type of a value, binding the generics it mentions.
+fn unify(ty: &Type, found: &Type, generics: &[Type], bound: &mut [Option<Type>]) -> bool {
+    if ty.generics().is_empty() || assignable(ty, found) {
+        return assignable(ty, found);
+    }
+    match (ty, found) {
+        (Type::Generic(_, _), found) => match generics.iter().position(|generic| generic.equals(ty.clone())) {
+            Some(index) => match &bound[index] {
+                Some(argument) => argument.equals(found.clone()),
+                None => {
+                    bound[index] = Some(found.clone());
+                    true
+                }
+            },
+            None => false,
+        },
+        (Type::Pointer(ty, _), Type::Pointer(found, _))
+        | (Type::Array(ty, _), Type::Array(found, _))
+        | (Type::Pointer(ty, _), Type::Array(found, _))
+        | (Type::Optional(ty, _), Type::Optional(found, _)) => unify(ty, found, generics, bound),
+        (Type::FixedArray(ty, length, _), Type::FixedArray(found, found_length, _)) => length == found_length && unify(ty, found, generics, bound),
+        (Type::Optional(ty, _), found) => unify(ty, found, generics, bound),
+        (Type::ErrorUnion(set, ty, _), Type::ErrorUnion(found_set, found, _)) => assignable(set, found_set) && unify(ty, found, generics, bound),
+        (Type::ErrorUnion(_, ty, _), found) => unify(ty, found, generics, bound),
+        (Type::GenericInstance(name, types, _), Type::GenericInstance(found_name, found_types, _)) => {
+            name == found_name
+                && types.len() == found_types.len()
+                && types.iter().zip(found_types).all(|(ty, found)| unify(ty, found, generics, bound))
+        }
+        _ => false,
+    }
+}
+
+// Adds a statement to the namespace at `path`, e.g. a generic function instance next to its generic function.
+fn insert_statement(statements: &mut Vec<Statement>, path: &[String], statement: Statement) {
+    let (first, rest) = match path.split_first() {
+        Some(split) => split,
+        None => return statements.push(statement),
+    };
+    for candidate in statements.iter_mut() {
+        let mut candidate: &mut Statement = candidate;
+        while let Statement::Documented(_, inner, _) | Statement::Attribute(_, _, inner, _) = candidate {
+            candidate = inner;
+        }
+        if let Statement::Namespace(name, statements, _) = candidate {
+            if name == first {
+                return insert_statement(statements, rest, statement);
+            }
+        }
+    }
+}
+
+fn check_fields(fields: &[StructField]) -> Result<(), BlazeError> {
+    for (index, field) in fields.iter().enumerate() {
+        if let Some(other) = fields[..index].iter().find(|other| other.name == field.name) {
+            return Err(BlazeError::TypeError(ErrorCode::DuplicateDefinition, format!("field '{}' is already declared", field.name), field.span.clone())
+                .with_label(other.span.clone(), "first declared here"));
+        }
+    }
+    Ok(())
+}
+
+fn fixed_length(ty: &Type) -> Option<usize> {
+    match ty {
+        Type::FixedArray(_, length, _) => Some(*length),
+        _ => None,
+    }
+}
+
+fn value_names(count: usize) -> String {
+    (0..count).map(|index| ((b'a' + (index % 26) as u8) as char).to_string()).collect::<Vec<String>>().join(", ")
+}
+
+fn unwrapped(ty: &Type, position: &str, span: &Span) -> Result<Type, BlazeError> {
+    match ty {
+        Type::Optional(ty, _) => Ok(*ty.clone()),
+        ty => Err(BlazeError::TypeError(ErrorCode::MismatchedTypes, format!("expected an optional {}, found '{}'", position, ty.name()), span.clone())),
+    }
+}
+
+fn not_narrowed(action: &str, ty: &Type, value: &Expression, span: &Span) -> BlazeError {
+    let help: String = match value {
+        Expression::Identifier(name, _) => format!("check it with 'if {} != null', or unwrap it with '{}.?'", name, name),
+        _ => "check it against 'null' first, or unwrap it with '.?'".to_string(),
+    };
+    BlazeError::TypeError(ErrorCode::UncheckedOptional, format!("cannot {} an optional '{}' that may be null", action, ty.name()), span.clone()).with_help(&help)
+}
+
+fn any_error(payload: &Type, span: &Span) -> Type {
+    Type::ErrorUnion(Box::new(Type::ErrorSet(None, span.clone())), Box::new(payload.clone()), span.clone())
+}
+
+fn error_union(ty: &Type, position: &str, span: &Span) -> Result<(Type, Type), BlazeError> {
+    match ty {
+        Type::ErrorUnion(set, payload, _) => Ok((*set.clone(), *payload.clone())),
+        ty => Err(BlazeError::TypeError(ErrorCode::MismatchedTypes, format!("expected an error union {}, found '{}'", position, ty.name()), span.clone())),
+    }
+}
+
+fn check_variants(variants: &[EnumVariant]) -> Result<(), BlazeError> {
+    for (index, variant) in variants.iter().enumerate() {
+        if let Some(other) = variants[..index].iter().find(|other| variant_name(other) == variant_name(variant)) {
+            return Err(BlazeError::TypeError(ErrorCode::DuplicateDefinition, format!("variant '{}' is already declared", variant_name(variant)), variant_span(variant))
+                .with_label(variant_span(other), "first declared here"));
+        }
+        if let EnumVariant::Payload(_, fields, _) = variant {
+            check_fields(fields)?;
+        }
+    }
+    Ok(())
+}
+
+fn check_exhaustive(enum_name: &str, variants: &[EnumVariant], covered: &[String], span: &Span) -> Result<(), BlazeError> {
+    if covered.iter().any(|name| name == "_") {
+        return Ok(());
+    }
+    let (_, short_name) = split_qualified(enum_name);
+    let missing: Vec<String> = variants.iter()
+        .filter(|variant| !covered.iter().any(|name| name == variant_name(variant)))
+        .map(|variant| format!("'{}::{}'", short_name, variant_name(variant)))
+        .collect();
+    match missing.len() {
+        0 => Ok(()),
+        1 => Err(BlazeError::TypeError(ErrorCode::NonExhaustiveMatch, format!("non-exhaustive match: {} is not covered", missing[0]), span.clone())
+            .with_help("add an arm for it, or a '_' arm")),
+        _ => Err(BlazeError::TypeError(ErrorCode::NonExhaustiveMatch, format!("non-exhaustive match: {} are not covered", missing.join(", ")), span.clone())
+            .with_help("add an arm for each of them, or a '_' arm")),
+    }
+}
+
+fn is_tagged(variants: &[EnumVariant]) -> bool {
+    variants.iter().any(|variant| matches!(variant, EnumVariant::Payload(_, _, _)))
+}
+
+fn variant_span(variant: &EnumVariant) -> Span {
+    match variant {
+        EnumVariant::Unit(_, span) | EnumVariant::Expression(_, _, span) | EnumVariant::Payload(_, _, span) => span.clone(),
+    }
+}
+
+fn variant_name(variant: &EnumVariant) -> &str {
+    match variant {
+        EnumVariant::Unit(name, _) | EnumVariant::Expression(name, _, _) | EnumVariant::Payload(name, _, _) => name,
+    }
+}
+
+fn method_owner(self_type: &Type, span: &Span) -> Result<String, BlazeError> {
+    match self_type {
+        Type::Pointer(ty, _) => method_owner(ty, span),
+        Type::Struct(name, _) | Type::Enum(name, _) | Type::Union(name, _) | Type::GenericInstance(name, _, _) => Ok(name.clone()),
+        _ => Err(BlazeError::TypeError(ErrorCode::InvalidMethod, format!("cannot define a method on '{}'", self_type.name()), span.clone())),
+    }
+}
+
+fn split_qualified(qualified: &str) -> (Vec<String>, String) {
+    match qualified.rsplit_once("::") {
+        Some((path, name)) => (path.split("::").map(|segment| segment.to_string()).collect(), name.to_string()),
+        None => (Vec::new(), qualified.to_string()),
+    }
+}
+
+fn static_path(expression: &Expression) -> Result<Vec<String>, BlazeError> {
+    match expression {
+        Expression::Identifier(name, _) => Ok(vec![name.clone()]),
+        Expression::StaticMemberAccess(left, right, _) => {
+            let mut segments: Vec<String> = static_path(left)?;
+            segments.extend(static_path(right)?);
+            Ok(segments)
+        }
+        _ => Err(BlazeError::TypeError(ErrorCode::ExpectedName, "expected a name".to_string(), expression.span())),
+    }
+}
+
+fn is_literal(expression: &Expression) -> bool {
+    match expression {
+        Expression::UnaryOperation(TokenKind::Minus | TokenKind::Tilde, value, _) => is_literal(value),
+        _ => matches!(expression, Expression::Integer(_, _, _) | Expression::Float(_, _, _) | Expression::Null(_)),
+    }
+}
+
+fn is_unsuffixed_integer(expression: &Expression) -> bool {
+    match expression {
+        Expression::UnaryOperation(TokenKind::Minus | TokenKind::Tilde, value, _) => is_unsuffixed_integer(value),
+        _ => matches!(expression, Expression::Integer(_, None, _)),
+    }
+}
+
+fn check_integer(value: u64, suffix: Option<&Type>, expected: Option<&Type>, negative: bool, span: &Span) -> Result<Type, BlazeError> {
+    let ty: Type = match suffix {
+        Some(ty) => ty.clone(),
+        None => match expected {
+            Some(ty) if ty.is_integer() || ty.is_float() => with_span(ty, span),
+            _ => Type::I64(span.clone()),
+        },
+    };
+    let (min, max): (i128, i128) = match ty {
+        Type::I8(_) => (i8::MIN as i128, i8::MAX as i128),
+        Type::I16(_) => (i16::MIN as i128, i16::MAX as i128),
+        Type::I32(_) => (i32::MIN as i128, i32::MAX as i128),
+        Type::I64(_) => (i64::MIN as i128, i64::MAX as i128),
+        Type::U8(_) => (0, u8::MAX as i128),
+        Type::U16(_) => (0, u16::MAX as i128),
+        Type::U32(_) => (0, u32::MAX as i128),
+        Type::U64(_) => (0, u64::MAX as i128),
+        _ => return Ok(ty),
+    };
+    let signed: i128 = if negative { -(value as i128) } else { value as i128 };
+    if signed < min || signed > max {
+        return Err(BlazeError::TypeError(
+            ErrorCode::LiteralOutOfRange,
+            format!("integer literal '{}' does not fit in '{}' (range {}..={})", signed, ty.name(), min, max),
+            span.clone(),
+        ));
+    }
+    Ok(ty)
+}
+
+fn with_span(ty: &Type, span: &Span) -> Type {
+    match ty {
+        Type::I8(_) => Type::I8(span.clone()),
+        Type::I16(_) => Type::I16(span.clone()),
+        Type::I32(_) => Type::I32(span.clone()),
+        Type::I64(_) => Type::I64(span.clone()),
+        Type::U8(_) => Type::U8(span.clone()),
+        Type::U16(_) => Type::U16(span.clone()),
+        Type::U32(_) => Type::U32(span.clone()),
+        Type::U64(_) => Type::U64(span.clone()),
+        Type::F32(_) => Type::F32(span.clone()),
+        Type::F64(_) => Type::F64(span.clone()),
+        ty => ty.clone(),
+    }
+}
\ No newline at end of file
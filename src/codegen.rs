@@ -0,0 +1,1824 @@
+use std::collections::{HashMap, HashSet};
+use std::mem::{discriminant, Discriminant};
+
+use crate::ast::{Statement, Expression, Type, StructField, EnumVariant, Pattern};
+use crate::checker::Types;
+use crate::error::{BlazeError, ErrorCode};
+use crate::mangle::{mangle, mangle_arguments, mangle_type};
+use crate::diagnostic::SourceMap;
+use crate::span::Span;
+use crate::token::TokenKind;
+
+#[derive(Debug, Clone)]
+enum Symbol {
+    Struct(Vec<Type>, Vec<StructField>),
+    Enum(Option<Type>, Vec<EnumVariant>),
+    ErrorSet(Vec<(String, Span)>),
+    Union(Vec<Type>),
+    Function(Vec<(String, Type)>, Vec<Type>),
+}
+
+#[derive(Debug, Clone)]
+struct Declaration {
+    symbol: Symbol,
+    span: Span,
+}
+
+#[derive(Debug, Clone)]
+struct Loop {
+    label: Option<String>,
+    id: usize,
+    depth: usize,
+    broken: bool,
+    continued: bool,
+}
+
+type Definition = (String, String, Vec<String>, Span);
+
+// Tells apart hoisted expressions that start at the same token, like `try parse(a) && ok`.
+type HoistedKey = (Span, Span, Discriminant<Expression>);
+
+#[derive(Debug, Clone)]
+pub struct Codegen {
+    pub statements: Vec<Statement>,
+    pub types: Types,
+    pub errors: Vec<BlazeError>,
+    pub output: String,
+    pub sources: SourceMap,
+    pub release: bool,
+    declarations: HashMap<String, Declaration>,
+    order: Vec<String>,
+    instances: Vec<(String, String, Vec<Type>)>,
+    wrappers: Vec<(String, Vec<StructField>)>,
+    instantiated: HashSet<String>,
+    // The `#extern` functions, which are called by the name the C library gives them.
+    externs: HashSet<String>,
+    path: Vec<String>,
+    function: String,
+    bindings: HashMap<String, String>,
+    loops: Vec<Loop>,
+    loop_count: usize,
+    defers: Vec<Vec<Statement>>,
+    hoisted: Vec<(HoistedKey, String)>,
+    temporaries: usize,
+    traps: bool,
+    checks_bounds: bool,
+    returns: Vec<Type>,
+    return_type: String,
+    indent: usize,
+    helpers: String,
+    prototypes: String,
+    globals: String,
+    bodies: String,
+}
+
+impl Codegen {
+    pub fn new(statements: Vec<Statement>) -> Codegen {
+        Codegen {
+            statements,
+            types: Types::default(),
+            errors: Vec::new(),
+            output: String::new(),
+            sources: SourceMap::new(),
+            release: false,
+            declarations: HashMap::new(),
+            order: Vec::new(),
+            instances: Vec::new(),
+            wrappers: Vec::new(),
+            instantiated: HashSet::new(),
+            externs: HashSet::new(),
+            path: Vec::new(),
+            function: String::new(),
+            bindings: HashMap::new(),
+            loops: Vec::new(),
+            loop_count: 0,
+            defers: Vec::new(),
+            hoisted: Vec::new(),
+            temporaries: 0,
+            traps: false,
+            checks_bounds: false,
+            returns: Vec::new(),
+            return_type: String::new(),
+            indent: 0,
+            helpers: String::new(),
+            prototypes: String::new(),
+            globals: String::new(),
+            bodies: String::new(),
+        }
+    }
+
+    pub fn generate(&mut self) -> Result<String, Vec<BlazeError>> {
+        let statements: Vec<Statement> = self.statements.clone();
+        self.declare(&statements);
+        if self.errors.is_empty() {
+            for statement in &statements {
+                if let Err(error) = self.emit_declaration(statement) {
+                    self.errors.push(error);
+                }
+            }
+        }
+        if !self.errors.is_empty() {
+            return Err(self.errors.clone());
+        }
+
+        let types: String = match self.emit_types() {
+            Ok(types) => types,
+            Err(error) => return Err(vec![error]),
+        };
+
+        self.output.push_str("#include <stdint.h>\n");
+        self.output.push_str("#include <stddef.h>\n");
+        self.output.push_str("#include <stdbool.h>\n\n");
+        if self.traps || self.checks_bounds || !self.externs.is_empty() {
+            self.output.push_str("#include <stdio.h>\n#include <stdlib.h>\n\n");
+        }
+        if self.traps || self.checks_bounds {
+            self.output.push_str("static void __blaze_trap(const char* file, int line, const char* message) {\n");
+            self.output.push_str("    fprintf(stderr, \"%s:%d: %s\\n\", file, line, message);\n    abort();\n}\n\n");
+        }
+        if self.checks_bounds {
+            self.output.push_str("static uint64_t __blaze_index(uint64_t index, uint64_t length, const char* file, int line) {\n");
+            self.output.push_str("    if (index >= length) {\n");
+            self.output.push_str("        fprintf(stderr, \"%s:%d: index %llu is out of bounds for length %llu\\n\", file, line, (unsigned long long)index, (unsigned long long)length);\n");
+            self.output.push_str("        abort();\n    }\n    return index;\n}\n\n");
+            self.output.push_str("static void __blaze_slice(uint64_t start, uint64_t end, uint64_t length, const char* file, int line) {\n");
+            self.output.push_str("    if (start > end || end > length) {\n");
+            self.output.push_str("        fprintf(stderr, \"%s:%d: slice %llu..%llu is out of bounds for length %llu\\n\", file, line, (unsigned long long)start, (unsigned long long)end, (unsigned long long)length);\n");
+            self.output.push_str("        abort();\n    }\n}\n\n");
+        }
+        self.output.push_str(types.as_str());
+        self.output.push_str(self.helpers.clone().as_str());
+        self.output.push_str(self.prototypes.clone().as_str());
+        self.output.push('\n');
+        if !self.globals.is_empty() {
+            self.output.push_str(self.globals.clone().as_str());
+            self.output.push('\n');
+        }
+        self.output.push_str(self.bodies.clone().as_str());
+        Ok(self.output.clone())
+    }
+
+    // Indexes the declarations of the checked program by their qualified names. The checker has already resolved
+    // the types in them.
+    fn declare(&mut self, statements: &[Statement]) {
+        for statement in statements {
+            if let Err(error) = self.declare_statement(statement) {
+                self.errors.push(error);
+            }
+        }
+    }
+    fn declare_statement(&mut self, statement: &Statement) -> Result<(), BlazeError> {
+        match statement {
+            Statement::Attribute(name, _, statement, _) if name == "extern" => {
+                if let Statement::Function(name, _, _, _, _) = &**statement {
+                    self.externs.insert(self.qualify(name));
+                }
+                self.declare_statement(statement)?;
+            }
+            Statement::Documented(_, statement, _) | Statement::Attribute(_, _, statement, _) => {
+                self.declare_statement(statement)?;
+            }
+            Statement::Namespace(name, statements, _) => {
+                self.path.push(name.clone());
+                self.declare(statements);
+                self.path.pop();
+            }
+            Statement::Struct(name, generics, _, fields, span) => {
+                self.insert(self.qualify(name), Symbol::Struct(generics.clone(), fields.clone()), span);
+            }
+            Statement::Enum(name, variants, span) => {
+                self.insert(self.qualify(name), Symbol::Enum(None, variants.clone()), span);
+            }
+            Statement::TypedEnum(name, ty, variants, span) => {
+                self.insert(self.qualify(name), Symbol::Enum(Some(ty.clone()), variants.clone()), span);
+            }
+            Statement::ErrorSet(name, errors, span) => {
+                self.insert(self.qualify(name), Symbol::ErrorSet(errors.clone()), span);
+            }
+            Statement::Union(name, types, span) => {
+                self.insert(self.qualify(name), Symbol::Union(types.clone()), span);
+            }
+            // Generic functions are emitted through the instances the checker added next to them.
+            Statement::Function(_, parameters, returns, _, _) if is_generic(parameters, returns) => {}
+            Statement::Function(name, parameters, returns, _, span) => {
+                let parameters: Vec<(String, Type)> = parameters.iter()
+                    .map(|(name, ty, _, _)| (name.clone(), ty.clone()))
+                    .collect();
+                self.insert(self.qualify(name), Symbol::Function(parameters, values(returns)), span);
+            }
+            Statement::StructFunction(self_type, _, parameters, returns, _, _)
+                if !self_type.generics().is_empty() || is_generic(parameters, returns) => {}
+            Statement::StructFunction(self_type, name, parameters, returns, _, span) => {
+                let qualified: String = format!("{}::{}", self.method_owner(self_type, span)?, name);
+                let mut all_parameters: Vec<(String, Type)> = vec![("self".to_string(), self_type.clone())];
+                all_parameters.extend(parameters.iter().map(|(name, ty, _, _)| (name.clone(), ty.clone())));
+                self.insert(qualified, Symbol::Function(all_parameters, values(returns)), span);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+    fn insert(&mut self, qualified: String, symbol: Symbol, span: &Span) {
+        self.declarations.insert(qualified.clone(), Declaration { symbol, span: span.clone() });
+        self.order.push(qualified);
+    }
+    fn qualify(&self, name: &str) -> String {
+        let mut segments: Vec<String> = self.path.clone();
+        segments.push(name.to_string());
+        segments.join("::")
+    }
+    fn method_owner(&self, self_type: &Type, span: &Span) -> Result<String, BlazeError> {
+        match self_type {
+            Type::Pointer(ty, _) => self.method_owner(ty, span),
+            Type::Struct(name, _) | Type::Enum(name, _) | Type::Union(name, _) => Ok(name.clone()),
+            _ => Err(BlazeError::CodegenError(ErrorCode::InvalidMethod, format!("cannot define a method on '{}'", self_type.name()), span.clone())),
+        }
+    }
+
+    fn emit_declaration(&mut self, statement: &Statement) -> Result<(), BlazeError> {
+        match statement {
+            // The C library defines it, and the headers included for it declare it.
+            Statement::Attribute(name, _, _, _) if name == "extern" => Ok(()),
+            Statement::Documented(_, statement, _) | Statement::Attribute(_, _, statement, _) => self.emit_declaration(statement),
+            Statement::Namespace(name, statements, _) => {
+                self.path.push(name.clone());
+                for statement in statements {
+                    if let Err(error) = self.emit_declaration(statement) {
+                        self.errors.push(error);
+                    }
+                }
+                self.path.pop();
+                Ok(())
+            }
+            Statement::Function(_, parameters, returns, _, _) if is_generic(parameters, returns) => Ok(()),
+            Statement::Function(name, _, _, body, span) => {
+                let qualified: String = self.qualify(name);
+                self.emit_function(&qualified, body, span)
+            }
+            Statement::StructFunction(self_type, _, parameters, returns, _, _)
+                if !self_type.generics().is_empty() || is_generic(parameters, returns) => Ok(()),
+            Statement::StructFunction(self_type, name, _, _, body, span) => {
+                let qualified: String = format!("{}::{}", self.method_owner(self_type, span)?, name);
+                self.emit_function(&qualified, body, span)
+            }
+            Statement::ConstantDeclaration(name, ty, value, span)
+            | Statement::VariableDeclaration(name, ty, value, span)
+            | Statement::MutableDeclaration(name, ty, value, span) => {
+                let qualified: String = self.qualify(name);
+                self.function = qualified.clone();
+                let declarator: String = self.declarator(ty, &mangle(&qualified), span)?;
+                let value: String = self.coerce(ty, value)?;
+                let qualifier: &str = if let Statement::ConstantDeclaration(..) = statement { "const " } else { "" };
+                self.globals.push_str(format!("static {}{} = {};\n", qualifier, declarator, value).as_str());
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+    fn emit_function(&mut self, qualified: &str, body: &[Statement], span: &Span) -> Result<(), BlazeError> {
+        let (parameters, returns) = match &self.declarations[qualified].symbol {
+            Symbol::Function(parameters, returns) => (parameters.clone(), returns.clone()),
+            _ => unreachable!("Codegen::emit_function()"),
+        };
+        let is_main: bool = qualified == "main";
+        let fails: bool = matches!(returns.as_slice(), [Type::ErrorUnion(_, _, _)]);
+        let return_type: String = match returns.len() {
+            0 if is_main => "int".to_string(),
+            0 => "void".to_string(),
+            1 => self.c_type(&returns[0], span)?,
+            _ => self.returns_type(&returns, span),
+        };
+
+        let mut c_parameters: Vec<String> = Vec::new();
+        for (name, ty) in &parameters {
+            if let Type::VarArgs(_, _) = ty {
+                c_parameters.push("...".to_string());
+                continue;
+            }
+            c_parameters.push(self.declarator(ty, &mangle(name), span)?);
+        }
+        if c_parameters.is_empty() {
+            c_parameters.push("void".to_string());
+        }
+        // A `main` that can fail is wrapped in one that turns an error into exit status 1.
+        let c_name: String = if is_main && fails { "__main".to_string() } else { mangle(qualified) };
+        let signature: String = format!("{} {}({})", return_type, c_name, c_parameters.join(", "));
+        self.prototypes.push_str(format!("{};\n", signature).as_str());
+
+        self.function = qualified.to_string();
+        self.indent = 1;
+        self.returns = returns.clone();
+        self.return_type = return_type.clone();
+        let mut out: String = format!("{}{} {{\n", self.line_directive(span), signature);
+        let result: Result<(), BlazeError> = self.block(body, &mut out);
+        if is_main && returns.is_empty() {
+            out.push_str("    return 0;\n");
+        }
+        if let [Type::ErrorUnion(_, payload, _)] = returns.as_slice() {
+            if matches!(**payload, Type::Void(_)) && !body.last().is_some_and(is_jump) {
+                out.push_str(format!("    return ({}){{ .error = 0 }};\n", return_type).as_str());
+            }
+        }
+        out.push_str("}\n\n");
+        if is_main && fails {
+            let arguments: Vec<&str> = parameters.iter().map(|(name, _)| name.as_str()).collect();
+            let status: &str = match &returns[0] {
+                Type::ErrorUnion(_, payload, _) if matches!(**payload, Type::Void(_)) => "0",
+                _ => "__result.value",
+            };
+            out.push_str(format!(
+                "int main({}) {{\n    {} __result = __main({});\n    return __result.error ? 1 : {};\n}}\n\n",
+                c_parameters.join(", "), return_type, arguments.join(", "), status,
+            ).as_str());
+        }
+        self.indent = 0;
+        self.defers.clear();
+        self.hoisted.clear();
+        result?;
+        self.bodies.push_str(out.as_str());
+        Ok(())
+    }
+
+    // Runs the statements deferred in a block when it falls off its end. Leaving it early through a `return`,
+    // `break` or `continue` runs them at the jump instead.
+    fn block(&mut self, statements: &[Statement], out: &mut String) -> Result<(), BlazeError> {
+        self.defers.push(Vec::new());
+        let result: Result<(), BlazeError> = statements.iter().try_for_each(|statement| self.statement(statement, out));
+        let deferred: Vec<Statement> = self.defers.pop().unwrap();
+        result?;
+        if !statements.last().is_some_and(is_jump) {
+            self.emit_deferred(vec![deferred], out)?;
+        }
+        Ok(())
+    }
+    fn emit_deferred(&mut self, frames: Vec<Vec<Statement>>, out: &mut String) -> Result<(), BlazeError> {
+        for statement in frames.iter().rev().flat_map(|frame| frame.iter().rev()) {
+            self.statement(statement, out)?;
+        }
+        Ok(())
+    }
+    fn statement(&mut self, statement: &Statement, out: &mut String) -> Result<(), BlazeError> {
+        if let Statement::Documented(_, statement, _) | Statement::Attribute(_, _, statement, _) = statement {
+            return self.statement(statement, out);
+        }
+        if let Statement::Defer(statement, _) = statement {
+            self.defers.last_mut().unwrap().push((**statement).clone());
+            return Ok(());
+        }
+        let indent: String = "    ".repeat(self.indent);
+        out.push_str(self.line_directive(&statement.span()).as_str());
+        for value in statement_values(statement) {
+            self.hoist(value, out)?;
+        }
+        match statement {
+            Statement::ConstantDeclaration(name, ty, value, span)
+            | Statement::VariableDeclaration(name, ty, value, span)
+            | Statement::MutableDeclaration(name, ty, value, span) => {
+                let declarator: String = self.declarator(ty, &mangle(name), span)?;
+                let value: String = self.coerce(ty, value)?;
+                self.bindings.remove(name);
+                let qualifier: &str = if let Statement::ConstantDeclaration(..) = statement { "const " } else { "" };
+                out.push_str(format!("{}{}{} = {};\n", indent, qualifier, declarator, value).as_str());
+            }
+            Statement::Return(values, _) if self.defers.iter().any(|frame| !frame.is_empty()) => {
+                let frames: Vec<Vec<Statement>> = self.defers.clone();
+                out.push_str(format!("{}{{\n", indent).as_str());
+                self.indent += 1;
+                let result: Result<(), BlazeError> = match values.is_empty() {
+                    false => {
+                        let value: String = self.return_value(values)?;
+                        out.push_str(format!("{}    {} __result = {};\n", indent, self.return_type, value).as_str());
+                        self.emit_deferred(frames, out).map(|_| out.push_str(format!("{}    return __result;\n", indent).as_str()))
+                    }
+                    true => {
+                        let value: String = self.success();
+                        self.emit_deferred(frames, out).map(|_| out.push_str(format!("{}    return{};\n", indent, value).as_str()))
+                    }
+                };
+                self.indent -= 1;
+                result?;
+                out.push_str(format!("{}}}\n", indent).as_str());
+            }
+            Statement::Return(values, _) if values.is_empty() => out.push_str(format!("{}return{};\n", indent, self.success()).as_str()),
+            Statement::Return(values, _) => {
+                let value: String = self.return_value(values)?;
+                out.push_str(format!("{}return {};\n", indent, value).as_str());
+            }
+            Statement::Destructuring(names, value, span) => {
+                let types: Vec<Type> = self.call_returns(value)
+                    .ok_or_else(|| BlazeError::CodegenError(ErrorCode::CannotInfer, "cannot infer the types of the destructured values".to_string(), value.span()))?;
+                let c_type: String = self.returns_type(&types, span);
+                self.temporaries += 1;
+                let temporary: String = format!("__values{}", self.temporaries);
+                let value: String = self.expression(value)?;
+                out.push_str(format!("{}{} {} = {};\n", indent, c_type, temporary, value).as_str());
+                for (index, ((name, _, name_span), ty)) in names.iter().zip(types).enumerate() {
+                    if name == "_" {
+                        continue;
+                    }
+                    let declarator: String = self.declarator(&ty, &mangle(name), name_span)?;
+                    out.push_str(format!("{}{} = {}._{};\n", indent, declarator, temporary, index).as_str());
+                    self.bindings.remove(name);
+                }
+            }
+            Statement::While(..) | Statement::For(..) => self.emit_loop(None, statement, out)?,
+            Statement::Labeled(label, statement, _) => self.emit_loop(Some(label.clone()), statement, out)?,
+            Statement::Break(label, _) | Statement::Continue(label, _) => {
+                let breaks: bool = matches!(statement, Statement::Break(..));
+                let index: usize = match label {
+                    Some(label) => self.loops.iter().rposition(|target| target.label.as_ref() == Some(label)).unwrap(),
+                    None => self.loops.len() - 1,
+                };
+                let innermost: bool = index + 1 == self.loops.len();
+                let frames: Vec<Vec<Statement>> = self.defers[self.loops[index].depth..].to_vec();
+                self.emit_deferred(frames, out)?;
+                let target: &mut Loop = &mut self.loops[index];
+                let jump: String = match (innermost, breaks) {
+                    (true, true) => "break".to_string(),
+                    (true, false) => "continue".to_string(),
+                    (false, true) => {
+                        target.broken = true;
+                        format!("goto {}", loop_label("break", target))
+                    }
+                    (false, false) => {
+                        target.continued = true;
+                        format!("goto {}", loop_label("continue", target))
+                    }
+                };
+                out.push_str(format!("{}{};\n", indent, jump).as_str());
+            }
+            Statement::If(value, Some((name, _)), then_body, else_body, span) => {
+                let ty: Type = self.type_of(value)
+                    .ok_or_else(|| BlazeError::CodegenError(ErrorCode::CannotInfer, "cannot infer the type of the unwrapped value".to_string(), span.clone()))?;
+                let inner: Type = match &ty {
+                    Type::Optional(inner, _) => *inner.clone(),
+                    _ => return Err(BlazeError::CodegenError(ErrorCode::MismatchedTypes, format!("expected an optional, found '{}'", ty.name()), value.span())),
+                };
+                self.temporaries += 1;
+                let temporary: String = format!("__if{}", self.temporaries);
+                let declarator: String = self.declarator(&ty, &temporary, span)?;
+                let value: String = self.expression(value)?;
+                out.push_str(format!("{}{{\n{}    {} = {};\n", indent, indent, declarator, value).as_str());
+                let (present, unwrapped) = optional_parts(&ty, &temporary);
+                out.push_str(format!("{}    if ({}) {{\n", indent, present).as_str());
+                out.push_str(format!("{}        {} = {};\n", indent, self.declarator(&inner, &mangle(name), span)?, unwrapped).as_str());
+                self.indent += 1;
+                let saved: Option<String> = self.bindings.remove(name);
+                let result: Result<(), BlazeError> = self.nested_block(then_body, out);
+                if let Some(saved) = saved {
+                    self.bindings.insert(name.clone(), saved);
+                }
+                result?;
+                if !else_body.is_empty() {
+                    out.push_str(format!("{}    }} else {{\n", indent).as_str());
+                    self.nested_block(else_body, out)?;
+                }
+                self.indent -= 1;
+                out.push_str(format!("{}    }}\n{}}}\n", indent, indent).as_str());
+            }
+            Statement::If(condition, None, then_body, else_body, _) => self.if_chain(condition, then_body, else_body, out)?,
+            Statement::Block(body, _) => {
+                out.push_str(format!("{}{{\n", indent).as_str());
+                self.nested_block(body, out)?;
+                out.push_str(format!("{}}}\n", indent).as_str());
+            }
+            Statement::Match(value, arms, span) => {
+                let ty: Type = self.type_of(value)
+                    .ok_or_else(|| BlazeError::CodegenError(ErrorCode::CannotInfer, "cannot infer the type of the matched value".to_string(), span.clone()))?;
+                let declarator: String = self.declarator(&ty, "__match", span)?;
+                let value: String = self.expression(value)?;
+                out.push_str(format!("{}{{\n{}    {} = {};\n", indent, indent, declarator, value).as_str());
+                self.indent += 1;
+                let mut opened: bool = false;
+                for (pattern, body, _) in arms {
+                    let keyword: &str = if opened { "} else " } else { "" };
+                    let arm_indent: String = "    ".repeat(self.indent);
+                    match self.pattern_condition(pattern, "__match")? {
+                        Some(condition) => out.push_str(format!("{}{}if ({}) {{\n", arm_indent, keyword, condition).as_str()),
+                        None if opened => out.push_str(format!("{}}} else {{\n", arm_indent).as_str()),
+                        None => out.push_str(format!("{}{{\n", arm_indent).as_str()),
+                    }
+                    opened = true;
+                    self.indent += 1;
+                    for (name, ty, field) in self.pattern_bindings(pattern, "__match")? {
+                        out.push_str(format!("{}    {} = {};\n", arm_indent, self.declarator(&ty, &mangle(&name), span)?, field).as_str());
+                    }
+                    let result: Result<(), BlazeError> = self.block(body, out);
+                    self.indent -= 1;
+                    result?;
+                    if let Pattern::Wildcard(_) = pattern {
+                        break;
+                    }
+                }
+                self.indent -= 1;
+                if opened {
+                    out.push_str(format!("{}    }}\n", indent).as_str());
+                }
+                out.push_str(format!("{}}}\n", indent).as_str());
+            }
+            Statement::Expression(expression, _) => {
+                let expression: String = self.expression(expression)?;
+                out.push_str(format!("{}{};\n", indent, expression).as_str());
+            }
+            _ => {
+                return Err(BlazeError::CodegenError(ErrorCode::MisplacedDeclaration, "declarations are only allowed at the top level or in a namespace".to_string(), statement.span()));
+            }
+        }
+        Ok(())
+    }
+    // Emits an `if` and the `else if`s chained after it. Each branch sees the optionals that its condition and
+    // those of the branches before it have checked against `null` as their values.
+    fn if_chain(&mut self, condition: &Expression, then_body: &[Statement], else_body: &[Statement], out: &mut String) -> Result<(), BlazeError> {
+        let indent: String = "    ".repeat(self.indent);
+        let c_condition: String = self.expression(condition)?;
+        out.push_str(format!("{}if ({}) {{\n", indent, c_condition).as_str());
+        self.nested_block(then_body, out)?;
+        let mut else_body: &[Statement] = else_body;
+        while let [Statement::If(next_condition, None, then_body, next, _)] = else_body {
+            if is_hoisted(next_condition) {
+                break;
+            }
+            let c_condition: String = self.expression(next_condition)?;
+            out.push_str(format!("{}}} else if ({}) {{\n", indent, c_condition).as_str());
+            self.nested_block(then_body, out)?;
+            else_body = next;
+        }
+        if !else_body.is_empty() {
+            out.push_str(format!("{}}} else {{\n", indent).as_str());
+            self.nested_block(else_body, out)?;
+        }
+        out.push_str(format!("{}}}\n", indent).as_str());
+        Ok(())
+    }
+    // Labeled `break` and `continue` from an inner loop become jumps to labels after the loop and at the end of its body.
+    fn emit_loop(&mut self, label: Option<String>, statement: &Statement, out: &mut String) -> Result<(), BlazeError> {
+        let indent: String = "    ".repeat(self.indent);
+        let (header, body, prelude) = match statement {
+            // An unwrapped optional is evaluated at the start of every iteration, which stops once it is `null`.
+            Statement::While(value, Some((name, _)), body, span) => {
+                let ty: Type = self.type_of(value)
+                    .ok_or_else(|| BlazeError::CodegenError(ErrorCode::CannotInfer, "cannot infer the type of the unwrapped value".to_string(), span.clone()))?;
+                let inner: Type = match &ty {
+                    Type::Optional(inner, _) => *inner.clone(),
+                    _ => return Err(BlazeError::CodegenError(ErrorCode::MismatchedTypes, format!("expected an optional, found '{}'", ty.name()), value.span())),
+                };
+                self.temporaries += 1;
+                let temporary: String = format!("__while{}", self.temporaries);
+                let mut prelude: String = String::new();
+                self.indent += 1;
+                let result: Result<String, BlazeError> = self.hoist(value, &mut prelude).and_then(|_| self.expression(value));
+                self.indent -= 1;
+                let (present, unwrapped) = optional_parts(&ty, &temporary);
+                prelude.push_str(format!("{}    {} = {};\n", indent, self.declarator(&ty, &temporary, span)?, result?).as_str());
+                prelude.push_str(format!("{}    if (!({})) break;\n", indent, present).as_str());
+                prelude.push_str(format!("{}    {} = {};\n", indent, self.declarator(&inner, &mangle(name), span)?, unwrapped).as_str());
+                ("while (1)".to_string(), body, Some(prelude))
+            }
+            // A condition with hoisted values is evaluated again at the start of every iteration.
+            Statement::While(condition, None, body, _) if is_hoisted(condition) => {
+                let mut prelude: String = String::new();
+                self.indent += 1;
+                let result: Result<String, BlazeError> = self.hoist(condition, &mut prelude).and_then(|_| self.expression(condition));
+                self.indent -= 1;
+                prelude.push_str(format!("{}    if (!({})) break;\n", indent, result?).as_str());
+                ("while (1)".to_string(), body, Some(prelude))
+            }
+            Statement::While(condition, None, body, _) => (format!("while ({})", self.expression(condition)?), body, None),
+            Statement::For((name, _), Expression::Range(start, end, _), body, span) => {
+                let typed: &Expression = if let Expression::Integer(_, None, _) = **start { end } else { start };
+                let ty: Type = self.type_of(typed)
+                    .ok_or_else(|| BlazeError::CodegenError(ErrorCode::CannotInfer, "cannot infer the type of the range".to_string(), span.clone()))?;
+                let c_type: String = self.c_type(&ty, span)?;
+                let start: String = self.expression(start)?;
+                let end: String = self.expression(end)?;
+                out.push_str(format!("{}{{\n{}    {} __end = {};\n", indent, indent, c_type, end).as_str());
+                self.indent += 1;
+                let header: String = format!("for ({0} {1} = {2}; {1} < __end; {1} += 1)", c_type, mangle(name), start);
+                (header, body, None)
+            }
+            // An array is copied and a slice read through a copy of its pointer and length, so both are evaluated once.
+            Statement::For((name, _), iterable, body, span) => {
+                let ty: Type = self.type_of(iterable)
+                    .ok_or_else(|| BlazeError::CodegenError(ErrorCode::CannotInfer, "cannot infer the type of the iterated value".to_string(), iterable.span()))?;
+                self.temporaries += 1;
+                let iterated: String = format!("__iterable{}", self.temporaries);
+                let index: String = format!("__index{}", self.temporaries);
+                let (element, length, item) = match &ty {
+                    Type::FixedArray(element, length, _) => (*element.clone(), length.to_string(), format!("{}.items[{}]", iterated, index)),
+                    Type::Array(element, _) => (*element.clone(), format!("{}.len", iterated), format!("{}.ptr[{}]", iterated, index)),
+                    Type::Str(s) => (Type::Char(s.clone()), format!("{}.len", iterated), format!("{}.ptr[{}]", iterated, index)),
+                    _ => return Err(BlazeError::CodegenError(ErrorCode::InvalidOperand, format!("cannot iterate over a value of type '{}'", ty.name()), iterable.span())),
+                };
+                let declarator: String = self.declarator(&ty, &iterated, span)?;
+                let value: String = self.expression(iterable)?;
+                out.push_str(format!("{}{{\n{}    {} = {};\n", indent, indent, declarator, value).as_str());
+                self.indent += 1;
+                let header: String = format!("for (uint64_t {} = 0; {} < {}; {} += 1)", index, index, length, index);
+                let binding: Option<String> = match name.as_str() {
+                    "_" => None,
+                    _ => Some(format!("{}    {} = {};\n", "    ".repeat(self.indent), self.declarator(&element, &mangle(name), span)?, item)),
+                };
+                (header, body, binding)
+            }
+            _ => unreachable!("Codegen::emit_loop()"),
+        };
+        let loop_indent: String = "    ".repeat(self.indent);
+        out.push_str(format!("{}{} {{\n", loop_indent, header).as_str());
+        if let Some(prelude) = prelude {
+            out.push_str(prelude.as_str());
+        }
+        self.loop_count += 1;
+        self.loops.push(Loop { label, id: self.loop_count, depth: self.defers.len(), broken: false, continued: false });
+        let saved: Option<(String, Option<String>)> = match statement {
+            Statement::While(_, Some((name, _)), _, _) => Some((name.clone(), self.bindings.remove(name))),
+            _ => None,
+        };
+        let result: Result<(), BlazeError> = self.nested_block(body, out);
+        if let Some((name, Some(binding))) = saved {
+            self.bindings.insert(name, binding);
+        }
+        let target: Loop = self.loops.pop().unwrap();
+        if target.continued {
+            out.push_str(format!("{}    {}: ;\n", loop_indent, loop_label("continue", &target)).as_str());
+        }
+        out.push_str(format!("{}}}\n", loop_indent).as_str());
+        if let Statement::For(..) = statement {
+            self.indent -= 1;
+            out.push_str(format!("{}}}\n", indent).as_str());
+        }
+        if target.broken {
+            out.push_str(format!("{}{}: ;\n", indent, loop_label("break", &target)).as_str());
+        }
+        result
+    }
+    fn nested_block(&mut self, statements: &[Statement], out: &mut String) -> Result<(), BlazeError> {
+        self.indent += 1;
+        let result: Result<(), BlazeError> = self.block(statements, out);
+        self.indent -= 1;
+        result
+    }
+    // Evaluates every `try` and `catch` in a value into a temporary before the statement it is in, where a failed
+    // `try` returns its error from. Optionals that are not places are stored in a temporary the same way, so that
+    // unwrapping them evaluates them once. Operands that C would only evaluate conditionally are hoisted into an `if`.
+    fn hoist(&mut self, expression: &Expression, out: &mut String) -> Result<(), BlazeError> {
+        let conditional: Vec<&Expression> = match expression {
+            Expression::BinaryOperation(_, TokenKind::AmpersandAmpersand | TokenKind::PipePipe, right, _) => vec![right],
+            Expression::Match(_, arms, _) => arms.iter().map(|(_, arm, _)| arm).collect(),
+            Expression::Catch(_, _, fallback, _) | Expression::Orelse(_, fallback, _) => vec![fallback],
+            _ => Vec::new(),
+        };
+        if conditional.into_iter().any(is_hoisted) {
+            let lowered: String = self.hoist_conditional(expression, out)?;
+            self.hoisted.push((hoisted_key(expression), lowered));
+            return Ok(());
+        }
+        for operand in expression.operands() {
+            self.hoist(operand, out)?;
+        }
+        if let Expression::Orelse(value, _, span) | Expression::Unwrap(value, span) = expression {
+            if is_place(value) {
+                return Ok(());
+            }
+            let ty: Type = self.type_of(value)
+                .ok_or_else(|| BlazeError::CodegenError(ErrorCode::CannotInfer, "cannot infer the type of this value".to_string(), value.span()))?;
+            self.temporaries += 1;
+            let temporary: String = format!("__optional{}", self.temporaries);
+            let declarator: String = self.declarator(&ty, &temporary, span)?;
+            let c_value: String = self.expression(value)?;
+            out.push_str(format!("{}{} = {};\n", "    ".repeat(self.indent), declarator, c_value).as_str());
+            let lowered: String = self.unwrap_optional(expression, &ty, &temporary)?;
+            self.hoisted.push((hoisted_key(expression), lowered));
+            return Ok(());
+        }
+        if let Expression::AddressOf(value, span) = expression {
+            if !needs_storage(value) {
+                return Ok(());
+            }
+            // A call records the type of the method call it is the receiver of, so the type comes from the pointer.
+            let ty: Type = match self.type_of(expression) {
+                Some(Type::Pointer(ty, _)) => *ty,
+                _ => return Err(BlazeError::CodegenError(ErrorCode::CannotInfer, "cannot infer the type of this value".to_string(), value.span())),
+            };
+            self.temporaries += 1;
+            let temporary: String = format!("__stored{}", self.temporaries);
+            let declarator: String = self.declarator(&ty, &temporary, span)?;
+            let c_value: String = self.expression(value)?;
+            out.push_str(format!("{}{} = {};\n", "    ".repeat(self.indent), declarator, c_value).as_str());
+            self.hoisted.push((hoisted_key(expression), format!("&{}", temporary)));
+            return Ok(());
+        }
+        let (value, span) = match expression {
+            Expression::Try(value, span) | Expression::Catch(value, _, _, span) => (value, span),
+            _ => return Ok(()),
+        };
+        let ty: Type = self.type_of(value)
+            .ok_or_else(|| BlazeError::CodegenError(ErrorCode::CannotInfer, "cannot infer the type of this value".to_string(), value.span()))?;
+        let payload: Type = match &ty {
+            Type::ErrorUnion(_, payload, _) => *payload.clone(),
+            _ => return Err(BlazeError::CodegenError(ErrorCode::MismatchedTypes, format!("expected an error union, found '{}'", ty.name()), value.span())),
+        };
+        let indent: String = "    ".repeat(self.indent);
+        self.temporaries += 1;
+        let temporary: String = format!("__{}{}", if let Expression::Try(..) = expression { "try" } else { "catch" }, self.temporaries);
+        let declarator: String = self.declarator(&ty, &temporary, span)?;
+        let c_value: String = self.expression(value)?;
+        out.push_str(format!("{}{} = {};\n", indent, declarator, c_value).as_str());
+        let lowered: String = match expression {
+            Expression::Try(..) => {
+                out.push_str(format!("{}if ({}.error) {{\n", indent, temporary).as_str());
+                self.indent += 1;
+                let frames: Vec<Vec<Statement>> = self.defers.clone();
+                let result: Result<(), BlazeError> = self.emit_deferred(frames, out);
+                self.indent -= 1;
+                result?;
+                out.push_str(format!("{}    return ({}){{ .error = {}.error }};\n{}}}\n", indent, self.return_type, temporary, indent).as_str());
+                match payload {
+                    Type::Void(_) => "(void)0".to_string(),
+                    _ => format!("{}.value", temporary),
+                }
+            }
+            Expression::Catch(_, capture, fallback, _) => {
+                let saved: HashMap<String, String> = self.bindings.clone();
+                if let Some((name, _)) = capture {
+                    self.bindings.insert(name.clone(), format!("{}.error", temporary));
+                }
+                let result: Result<String, BlazeError> = self.coerce(&payload, fallback);
+                self.bindings = saved;
+                match payload {
+                    Type::Void(_) => format!("({}.error ? (void)({}) : (void)0)", temporary, result?),
+                    _ => format!("({}.error ? {} : {}.value)", temporary, result?, temporary),
+                }
+            }
+            _ => unreachable!("Codegen::hoist()"),
+        };
+        self.hoisted.push((hoisted_key(expression), lowered));
+        Ok(())
+    }
+    // Lowers a value with conditional operands that need hoisting into a temporary that an `if` assigns, so that
+    // those operands are only evaluated, and a `try` in them only returns, when C would evaluate them.
+    fn hoist_conditional(&mut self, expression: &Expression, out: &mut String) -> Result<String, BlazeError> {
+        let indent: String = "    ".repeat(self.indent);
+        self.temporaries += 1;
+        let id: usize = self.temporaries;
+        match expression {
+            Expression::BinaryOperation(left, operator, right, _) => {
+                self.hoist(left, out)?;
+                let temporary: String = format!("__condition{}", id);
+                let left: String = self.expression(left)?;
+                let negation: &str = if *operator == TokenKind::PipePipe { "!" } else { "" };
+                out.push_str(format!("{}bool {} = {};\n{}if ({}{}) {{\n", indent, temporary, left, indent, negation, temporary).as_str());
+                self.branch(right, Some((&temporary, &Type::Bool(right.span()))), out)?;
+                out.push_str(format!("{}}}\n", indent).as_str());
+                Ok(temporary)
+            }
+            Expression::Catch(value, capture, fallback, span) => {
+                self.hoist(value, out)?;
+                let ty: Type = self.type_of(value)
+                    .ok_or_else(|| BlazeError::CodegenError(ErrorCode::CannotInfer, "cannot infer the type of this value".to_string(), value.span()))?;
+                let payload: Type = match &ty {
+                    Type::ErrorUnion(_, payload, _) => *payload.clone(),
+                    _ => return Err(BlazeError::CodegenError(ErrorCode::MismatchedTypes, format!("expected an error union, found '{}'", ty.name()), value.span())),
+                };
+                let union: String = format!("__catch{}", id);
+                let declarator: String = self.declarator(&ty, &union, span)?;
+                let c_value: String = self.expression(value)?;
+                out.push_str(format!("{}{} = {};\n", indent, declarator, c_value).as_str());
+                let temporary: Option<String> = match payload {
+                    Type::Void(_) => None,
+                    _ => Some(format!("__caught{}", id)),
+                };
+                if let Some(temporary) = &temporary {
+                    out.push_str(format!("{}{};\n", indent, self.declarator(&payload, temporary, span)?).as_str());
+                }
+                out.push_str(format!("{}if ({}.error) {{\n", indent, union).as_str());
+                let saved: HashMap<String, String> = self.bindings.clone();
+                if let Some((name, _)) = capture {
+                    self.bindings.insert(name.clone(), format!("{}.error", union));
+                }
+                let result: Result<(), BlazeError> = self.branch(fallback, temporary.as_deref().map(|temporary| (temporary, &payload)), out);
+                self.bindings = saved;
+                result?;
+                if let Some(temporary) = &temporary {
+                    out.push_str(format!("{}}} else {{\n{}    {} = {}.value;\n", indent, indent, temporary, union).as_str());
+                }
+                out.push_str(format!("{}}}\n", indent).as_str());
+                Ok(temporary.unwrap_or_else(|| "(void)0".to_string()))
+            }
+            Expression::Orelse(value, fallback, span) => {
+                self.hoist(value, out)?;
+                let ty: Type = self.type_of(value)
+                    .ok_or_else(|| BlazeError::CodegenError(ErrorCode::CannotInfer, "cannot infer the type of this value".to_string(), value.span()))?;
+                let inner: Type = match &ty {
+                    Type::Optional(inner, _) => *inner.clone(),
+                    _ => return Err(BlazeError::CodegenError(ErrorCode::MismatchedTypes, format!("expected an optional, found '{}'", ty.name()), value.span())),
+                };
+                let optional: String = match is_place(value) {
+                    true => self.operand(value)?,
+                    false => {
+                        let optional: String = format!("__optional{}", id);
+                        let declarator: String = self.declarator(&ty, &optional, span)?;
+                        let c_value: String = self.expression(value)?;
+                        out.push_str(format!("{}{} = {};\n", indent, declarator, c_value).as_str());
+                        optional
+                    }
+                };
+                let (present, unwrapped) = optional_parts(&ty, &optional);
+                let temporary: String = format!("__value{}", id);
+                out.push_str(format!("{}{};\n", indent, self.declarator(&inner, &temporary, span)?).as_str());
+                out.push_str(format!("{}if ({}) {{\n{}    {} = {};\n{}}} else {{\n", indent, present, indent, temporary, unwrapped, indent).as_str());
+                self.branch(fallback, Some((&temporary, &inner)), out)?;
+                out.push_str(format!("{}}}\n", indent).as_str());
+                Ok(temporary)
+            }
+            // Like a `match` expression lowered to conditionals, the last arm is taken when no other one matches.
+            Expression::Match(value, arms, span) => {
+                if !is_place(value) {
+                    return Err(BlazeError::CodegenError(
+                        ErrorCode::Unsupported,
+                        "matching on this expression is not supported by the C backend yet; store the value in a variable first".to_string(),
+                        value.span(),
+                    ));
+                }
+                if arms.is_empty() {
+                    return Err(BlazeError::CodegenError(ErrorCode::NonExhaustiveMatch, "a 'match' expression needs at least one arm".to_string(), span.clone()));
+                }
+                let ty: Type = self.type_of(expression)
+                    .ok_or_else(|| BlazeError::CodegenError(ErrorCode::CannotInfer, "cannot infer the type of this value".to_string(), span.clone()))?;
+                let temporary: String = format!("__match{}", id);
+                out.push_str(format!("{}{};\n", indent, self.declarator(&ty, &temporary, span)?).as_str());
+                let value: String = self.operand(value)?;
+                for (index, (pattern, arm, _)) in arms.iter().enumerate() {
+                    let condition: Option<String> = match index + 1 == arms.len() {
+                        true => None,
+                        false => self.pattern_condition(pattern, &value)?,
+                    };
+                    let keyword: &str = if index == 0 { "" } else { "} else " };
+                    match &condition {
+                        Some(condition) => out.push_str(format!("{}{}if ({}) {{\n", indent, keyword, condition).as_str()),
+                        None => out.push_str(format!("{}{}{{\n", indent, keyword).as_str()),
+                    }
+                    let saved: HashMap<String, String> = self.bindings.clone();
+                    for (name, _, field) in self.pattern_bindings(pattern, &value)? {
+                        self.bindings.insert(name, format!("({})", field));
+                    }
+                    let result: Result<(), BlazeError> = self.branch(arm, Some((&temporary, &ty)), out);
+                    self.bindings = saved;
+                    result?;
+                    if condition.is_none() {
+                        break;
+                    }
+                }
+                out.push_str(format!("{}}}\n", indent).as_str());
+                Ok(temporary)
+            }
+            _ => unreachable!("Codegen::hoist_conditional()"),
+        }
+    }
+    // Evaluates a value in a branch of a hoisted conditional, storing it in the temporary unless it is `void`.
+    fn branch(&mut self, value: &Expression, temporary: Option<(&str, &Type)>, out: &mut String) -> Result<(), BlazeError> {
+        let indent: String = "    ".repeat(self.indent + 1);
+        self.indent += 1;
+        let result: Result<(), BlazeError> = self.hoist(value, out);
+        let result: Result<String, BlazeError> = result.and_then(|_| match temporary {
+            Some((_, ty)) => self.coerce(ty, value),
+            None => self.expression(value),
+        });
+        self.indent -= 1;
+        match temporary {
+            Some((temporary, _)) => out.push_str(format!("{}{} = {};\n", indent, temporary, result?).as_str()),
+            None => out.push_str(format!("{}(void)({});\n", indent, result?).as_str()),
+        }
+        Ok(())
+    }
+    fn hoisted(&self, expression: &Expression) -> Option<String> {
+        let key: HoistedKey = hoisted_key(expression);
+        self.hoisted.iter().rev().find(|(hoisted, _)| *hoisted == key).map(|(_, lowered)| lowered.clone())
+    }
+    // Lowers `orelse` and `.?` on an optional that has already been evaluated to `value`.
+    fn unwrap_optional(&mut self, expression: &Expression, ty: &Type, value: &str) -> Result<String, BlazeError> {
+        let (present, unwrapped) = optional_parts(ty, value);
+        match expression {
+            Expression::Orelse(_, fallback, _) => {
+                let fallback: String = match ty {
+                    Type::Optional(inner, _) => self.coerce(inner, fallback)?,
+                    _ => self.expression(fallback)?,
+                };
+                Ok(format!("({} ? {} : {})", present, unwrapped, fallback))
+            }
+            Expression::Unwrap(_, span) => {
+                self.traps = true;
+                Ok(format!(
+                    "({} ? {} : (__blaze_trap({}, \"unwrapped a null optional\"), {}))",
+                    present, unwrapped, self.trap_site(span), unwrapped,
+                ))
+            }
+            _ => unreachable!("Codegen::unwrap_optional()"),
+        }
+    }
+    // Wraps a value stored in an error union, which is either an error or the payload, or in an optional that is
+    // not a pointer, which is either empty or the value. Array literals take the element type they are stored as,
+    // and slices passed as pointers pass their pointer.
+    fn coerce(&mut self, target: &Type, value: &Expression) -> Result<String, BlazeError> {
+        let value_type: Option<Type> = self.type_of(value);
+        let c_type: String = match (target, value, &value_type) {
+            (Type::FixedArray(element, _, _), Expression::ArrayLiteral(elements, span), _) => return self.array_literal(element, elements, span),
+            (Type::Pointer(_, _), _, Some(Type::Array(_, _))) => return Ok(format!("{}.ptr", self.operand(value)?)),
+            (Type::Optional(inner, _), _, _) if inner.is_pointer() => return self.coerce(inner, value),
+            (Type::ErrorUnion(_, _, _), _, _) => self.c_type(target, &value.span())?,
+            (Type::Optional(inner, _), _, _) if !inner.is_pointer() => self.c_type(target, &value.span())?,
+            _ => return self.expression(value),
+        };
+        match (target, value, value_type) {
+            (Type::Optional(_, _), Expression::Null(_), _) => Ok(format!("({}){{0}}", c_type)),
+            (Type::Optional(_, _), _, Some(Type::Optional(_, _))) => self.expression(value),
+            (Type::Optional(inner, _), _, _) => Ok(format!("({}){{ .present = true, .value = {} }}", c_type, self.coerce(inner, value)?)),
+            (_, _, Some(Type::ErrorUnion(_, _, _))) => self.expression(value),
+            (_, _, Some(Type::ErrorSet(_, _))) => Ok(format!("({}){{ .error = {} }}", c_type, self.expression(value)?)),
+            (Type::ErrorUnion(_, payload, _), _, _) => Ok(format!("({}){{ .value = {} }}", c_type, self.coerce(payload, value)?)),
+            _ => unreachable!("Codegen::coerce()"),
+        }
+    }
+    fn array_literal(&mut self, element: &Type, elements: &[Expression], span: &Span) -> Result<String, BlazeError> {
+        let c_type: String = self.c_type(&Type::FixedArray(Box::new(element.clone()), elements.len(), span.clone()), span)?;
+        let mut values: Vec<String> = Vec::new();
+        for value in elements {
+            values.push(self.coerce(element, value)?);
+        }
+        Ok(format!("({}){{ {{ {} }} }}", c_type, values.join(", ")))
+    }
+    // A function returning several values returns a struct of them, which a call returning the same struct can
+    // be returned as is.
+    fn return_value(&mut self, values: &[Expression]) -> Result<String, BlazeError> {
+        match (values, self.returns.clone().as_slice()) {
+            ([value], [ty]) => self.coerce(ty, value),
+            ([value], _) => self.expression(value),
+            (values, returns) => {
+                let mut fields: Vec<String> = Vec::new();
+                for (index, (value, ty)) in values.iter().zip(returns).enumerate() {
+                    fields.push(format!("._{} = {}", index, self.coerce(ty, value)?));
+                }
+                Ok(format!("({}){{ {} }}", self.return_type, fields.join(", ")))
+            }
+        }
+    }
+    // The value a bare `return` returns, which is a success for functions returning `!void`.
+    fn success(&self) -> String {
+        match self.returns.as_slice() {
+            [Type::ErrorUnion(_, _, _)] => format!(" ({}){{ .error = 0 }}", self.return_type),
+            _ => String::new(),
+        }
+    }
+
+    fn expression(&mut self, expression: &Expression) -> Result<String, BlazeError> {
+        // Conditionals whose operands had values hoisted out of them are already stored in a temporary.
+        if let Expression::BinaryOperation(_, TokenKind::AmpersandAmpersand | TokenKind::PipePipe, _, _) | Expression::Match(..) = expression {
+            if let Some(lowered) = self.hoisted(expression) {
+                return Ok(lowered);
+            }
+        }
+        match expression {
+            Expression::SelfLiteral(_) => Ok("self".to_string()),
+            Expression::Identifier(name, span) => {
+                let value: String = match self.bindings.get(name) {
+                    Some(binding) => binding.clone(),
+                    None if self.tagged_variant(name).is_some() => return self.variant_value(name, &[], span),
+                    None if self.externs.contains(name) => name.rsplit("::").next().unwrap_or(name).to_string(),
+                    None => mangle(name),
+                };
+                match self.type_of(expression) {
+                    Some(ty) if self.types.is_narrowed(&self.function, expression) => {
+                        Ok(optional_parts(&Type::Optional(Box::new(ty), span.clone()), &value).1)
+                    }
+                    _ => Ok(value),
+                }
+            }
+            // A C integer constant is an `int` when it fits in one, so wider types say theirs for shifts and products.
+            Expression::Integer(value, _, _) => match self.type_of(expression) {
+                Some(Type::I64(_)) => Ok(format!("INT64_C({})", value)),
+                Some(Type::U64(_)) => Ok(format!("UINT64_C({})", value)),
+                Some(Type::U32(_)) => Ok(format!("UINT32_C({})", value)),
+                _ if *value > i64::MAX as u64 => Ok(format!("{}u", value)),
+                _ => Ok(value.to_string()),
+            },
+            Expression::Float(value, suffix, _) => {
+                let mut literal: String = format!("{:?}", value);
+                if matches!(suffix, Some(Type::F32(_))) {
+                    literal.push('f');
+                }
+                Ok(literal)
+            }
+            Expression::Bool(value, _) => Ok(value.to_string()),
+            Expression::Char(value, _) => Ok(format!("'{}'", escape_char(*value))),
+            // The length of a string is its UTF-8 bytes, and the C literal keeps the '\0' after them for C functions.
+            Expression::String(value, span) => match self.type_of(expression) {
+                Some(Type::Pointer(_, _)) => Ok(format!("\"{}\"", escape_string(value))),
+                _ => {
+                    let c_type: String = self.c_type(&Type::Str(span.clone()), span)?;
+                    Ok(format!("({}){{ \"{}\", {} }}", c_type, escape_string(value), value.len()))
+                }
+            },
+            // The checker has turned the paths that name values into qualified identifiers.
+            Expression::StaticMemberAccess(_, _, _) => unreachable!("Codegen::expression()"),
+            Expression::MemberAccess(object, member, span) => {
+                let field: String = match &**member {
+                    Expression::Identifier(field, _) => field.clone(),
+                    _ => return Err(BlazeError::CodegenError(ErrorCode::ExpectedName, "expected a field name".to_string(), member.span())),
+                };
+                let ty: Option<Type> = self.type_of(object);
+                let target: Option<&Type> = match &ty {
+                    Some(Type::Pointer(ty, _)) => Some(ty),
+                    ty => ty.as_ref(),
+                };
+                if let (Some(Type::FixedArray(_, length, _)), "len") = (target, field.as_str()) {
+                    return match is_place(object) {
+                        true => Ok(format!("UINT64_C({})", length)),
+                        false => Ok(format!("((void)({}), UINT64_C({}))", self.expression(object)?, length)),
+                    };
+                }
+                let operator: &str = match ty {
+                    Some(Type::Pointer(_, _)) => "->",
+                    Some(_) => ".",
+                    None => return Err(BlazeError::CodegenError(ErrorCode::UnknownMember, format!("cannot access field '{}' here", field), span.clone())),
+                };
+                let object: String = self.operand(object)?;
+                Ok(format!("{}{}{}", object, operator, mangle(&field)))
+            }
+            Expression::BinaryOperation(left, operator @ (TokenKind::EqualEqual | TokenKind::BangEqual), right, _)
+                if matches!((&**left, &**right), (Expression::Null(_), _) | (_, Expression::Null(_))) => {
+                let value: &Expression = if let Expression::Null(_) = **left { right } else { left };
+                match self.type_of(value) {
+                    Some(ty @ Type::Optional(_, _)) => {
+                        let value: String = self.operand(value)?;
+                        let (present, _) = optional_parts(&ty, &value);
+                        let negation: &str = if *operator == TokenKind::EqualEqual { "!" } else { "" };
+                        Ok(format!("{}{}", negation, present))
+                    }
+                    _ => Ok(format!("{} {} ((void*)0)", self.operand(value)?, binary_operator(operator).unwrap())),
+                }
+            }
+            Expression::BinaryOperation(left, operator @ (TokenKind::AmpersandAmpersand | TokenKind::PipePipe), right, _) => {
+                let left: String = self.operand(left)?;
+                let right: String = self.operand(right)?;
+                Ok(format!("{} {} {}", left, binary_operator(operator).unwrap(), right))
+            }
+            Expression::BinaryOperation(left, operator, right, span) => {
+                let c_operator: &str = binary_operator(operator)
+                    .ok_or_else(|| BlazeError::CodegenError(ErrorCode::InvalidOperand, format!("unsupported operator '{}'", operator.spelling()), span.clone()))?;
+                let right: String = match (operator, self.type_of(left)) {
+                    (TokenKind::Equal, Some(ty)) => self.coerce(&ty, right)?,
+                    _ => self.operand(right)?,
+                };
+                let left: String = self.operand(left)?;
+                Ok(format!("{} {} {}", left, c_operator, right))
+            }
+            Expression::UnaryOperation(operator, value, span) => {
+                let c_operator: &str = match operator {
+                    TokenKind::Bang => "!",
+                    TokenKind::Minus => "-",
+                    TokenKind::Tilde => "~",
+                    _ => return Err(BlazeError::CodegenError(ErrorCode::InvalidOperand, format!("unsupported operator '{}'", operator.spelling()), span.clone())),
+                };
+                Ok(format!("{}{}", c_operator, self.operand(value)?))
+            }
+            // Outside of release builds, indexing into an array or slice traps with the file and line when out of bounds.
+            // The checker has already rejected literal indices out of the bounds of a fixed-size array.
+            Expression::ArrayAccess(object, index, span) => {
+                let ty: Type = self.type_of(object)
+                    .ok_or_else(|| BlazeError::CodegenError(ErrorCode::CannotInfer, "cannot infer the type of this value".to_string(), object.span()))?;
+                let checked: bool = !matches!(**index, Expression::Integer(_, _, _));
+                let array: String = self.operand(object)?;
+                let index: String = self.expression(index)?;
+                match ty {
+                    Type::FixedArray(_, _, _) if self.release || !checked => Ok(format!("{}.items[{}]", array, index)),
+                    Type::FixedArray(_, length, _) => {
+                        self.checks_bounds = true;
+                        Ok(format!("{}.items[__blaze_index({}, {}, {})]", array, index, length, self.trap_site(span)))
+                    }
+                    Type::Array(_, _) | Type::Str(_) if self.release => Ok(format!("{}.ptr[{}]", array, index)),
+                    Type::Array(_, _) | Type::Str(_) => {
+                        let at: String = self.slice_helper("at", &ty, span)?;
+                        Ok(format!("(*{}({}, {}, {}))", at, array, index, self.trap_site(span)))
+                    }
+                    _ => Ok(format!("{}[{}]", array, index)),
+                }
+            }
+            Expression::Slice(object, start, end, span) => {
+                let ty: Type = self.type_of(object)
+                    .ok_or_else(|| BlazeError::CodegenError(ErrorCode::CannotInfer, "cannot infer the type of this value".to_string(), object.span()))?;
+                let (slice_type, slice, length) = match &ty {
+                    Type::FixedArray(_, _, _) if !is_place(object) => {
+                        return Err(BlazeError::CodegenError(
+                            ErrorCode::Unsupported,
+                            "slicing a temporary array is not supported by the C backend yet; store it in a variable first".to_string(),
+                            object.span(),
+                        ));
+                    }
+                    Type::FixedArray(element, length, _) => {
+                        let slice_type: Type = Type::Array(element.clone(), span.clone());
+                        let c_type: String = self.c_type(&slice_type, span)?;
+                        let slice: String = format!("({}){{ {}.items, {} }}", c_type, self.operand(object)?, length);
+                        (slice_type, slice, Some(length.to_string()))
+                    }
+                    _ => {
+                        let slice: String = self.operand(object)?;
+                        let length: Option<String> = is_place(object).then(|| format!("{}.len", slice));
+                        (ty.clone(), slice, length)
+                    }
+                };
+                let start: String = match start {
+                    Some(start) => self.expression(start)?,
+                    None => "0".to_string(),
+                };
+                let end: String = match (end, length) {
+                    (Some(end), _) => self.expression(end)?,
+                    (None, Some(length)) => length,
+                    (None, None) => return Err(BlazeError::CodegenError(
+                        ErrorCode::Unsupported,
+                        "slicing a temporary slice up to its end is not supported by the C backend yet; store it in a variable first".to_string(),
+                        object.span(),
+                    )),
+                };
+                let helper: String = self.slice_helper("range", &slice_type, span)?;
+                Ok(format!("{}({}, {}, {}, {})", helper, slice, start, end, self.trap_site(span)))
+            }
+            Expression::ArrayLiteral(elements, span) => {
+                let element: Type = elements.first().and_then(|element| self.type_of(element))
+                    .ok_or_else(|| BlazeError::CodegenError(ErrorCode::CannotInfer, "cannot infer the element type of this array".to_string(), span.clone()))?;
+                self.array_literal(&element, elements, span)
+            }
+            Expression::Call(callee, arguments, span) => {
+                let parameters: Vec<(String, Type)> = match &**callee {
+                    Expression::Identifier(name, _) if self.tagged_variant(name).is_some() => return self.variant_value(name, arguments, span),
+                    Expression::Identifier(name, _) => match self.declarations.get(name).map(|declaration| &declaration.symbol) {
+                        Some(Symbol::Function(parameters, _)) => parameters.clone(),
+                        _ => Vec::new(),
+                    },
+                    _ => Vec::new(),
+                };
+                let callee: String = self.expression(callee)?;
+                let mut c_arguments: Vec<String> = Vec::new();
+                for (index, argument) in arguments.iter().enumerate() {
+                    c_arguments.push(match parameters.get(index) {
+                        Some((_, ty)) => self.coerce(ty, argument)?,
+                        None => self.expression(argument)?,
+                    });
+                }
+                Ok(format!("{}({})", callee, c_arguments.join(", ")))
+            }
+            Expression::StructLiteral(name, arguments, fields, span) => self.struct_literal(name, arguments, fields, span),
+            Expression::AddressOf(value, span) if needs_storage(value) => self.hoisted(expression)
+                .ok_or_else(|| BlazeError::CodegenError(
+                    ErrorCode::Unsupported,
+                    "taking the address of this value is not supported by the C backend yet; store it in a variable first".to_string(),
+                    span.clone(),
+                )),
+            Expression::AddressOf(value, _) => Ok(format!("&{}", self.operand(value)?)),
+            Expression::Dereference(value, _) => Ok(format!("*{}", self.operand(value)?)),
+            Expression::Match(value, arms, span) => self.match_expression(value, arms, span),
+            Expression::Range(_, _, span) => Err(BlazeError::CodegenError(ErrorCode::MisplacedRange, "ranges are only allowed in 'for' loops".to_string(), span.clone())),
+            Expression::Try(_, span) | Expression::Catch(_, _, _, span) => self.hoisted(expression)
+                .ok_or_else(|| BlazeError::CodegenError(
+                    ErrorCode::Unsupported,
+                    "'try' or 'catch' here is not supported by the C backend yet; move it into its own statement".to_string(),
+                    span.clone(),
+                )),
+            Expression::Orelse(value, _, _) | Expression::Unwrap(value, _) if is_place(value) => {
+                let ty: Type = self.type_of(value)
+                    .ok_or_else(|| BlazeError::CodegenError(ErrorCode::CannotInfer, "cannot infer the type of this value".to_string(), value.span()))?;
+                let value: String = self.operand(value)?;
+                self.unwrap_optional(expression, &ty, &value)
+            }
+            Expression::Orelse(_, _, span) | Expression::Unwrap(_, span) => self.hoisted(expression)
+                .ok_or_else(|| BlazeError::CodegenError(
+                    ErrorCode::Unsupported,
+                    "unwrapping a temporary optional here is not supported by the C backend yet; store it in a variable first".to_string(),
+                    span.clone(),
+                )),
+            Expression::Null(_) => Ok("((void*)0)".to_string()),
+        }
+    }
+    // Lowers a `match` expression to a chain of conditionals, reading the payload fields straight out of the
+    // matched value. That value is evaluated once per arm, so it has to be a place without side effects.
+    fn match_expression(&mut self, value: &Expression, arms: &[(Pattern, Expression, Span)], span: &Span) -> Result<String, BlazeError> {
+        if !is_place(value) {
+            return Err(BlazeError::CodegenError(
+                ErrorCode::Unsupported,
+                "matching on this expression is not supported by the C backend yet; store the value in a variable first".to_string(),
+                value.span(),
+            ));
+        }
+        let value: String = self.operand(value)?;
+        let mut branches: Vec<(Option<String>, String)> = Vec::new();
+        for (pattern, arm, _) in arms {
+            let condition: Option<String> = self.pattern_condition(pattern, &value)?;
+            let saved: HashMap<String, String> = self.bindings.clone();
+            for (name, _, field) in self.pattern_bindings(pattern, &value)? {
+                self.bindings.insert(name, format!("({})", field));
+            }
+            let result: Result<String, BlazeError> = self.expression(arm);
+            self.bindings = saved;
+            let last: bool = condition.is_none();
+            branches.push((condition, result?));
+            if last {
+                break;
+            }
+        }
+        let (_, mut out) = branches.pop()
+            .ok_or_else(|| BlazeError::CodegenError(ErrorCode::NonExhaustiveMatch, "a 'match' expression needs at least one arm".to_string(), span.clone()))?;
+        for (condition, arm) in branches.into_iter().rev() {
+            out = format!("{} ? {} : {}", condition.unwrap_or_else(|| "1".to_string()), arm, out);
+        }
+        Ok(format!("({})", out))
+    }
+    fn pattern_condition(&self, pattern: &Pattern, value: &str) -> Result<Option<String>, BlazeError> {
+        match pattern {
+            Pattern::Wildcard(_) => Ok(None),
+            Pattern::Variant(name, _, _) => match self.tagged_variant(name) {
+                Some(_) => Ok(Some(format!("{}.kind == {}", value, mangle(name)))),
+                None => Ok(Some(format!("{} == {}", value, mangle(name)))),
+            },
+        }
+    }
+    // Returns the name, type and C access path of every field a pattern binds.
+    fn pattern_bindings(&self, pattern: &Pattern, value: &str) -> Result<Vec<(String, Type, String)>, BlazeError> {
+        let (name, bindings) = match pattern {
+            Pattern::Variant(name, bindings, _) => (name, bindings),
+            Pattern::Wildcard(_) => return Ok(Vec::new()),
+        };
+        let (index, fields) = match self.tagged_variant(name) {
+            Some(variant) => variant,
+            None => return Ok(Vec::new()),
+        };
+        Ok(bindings.iter().zip(fields)
+            .filter(|((binding, _), _)| binding != "_")
+            .map(|((binding, _), field)| (binding.clone(), field.ty, format!("{}.data._{}.{}", value, index, mangle(&field.name))))
+            .collect())
+    }
+    // Looks up a variant of an enum with payloads by its qualified name, returning its position and payload fields.
+    fn tagged_variant(&self, qualified: &str) -> Option<(usize, Vec<StructField>)> {
+        let (enum_name, name) = qualified.rsplit_once("::")?;
+        let variants: &Vec<EnumVariant> = match &self.declarations.get(enum_name)?.symbol {
+            Symbol::Enum(None, variants) if is_tagged(variants) => variants,
+            _ => return None,
+        };
+        variants.iter().enumerate().find_map(|(index, variant)| match variant {
+            EnumVariant::Payload(variant_name, fields, _) if variant_name == name => Some((index, fields.clone())),
+            EnumVariant::Unit(variant_name, _) if variant_name == name => Some((index, Vec::new())),
+            _ => None,
+        })
+    }
+    fn variant_value(&mut self, qualified: &str, arguments: &[Expression], span: &Span) -> Result<String, BlazeError> {
+        let (index, fields) = match self.tagged_variant(qualified) {
+            Some(variant) => variant,
+            None => return Ok(mangle(qualified)),
+        };
+        let enum_name: String = mangle(qualified.rsplit_once("::").unwrap().0);
+        if fields.is_empty() {
+            return Ok(format!("({}){{ .kind = {} }}", enum_name, mangle(qualified)));
+        }
+        let mut initializers: Vec<String> = Vec::new();
+        for (field, argument) in fields.iter().zip(arguments) {
+            initializers.push(format!(".{} = {}", mangle(&field.name), self.coerce(&field.ty, argument)?));
+        }
+        if initializers.len() != fields.len() {
+            return Err(BlazeError::CodegenError(ErrorCode::ArgumentCount, format!("'{}' expects {} values", qualified, fields.len()), span.clone()));
+        }
+        Ok(format!(
+            "({}){{ .kind = {}, .data = {{ ._{} = {{ {} }} }} }}",
+            enum_name, mangle(qualified), index, initializers.join(", "),
+        ))
+    }
+    fn operand(&mut self, expression: &Expression) -> Result<String, BlazeError> {
+        let out: String = self.expression(expression)?;
+        match expression {
+            Expression::BinaryOperation(_, _, _, _)
+            | Expression::UnaryOperation(_, _, _)
+            | Expression::AddressOf(_, _)
+            | Expression::Dereference(_, _) => Ok(format!("({})", out)),
+            _ => Ok(out),
+        }
+    }
+    fn struct_literal(&mut self, name: &str, arguments: &[Type], fields: &[(Option<String>, Expression, Span)], span: &Span) -> Result<String, BlazeError> {
+        let ty: Type = struct_literal_type(name, arguments, span);
+        let c_name: String = self.c_type(&ty, span)?;
+        let declared: Vec<StructField> = self.fields_of(&ty)
+            .ok_or_else(|| BlazeError::CodegenError(ErrorCode::InvalidStructLiteral, format!("'{}' is not a struct", name), span.clone()))?;
+        if fields.is_empty() {
+            return Ok(format!("({}){{0}}", c_name));
+        }
+
+        let mut initializers: Vec<String> = Vec::new();
+        for (index, (field_name, value, span)) in fields.iter().enumerate() {
+            let field: &StructField = match field_name {
+                Some(field_name) => declared.iter().find(|field| &field.name == field_name)
+                    .ok_or_else(|| BlazeError::CodegenError(ErrorCode::UnknownMember, format!("'{}' has no field named '{}'", name, field_name), span.clone()))?,
+                None => declared.get(index)
+                    .ok_or_else(|| BlazeError::CodegenError(ErrorCode::UnknownMember, format!("too many fields for '{}'", name), span.clone()))?,
+            };
+            let mut designator: String = mangle(&field.name);
+            if let (Type::Union(union_name, _), Some(value_type)) = (&field.ty, self.type_of(value)) {
+                if let Symbol::Union(members) = &self.declarations[union_name].symbol {
+                    if let Some(member) = members.iter().position(|member| member.equals(value_type.clone()) || same_named_type(member, &value_type)) {
+                        designator = format!("{}.__{}", designator, member);
+                    }
+                }
+            }
+            let value: String = self.coerce(&field.ty, value)?;
+            initializers.push(format!(".{} = {}", designator, value));
+        }
+        Ok(format!("({}){{ {} }}", c_name, initializers.join(", ")))
+    }
+    // The functions that index into and slice a slice, defined once per slice type after the types. Only the
+    // bounds checks of debug builds need the file and line they are called from.
+    fn slice_helper(&mut self, kind: &str, slice_type: &Type, span: &Span) -> Result<String, BlazeError> {
+        let c_type: String = self.c_type(slice_type, span)?;
+        let name: String = format!("__blz_{}_{}", kind, c_type);
+        if !self.instantiated.insert(name.clone()) {
+            return Ok(name);
+        }
+        let element: String = match slice_type {
+            Type::Array(element, _) => self.c_type(element, span)?,
+            Type::Str(_) => "char".to_string(),
+            _ => unreachable!("Codegen::slice_helper()"),
+        };
+        if !self.release {
+            self.checks_bounds = true;
+        }
+        let body: String = match kind {
+            "at" => format!(
+                "static {}* {}({} slice, uint64_t index, const char* file, int line) {{\n    return &slice.ptr[__blaze_index(index, slice.len, file, line)];\n}}\n\n",
+                element, name, c_type,
+            ),
+            _ => format!(
+                "static {0} {1}({0} slice, uint64_t start, uint64_t end, const char* file, int line) {{\n{2}    return ({0}){{ slice.ptr + start, end - start }};\n}}\n\n",
+                c_type, name, if self.release { "" } else { "    __blaze_slice(start, end, slice.len, file, line);\n" },
+            ),
+        };
+        self.helpers.push_str(body.as_str());
+        Ok(name)
+    }
+    // The file and line arguments of a runtime check that fails at the span.
+    fn trap_site(&self, span: &Span) -> String {
+        let (file, line) = self.location(span).unwrap_or_else(|| ("<unknown>".to_string(), 0));
+        format!("\"{}\", {}", file, line)
+    }
+    fn line_directive(&self, span: &Span) -> String {
+        match self.location(span) {
+            Some((file, line)) => format!("#line {} \"{}\"\n", line, file),
+            None => String::new(),
+        }
+    }
+    // The file, escaped for a C string literal, and the line a span starts at.
+    fn location(&self, span: &Span) -> Option<(String, usize)> {
+        let source = self.sources.get(span.file)?;
+        let (line, _) = source.location(span.start);
+        Some((source.name.replace('\\', "\\\\").replace('"', "\\\""), line))
+    }
+
+    // The type the checker inferred for an expression of the function being emitted.
+    fn type_of(&self, expression: &Expression) -> Option<Type> {
+        self.types.get(&self.function, expression).cloned()
+    }
+    fn call_returns(&self, expression: &Expression) -> Option<Vec<Type>> {
+        let callee: &str = match expression {
+            Expression::Call(callee, _, _) => match &**callee {
+                Expression::Identifier(callee, _) => callee,
+                _ => return None,
+            },
+            _ => return None,
+        };
+        match &self.declarations.get(callee)?.symbol {
+            Symbol::Function(_, returns) => Some(returns.clone()),
+            _ => None,
+        }
+    }
+    fn fields_of(&self, ty: &Type) -> Option<Vec<StructField>> {
+        match ty {
+            Type::Struct(name, _) => match &self.declarations.get(name)?.symbol {
+                Symbol::Struct(_, fields) => Some(fields.clone()),
+                _ => None,
+            },
+            Type::GenericInstance(name, arguments, _) => match &self.declarations.get(name)?.symbol {
+                Symbol::Struct(generics, fields) => Some(fields.iter().map(|field| StructField {
+                    name: field.name.clone(),
+                    ty: field.ty.substitute(generics, arguments),
+                    span: field.span.clone(),
+                }).collect()),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn declarator(&mut self, ty: &Type, name: &str, span: &Span) -> Result<String, BlazeError> {
+        Ok(format!("{} {}", self.c_type(ty, span)?, name))
+    }
+    fn c_type(&mut self, ty: &Type, span: &Span) -> Result<String, BlazeError> {
+        match ty {
+            Type::I8(_) => Ok("int8_t".to_string()),
+            Type::I16(_) => Ok("int16_t".to_string()),
+            Type::I32(_) => Ok("int32_t".to_string()),
+            Type::I64(_) => Ok("int64_t".to_string()),
+            Type::U8(_) => Ok("uint8_t".to_string()),
+            Type::U16(_) => Ok("uint16_t".to_string()),
+            Type::U32(_) => Ok("uint32_t".to_string()),
+            Type::U64(_) => Ok("uint64_t".to_string()),
+            Type::F32(_) => Ok("float".to_string()),
+            Type::F64(_) => Ok("double".to_string()),
+            Type::Char(_) => Ok("char".to_string()),
+            Type::Bool(_) => Ok("bool".to_string()),
+            Type::Void(_) => Ok("void".to_string()),
+            Type::Pointer(ty, _) => Ok(format!("{}*", self.c_type(ty, span)?)),
+            Type::Array(inner, s) => Ok(self.wrapper(mangle_type(ty), vec![
+                StructField { name: "ptr".to_string(), ty: Type::Pointer(inner.clone(), s.clone()), span: s.clone() },
+                StructField { name: "len".to_string(), ty: Type::U64(s.clone()), span: s.clone() },
+            ])),
+            // A string is a slice of its bytes, so strings and 'char[]' slices share one C type.
+            Type::Str(s) => self.c_type(&Type::Array(Box::new(Type::Char(s.clone())), s.clone()), span),
+            Type::FixedArray(inner, length, s) => Ok(self.wrapper(mangle_type(ty), vec![
+                StructField { name: format!("items[{}]", length), ty: *inner.clone(), span: s.clone() },
+            ])),
+            Type::Optional(inner, _) if inner.is_pointer() => self.c_type(inner, span),
+            Type::Optional(inner, s) => Ok(self.wrapper(mangle_type(ty), vec![
+                StructField { name: "present".to_string(), ty: Type::Bool(s.clone()), span: s.clone() },
+                StructField { name: "value".to_string(), ty: *inner.clone(), span: s.clone() },
+            ])),
+            Type::ErrorSet(_, _) => Ok("uint16_t".to_string()),
+            Type::ErrorUnion(_, payload, s) => {
+                let mut fields: Vec<StructField> = vec![StructField { name: "error".to_string(), ty: Type::U16(s.clone()), span: s.clone() }];
+                if !matches!(**payload, Type::Void(_)) {
+                    fields.push(StructField { name: "value".to_string(), ty: *payload.clone(), span: s.clone() });
+                }
+                Ok(self.wrapper(mangle_type(ty), fields))
+            }
+            Type::Struct(name, _) | Type::Enum(name, _) | Type::Union(name, _) => Ok(mangle(name)),
+            Type::GenericInstance(name, arguments, _) => {
+                let instance: String = mangle_type(ty);
+                if !self.instantiated.contains(&instance) {
+                    self.instantiated.insert(instance.clone());
+                    self.instances.push((instance.clone(), name.clone(), arguments.clone()));
+                }
+                Ok(instance)
+            }
+            _ => Err(BlazeError::CodegenError(ErrorCode::Unsupported, format!("type '{}' is not supported by the C backend yet", ty.name()), span.clone())),
+        }
+    }
+
+    // The struct a function returning several values returns them in, with the fields `_0`, `_1` and so on.
+    fn returns_type(&mut self, returns: &[Type], span: &Span) -> String {
+        let name: String = format!("__blz_returns{}", mangle_arguments(returns));
+        let fields: Vec<StructField> = returns.iter().enumerate()
+            .map(|(index, ty)| StructField { name: format!("_{}", index), ty: ty.clone(), span: span.clone() })
+            .collect();
+        self.wrapper(name, fields)
+    }
+    // Registers the struct that an error union, optional or list of return values becomes, to be emitted along
+    // with the other types.
+    fn wrapper(&mut self, name: String, fields: Vec<StructField>) -> String {
+        if !self.instantiated.contains(&name) {
+            self.instantiated.insert(name.clone());
+            self.wrappers.push((name.clone(), fields));
+        }
+        name
+    }
+
+    fn emit_types(&mut self) -> Result<String, BlazeError> {
+        let mut forward: String = String::new();
+        let mut enums: String = String::new();
+        let mut definitions: Vec<Definition> = Vec::new();
+        // Error codes are unique across all error sets, so that `!T` can hold an error from any of them. Zero means success.
+        let mut code: usize = 0;
+
+        for name in self.order.clone() {
+            let declaration: Declaration = self.declarations[&name].clone();
+            let c_name: String = mangle(&name);
+            match declaration.symbol.clone() {
+                Symbol::Struct(generics, fields) if generics.is_empty() => {
+                    forward.push_str(format!("typedef struct {0} {0};\n", c_name).as_str());
+                    let (body, dependencies) = self.struct_body(&user_fields(&fields))?;
+                    definitions.push((c_name.clone(), format!("struct {} {}", c_name, body), dependencies, declaration.span.clone()));
+                }
+                Symbol::Union(types) => {
+                    forward.push_str(format!("typedef union {0} {0};\n", c_name).as_str());
+                    let mut body: String = String::new();
+                    let mut dependencies: Vec<String> = Vec::new();
+                    for (index, ty) in types.iter().enumerate() {
+                        body.push_str(format!("    {};\n", self.declarator(ty, format!("__{}", index).as_str(), &declaration.span)?).as_str());
+                        dependencies.extend(by_value(ty));
+                    }
+                    definitions.push((c_name.clone(), format!("union {} {{\n{}}};\n\n", c_name, body), dependencies, declaration.span.clone()));
+                }
+                Symbol::Enum(None, variants) if is_tagged(&variants) => {
+                    enums.push_str(self.enum_definition(&name, &None, &variants, &declaration.span)?.as_str());
+                    let mut members: String = String::new();
+                    let mut payloads: Vec<String> = Vec::new();
+                    for (index, variant) in variants.iter().enumerate() {
+                        if let EnumVariant::Payload(variant_name, fields, span) = variant {
+                            let payload: String = format!("__blz_payload_{}", mangle(&format!("{}::{}", name, variant_name)));
+                            forward.push_str(format!("typedef struct {0} {0};\n", payload).as_str());
+                            let (body, dependencies) = self.struct_body(&user_fields(fields))?;
+                            definitions.push((payload.clone(), format!("struct {} {}", payload, body), dependencies, span.clone()));
+                            members.push_str(format!("    {} _{};\n", payload, index).as_str());
+                            payloads.push(payload);
+                        }
+                    }
+                    let data: String = format!("__blz_data_{}", c_name);
+                    forward.push_str(format!("typedef union {0} {0};\ntypedef struct {1} {1};\n", data, c_name).as_str());
+                    definitions.push((data.clone(), format!("union {} {{\n{}}};\n\n", data, members), payloads, declaration.span.clone()));
+                    let body: String = format!("struct {} {{\n    __blz_kind_{} kind;\n    {} data;\n}};\n\n", c_name, c_name, data);
+                    definitions.push((c_name.clone(), body, vec![data], declaration.span.clone()));
+                }
+                Symbol::Enum(ty, variants) => {
+                    enums.push_str(self.enum_definition(&name, &ty, &variants, &declaration.span)?.as_str());
+                }
+                Symbol::ErrorSet(errors) => {
+                    enums.push_str("enum {\n");
+                    for (error, _) in errors {
+                        code += 1;
+                        enums.push_str(format!("    {} = {},\n", mangle(&format!("{}::{}", name, error)), code).as_str());
+                    }
+                    enums.push_str("};\n\n");
+                }
+                _ => {}
+            }
+        }
+
+        // Generic instances and wrapper types can each use the other, so both lists grow until everything is emitted.
+        let mut next: usize = 0;
+        let mut next_wrapper: usize = 0;
+        while next < self.instances.len() || next_wrapper < self.wrappers.len() {
+            if next_wrapper < self.wrappers.len() {
+                let (wrapper, fields) = self.wrappers[next_wrapper].clone();
+                next_wrapper += 1;
+                let span: Span = fields[0].span.clone();
+                forward.push_str(format!("typedef struct {0} {0};\n", wrapper).as_str());
+                let (body, dependencies) = self.struct_body(&fields)?;
+                definitions.push((wrapper.clone(), format!("struct {} {}", wrapper, body), dependencies, span));
+                continue;
+            }
+            let (instance, name, arguments) = self.instances[next].clone();
+            next += 1;
+            let span: Span = self.declarations[&name].span.clone();
+            let fields: Vec<StructField> = self.fields_of(&Type::GenericInstance(name, arguments, span.clone()))
+                .unwrap_or_default();
+            forward.push_str(format!("typedef struct {0} {0};\n", instance).as_str());
+            let (body, dependencies) = self.struct_body(&user_fields(&fields))?;
+            definitions.push((instance.clone(), format!("struct {} {}", instance, body), dependencies, span));
+        }
+
+        let mut out: String = String::new();
+        if !forward.is_empty() {
+            out.push_str(forward.as_str());
+            out.push('\n');
+        }
+        out.push_str(enums.as_str());
+        let mut emitted: HashSet<String> = HashSet::new();
+        let mut visiting: HashSet<String> = HashSet::new();
+        for index in 0..definitions.len() {
+            emit_definition(index, &definitions, &mut emitted, &mut visiting, &mut out)?;
+        }
+        Ok(out)
+    }
+    fn struct_body(&mut self, fields: &[StructField]) -> Result<(String, Vec<String>), BlazeError> {
+        if fields.is_empty() {
+            return Ok(("{ void* _; };\n\n".to_string(), Vec::new()));
+        }
+        let mut body: String = String::from("{\n");
+        let mut dependencies: Vec<String> = Vec::new();
+        for field in fields {
+            body.push_str(format!("    {};\n", self.declarator(&field.ty, &field.name, &field.span)?).as_str());
+            dependencies.extend(by_value(&field.ty));
+        }
+        body.push_str("};\n\n");
+        Ok((body, dependencies))
+    }
+    fn enum_definition(&mut self, name: &str, ty: &Option<Type>, variants: &[EnumVariant], span: &Span) -> Result<String, BlazeError> {
+        let c_name: &str = &mangle(name);
+        let enum_name: String = match is_tagged(variants) {
+            true => format!("__blz_kind_{}", c_name),
+            false => c_name.to_string(),
+        };
+        let mut out: String = format!("typedef enum {} {{\n", enum_name);
+        for variant in variants {
+            let variant_name: &str = match variant {
+                EnumVariant::Unit(name, _) | EnumVariant::Expression(name, _, _) | EnumVariant::Payload(name, _, _) => name,
+            };
+            out.push_str(format!("    {},\n", mangle(&format!("{}::{}", name, variant_name))).as_str());
+        }
+        out.push_str(format!("}} {};\n", enum_name).as_str());
+
+        if let Some(ty) = ty {
+            out.push_str(format!("static {} const __blz_values_{}[] = {{\n", self.c_type(ty, span)?, c_name).as_str());
+            self.function = name.to_string();
+            let mut previous: Option<(String, usize)> = None;
+            for variant in variants {
+                let (variant_name, base, offset) = match (variant, previous) {
+                    (EnumVariant::Unit(variant_name, _), None) => (variant_name, "0".to_string(), 0),
+                    (EnumVariant::Unit(variant_name, _), Some((base, offset))) => (variant_name, base, offset + 1),
+                    (EnumVariant::Expression(variant_name, value, _), _) => (variant_name, self.operand(value)?, 0),
+                    (EnumVariant::Payload(_, _, _), _) => unreachable!("Codegen::enum_definition()"),
+                };
+                let value: String = if offset == 0 { base.clone() } else { format!("{} + {}", base, offset) };
+                previous = Some((base, offset));
+                out.push_str(format!("    [{}] = {},\n", mangle(&format!("{}::{}", name, variant_name)), value).as_str());
+            }
+            out.push_str("};\n");
+        }
+        out.push('\n');
+        Ok(out)
+    }
+}
+
+fn emit_definition(index: usize, definitions: &[Definition], emitted: &mut HashSet<String>, visiting: &mut HashSet<String>, out: &mut String) -> Result<(), BlazeError> {
+    let (name, body, dependencies, span) = &definitions[index];
+    if emitted.contains(name) {
+        return Ok(());
+    }
+    if visiting.contains(name) {
+        return Err(BlazeError::CodegenError(ErrorCode::RecursiveType, format!("recursive type '{}' has infinite size", name), span.clone()));
+    }
+    visiting.insert(name.clone());
+    for dependency in dependencies {
+        if let Some(dependency) = definitions.iter().position(|(name, _, _, _)| name == dependency) {
+            emit_definition(dependency, definitions, emitted, visiting, out)?;
+        }
+    }
+    visiting.remove(name);
+    emitted.insert(name.clone());
+    out.push_str(body.as_str());
+    Ok(())
+}
+
+fn by_value(ty: &Type) -> Vec<String> {
+    match ty {
+        Type::Struct(name, _) | Type::Enum(name, _) | Type::Union(name, _) => vec![mangle(name)],
+        Type::GenericInstance(_, _, _) | Type::ErrorUnion(_, _, _) | Type::Array(_, _) | Type::FixedArray(_, _, _) => vec![mangle_type(ty)],
+        Type::Optional(inner, _) if !inner.is_pointer() => vec![mangle_type(ty)],
+        Type::Str(s) => by_value(&Type::Array(Box::new(Type::Char(s.clone())), s.clone())),
+        _ => Vec::new(),
+    }
+}
+
+// The values a statement evaluates before anything else it does, where its `try` and `catch` are hoisted from.
+fn statement_values(statement: &Statement) -> Vec<&Expression> {
+    match statement {
+        Statement::ConstantDeclaration(_, _, value, _)
+        | Statement::VariableDeclaration(_, _, value, _)
+        | Statement::MutableDeclaration(_, _, value, _)
+        | Statement::Destructuring(_, value, _)
+        | Statement::If(value, _, _, _, _)
+        | Statement::Match(value, _, _)
+        | Statement::Expression(value, _) => vec![value],
+        Statement::Return(values, _) => values.iter().collect(),
+        Statement::For(_, Expression::Range(start, end, _), _, _) => vec![start, end],
+        Statement::For(_, iterable, _, _) => vec![iterable],
+        Statement::Labeled(_, statement, _) => statement_values(statement),
+        _ => Vec::new(),
+    }
+}
+
+// Whether evaluating the expression needs a temporary hoisted before its statement.
+fn is_hoisted(expression: &Expression) -> bool {
+    match expression {
+        Expression::Try(_, _) | Expression::Catch(_, _, _, _) => true,
+        Expression::Orelse(value, _, _) | Expression::Unwrap(value, _) if !is_place(value) => true,
+        Expression::AddressOf(value, _) if needs_storage(value) => true,
+        _ => expression.operands().into_iter().any(is_hoisted),
+    }
+}
+
+fn hoisted_key(expression: &Expression) -> HoistedKey {
+    (expression.span(), expression.last_span(), discriminant(expression))
+}
+
+// The C condition that an evaluated optional holds a value, and that value.
+fn optional_parts(ty: &Type, value: &str) -> (String, String) {
+    match ty {
+        Type::Optional(inner, _) if !inner.is_pointer() => (format!("{}.present", value), format!("{}.value", value)),
+        _ => (value.to_string(), value.to_string()),
+    }
+}
+
+fn is_jump(statement: &Statement) -> bool {
+    matches!(statement, Statement::Return(..) | Statement::Break(..) | Statement::Continue(..))
+}
+
+fn loop_label(kind: &str, target: &Loop) -> String {
+    format!("__{}_{}_{}", kind, target.label.as_deref().unwrap_or("loop"), target.id)
+}
+
+fn is_tagged(variants: &[EnumVariant]) -> bool {
+    variants.iter().any(|variant| matches!(variant, EnumVariant::Payload(_, _, _)))
+}
+
+fn is_place(expression: &Expression) -> bool {
+    match expression {
+        Expression::Identifier(_, _) | Expression::SelfLiteral(_) => true,
+        Expression::MemberAccess(object, _, _) | Expression::Dereference(object, _) => is_place(object),
+        Expression::ArrayAccess(object, index, _) => is_place(object) && (is_place(index) || matches!(**index, Expression::Integer(_, _, _))),
+        _ => false,
+    }
+}
+
+// Whether a value has to be stored in a temporary to take its address, like the result of a call a method that
+// takes `self: *T` is called on. C compound literals can have their address taken as they are.
+fn needs_storage(value: &Expression) -> bool {
+    !is_place(value) && !matches!(value, Expression::StructLiteral(..) | Expression::ArrayLiteral(..))
+}
+
+// The fields of a struct declared in the source, named the way C sees them. The fields of the wrapper types are
+// named by the backend and kept as they are.
+fn user_fields(fields: &[StructField]) -> Vec<StructField> {
+    fields.iter().map(|field| StructField { name: mangle(&field.name), ..field.clone() }).collect()
+}
+
+fn same_named_type(left: &Type, right: &Type) -> bool {
+    match (left, right) {
+        (Type::Struct(a, _), Type::Struct(b, _))
+        | (Type::Enum(a, _), Type::Enum(b, _))
+        | (Type::Union(a, _), Type::Union(b, _)) => a == b,
+        _ => false,
+    }
+}
+
+fn is_generic(parameters: &[(String, Type, bool, Span)], returns: &[Type]) -> bool {
+    parameters.iter().map(|(_, ty, _, _)| ty).chain(returns).any(|ty| !ty.generics().is_empty())
+}
+
+// The types of the values a function returns, which a `void` return type has none of.
+fn values(returns: &[Type]) -> Vec<Type> {
+    returns.iter().filter(|ty| !matches!(ty, Type::Void(_))).cloned().collect()
+}
+
+fn struct_literal_type(name: &str, arguments: &[Type], span: &Span) -> Type {
+    match arguments.is_empty() {
+        true => Type::Struct(name.to_string(), span.clone()),
+        false => Type::GenericInstance(name.to_string(), arguments.to_vec(), span.clone()),
+    }
+}
+
+fn escape_char(c: char) -> String {
+    match c {
+        '\n' => "\\n".to_string(),
+        '\r' => "\\r".to_string(),
+        '\t' => "\\t".to_string(),
+        '\0' => "\\0".to_string(),
+        '\\' => "\\\\".to_string(),
+        '\'' => "\\'".to_string(),
+        c => c.to_string(),
+    }
+}
+
+// Escapes a string for a C literal, writing its bytes outside printable ASCII as octal escapes so UTF-8 text
+// survives any source encoding of the C compiler. `?` is escaped so no trigraph can form.
+fn escape_string(text: &str) -> String {
+    let mut escaped: String = String::new();
+    for byte in text.bytes() {
+        match byte {
+            b'"' | b'\\' | b'?' => {
+                escaped.push('\\');
+                escaped.push(byte as char);
+            }
+            b'\n' => escaped.push_str("\\n"),
+            b'\t' => escaped.push_str("\\t"),
+            b' '..=b'~' => escaped.push(byte as char),
+            _ => escaped.push_str(format!("\\{:03o}", byte).as_str()),
+        }
+    }
+    escaped
+}
+
+fn binary_operator(operator: &TokenKind) -> Option<&'static str> {
+    match operator {
+        TokenKind::Plus => Some("+"),
+        TokenKind::Minus => Some("-"),
+        TokenKind::Asterisk => Some("*"),
+        TokenKind::Slash => Some("/"),
+        TokenKind::Percent => Some("%"),
+        TokenKind::EqualEqual => Some("=="),
+        TokenKind::BangEqual => Some("!="),
+        TokenKind::Greater => Some(">"),
+        TokenKind::GreaterEqual => Some(">="),
+        TokenKind::Less => Some("<"),
+        TokenKind::LessEqual => Some("<="),
+        TokenKind::Equal => Some("="),
+        TokenKind::PlusEquals => Some("+="),
+        TokenKind::MinusEquals => Some("-="),
+        TokenKind::AsteriskEquals => Some("*="),
+        TokenKind::SlashEquals => Some("/="),
+        TokenKind::PercentEquals => Some("%="),
+        TokenKind::AmpersandAmpersand => Some("&&"),
+        TokenKind::PipePipe => Some("||"),
+        TokenKind::Ampersand => Some("&"),
+        TokenKind::Pipe => Some("|"),
+        TokenKind::Caret => Some("^"),
+        TokenKind::LessLess => Some("<<"),
+        TokenKind::GreaterGreater => Some(">>"),
+        TokenKind::AmpersandEquals => Some("&="),
+        TokenKind::PipeEquals => Some("|="),
+        TokenKind::CaretEquals => Some("^="),
+        TokenKind::LessLessEquals => Some("<<="),
+        TokenKind::GreaterGreaterEquals => Some(">>="),
+        _ => None,
+    }
+}
\ No newline at end of file
@@ -1,53 +1,359 @@
-use crate::ast::Statement;
-use crate::lexer::Lexer;
-use crate::error::BlazeError;
-use crate::token::Token;
-use crate::parser::Parser;
-
-pub struct Compiler {
-    pub files: Vec<String>,
-    pub errors: Vec<BlazeError>,
-}
-
-impl Compiler { 
-    pub fn new() -> Compiler {
-        Compiler {
-            files: Vec::new(),
-            errors: Vec::new(),
-        }
-    }
-
-    pub fn compile(&mut self) -> Result<(), BlazeError> {
-        for file in &self.files {
-            println!("Compiling {}", file);
-
-            let contents: Result<String, std::io::Error> = std::fs::read_to_string(file);
-            if let Err(e) = contents {
-                let error = BlazeError::IOError(std::rc::Rc::new(e));
-                return Err(error);
-            }
-
-            let mut lexer: Lexer = Lexer::new(file.to_string(), contents.unwrap());
-            let tokens: Result<Vec<Token>, Vec<BlazeError>> = lexer.lex();
-            if let Err(errors) = tokens.clone() {
-                self.errors.extend(errors);
-                continue;
-            }
-
-            let mut parser: Parser = Parser::new(tokens.unwrap());
-            let statements: Result<Vec<Statement>, Vec<BlazeError>> = parser.parse();
-            if let Err(errors) = statements.clone() {
-                self.errors.extend(errors);
-                continue;
-            }
-
-            println!("Compiled {}", file);
-        }
-
-        Ok(())
-    }
-
-    pub fn add_file(&mut self, filename: String) {
-        self.files.push(filename);
-    }
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use crate::ast::Statement;
+use crate::checker::{Checker, Types};
+use crate::codegen::Codegen;
+use crate::lexer::Lexer;
+use crate::error::{BlazeError, ErrorCode};
+use crate::diagnostic::SourceMap;
+use crate::lint::Lints;
+use crate::span::{FileId, Span};
+use crate::token::Token;
+use crate::parser::Parser;
+
+const C_COMPILERS: [&str; 3] = ["cc", "gcc", "clang"];
+const STD_ROOT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/runtime");
+
+#[derive(Default)]
+pub struct Compiler {
+    pub files: Vec<String>,
+    pub errors: Vec<BlazeError>,
+    pub warnings: Vec<BlazeError>,
+    pub lints: Lints,
+    pub output: String,
+    pub sources: SourceMap,
+    pub std_root: Option<PathBuf>,
+    // The namespace each loaded module file was added to the program as, by its canonical path.
+    pub modules: HashMap<PathBuf, String>,
+    pub cc: Option<String>,
+    pub cc_flags: Vec<String>,
+    pub build_dir: Option<PathBuf>,
+    pub output_file: Option<PathBuf>,
+    pub object_only: bool,
+    pub release: bool,
+    pub artifact: Option<PathBuf>,
+}
+
+impl Compiler {
+    pub fn new() -> Compiler {
+        Compiler {
+            files: Vec::new(),
+            errors: Vec::new(),
+            warnings: Vec::new(),
+            lints: Lints::new(),
+            output: String::new(),
+            sources: SourceMap::new(),
+            std_root: None,
+            modules: HashMap::new(),
+            cc: None,
+            cc_flags: Vec::new(),
+            build_dir: None,
+            output_file: None,
+            object_only: false,
+            release: false,
+            artifact: None,
+        }
+    }
+
+    pub fn lex(&mut self) -> Result<Vec<(String, Vec<Token>)>, BlazeError> {
+        let mut files: Vec<(String, Vec<Token>)> = Vec::new();
+        for file in self.files.clone() {
+            let contents: Result<String, std::io::Error> = std::fs::read_to_string(&file);
+            if let Err(e) = contents {
+                let error = BlazeError::IOError(std::rc::Rc::new(std::io::Error::new(e.kind(), format!("cannot read '{}': {}", file, e))));
+                return Err(error);
+            }
+            let contents: String = contents.unwrap();
+            let id: FileId = self.sources.add(file.clone(), contents.clone());
+
+            let mut lexer: Lexer = Lexer::new(id, contents);
+            match lexer.lex() {
+                Ok(tokens) => files.push((file, tokens)),
+                Err(errors) => self.errors.extend(errors),
+            }
+        }
+        Ok(files)
+    }
+
+    pub fn parse(&mut self) -> Result<Vec<Statement>, BlazeError> {
+        let mut program: Vec<Statement> = Vec::new();
+        for (file, tokens) in self.lex()? {
+            let mut statements: Vec<Statement> = self.parse_tokens(tokens);
+            let project: PathBuf = Path::new(&file).parent().map(Path::to_path_buf).unwrap_or_default();
+            let canonical: PathBuf = std::fs::canonicalize(&file).unwrap_or_else(|_| PathBuf::from(&file));
+            let mut importers: Vec<(String, PathBuf)> = vec![(file, canonical)];
+            self.load_imports(&mut statements, &project, &mut importers, &mut program);
+            program.extend(statements);
+        }
+        Ok(program)
+    }
+
+    fn parse_tokens(&mut self, tokens: Vec<Token>) -> Vec<Statement> {
+        let mut parser: Parser = Parser::new(tokens);
+        match parser.parse() {
+            Ok(statements) => statements,
+            Err(errors) => {
+                self.errors.extend(errors);
+                parser.statements
+            }
+        }
+    }
+
+    // Resolves every import in `statements`, loading each module once as a namespace of `program`
+    // and pointing the import at that namespace.
+    fn load_imports(&mut self, statements: &mut [Statement], project: &Path, importers: &mut Vec<(String, PathBuf)>, program: &mut Vec<Statement>) {
+        for statement in statements {
+            match statement {
+                Statement::Documented(_, statement, _) | Statement::Attribute(_, _, statement, _) => {
+                    self.load_imports(std::slice::from_mut(&mut **statement), project, importers, program);
+                }
+                Statement::Namespace(_, statements, _) => self.load_imports(statements, project, importers, program),
+                Statement::Import(_, path, span) => match self.load_module(path, span, project, importers, program) {
+                    Ok(module) => *path = module,
+                    Err(error) => self.errors.push(error),
+                },
+                _ => {}
+            }
+        }
+    }
+
+    fn load_module(&mut self, path: &str, span: &Span, project: &Path, importers: &mut Vec<(String, PathBuf)>, program: &mut Vec<Statement>) -> Result<String, BlazeError> {
+        let module: String = path.strip_suffix(".bl").unwrap_or(path).to_string();
+        let segments: Vec<&str> = module.split('/').collect();
+        if !segments.iter().all(|segment| is_identifier(segment)) {
+            return Err(BlazeError::ImportError(ErrorCode::InvalidModulePath, format!("invalid module path '{}'", path), span.clone())
+                .with_help("module paths are names separated by '/', like \"std/io\""));
+        }
+        let file: PathBuf = match segments.split_first() {
+            Some((&"std", rest)) if !rest.is_empty() => self.std_root().join(rest.join("/")),
+            _ => project.join(&module),
+        }.with_extension("bl");
+
+        let canonical: PathBuf = std::fs::canonicalize(&file).map_err(|_| {
+            BlazeError::ImportError(ErrorCode::ModuleNotFound, format!("cannot find module '{}'", module), span.clone())
+                .with_note(&format!("looked for '{}'", file.display()))
+        })?;
+        if let Some(index) = importers.iter().position(|(_, importer)| *importer == canonical) {
+            let cycle: Vec<String> = importers[index..].iter()
+                .map(|(name, _)| name.clone())
+                .chain([module.clone()])
+                .collect();
+            return Err(BlazeError::ImportError(ErrorCode::ImportCycle, format!("import cycle: {}", cycle.join(" -> ")), span.clone())
+                .with_note("modules cannot import each other, directly or indirectly"));
+        }
+        if let Some(namespace) = self.modules.get(&canonical) {
+            return Ok(namespace.clone());
+        }
+        // Two files imported with the same path from different projects get namespaces of their own. A number
+        // cannot be a segment of a module path, so the suffix cannot clash with another module, and the C names
+        // of `lib/math/1` and `lib/math_1` differ because mangling escapes the underscore.
+        let mut module: String = module;
+        if self.modules.values().any(|namespace| *namespace == module) {
+            module = format!("{}/{}", module, self.modules.len());
+        }
+        self.modules.insert(canonical.clone(), module.clone());
+
+        let contents: String = std::fs::read_to_string(&file).map_err(|e| {
+            BlazeError::ImportError(ErrorCode::UnreadableModule, format!("cannot read module '{}': {}", module, e), span.clone())
+        })?;
+        let id: FileId = self.sources.add(file.to_string_lossy().to_string(), contents.clone());
+        let mut lexer: Lexer = Lexer::new(id, contents);
+        let tokens: Vec<Token> = match lexer.lex() {
+            Ok(tokens) => tokens,
+            Err(errors) => {
+                self.errors.extend(errors);
+                return Ok(module);
+            }
+        };
+        let mut statements: Vec<Statement> = self.parse_tokens(tokens);
+        importers.push((module.clone(), canonical));
+        self.load_imports(&mut statements, project, importers, program);
+        importers.pop();
+        program.push(Statement::Namespace(module.clone(), statements, Span { file: id, start: 0, end: 0 }));
+        Ok(module)
+    }
+
+    pub fn std_root(&self) -> PathBuf {
+        self.std_root.clone()
+            .or_else(|| std::env::var("BLAZE_STD").ok().map(PathBuf::from))
+            .unwrap_or_else(|| PathBuf::from(STD_ROOT))
+    }
+
+    pub fn check(&mut self) -> Result<(Vec<Statement>, Types), BlazeError> {
+        let program: Vec<Statement> = self.parse()?;
+        if self.errors.iter().any(|error| error.is_syntax_error() || error.is_import_error()) {
+            return Ok((program, Types::default()));
+        }
+
+        let mut checker: Checker = Checker::new(program);
+        checker.lints = self.lints.clone();
+        if !self.object_only {
+            checker.program = self.files.last().and_then(|file| self.sources.find(file));
+        }
+        let result: Result<Vec<Statement>, Vec<BlazeError>> = checker.check();
+        self.warnings.extend(checker.warnings);
+        match result {
+            Ok(statements) => Ok((statements, checker.types)),
+            Err(errors) => {
+                self.errors.extend(errors);
+                Ok((Vec::new(), Types::default()))
+            }
+        }
+    }
+
+    pub fn compile(&mut self) -> Result<(), BlazeError> {
+        let (program, types) = self.check()?;
+        if !self.errors.is_empty() {
+            return Ok(());
+        }
+
+        let mut codegen: Codegen = Codegen::new(program);
+        codegen.types = types;
+        codegen.sources = self.sources.clone();
+        codegen.release = self.release;
+        match codegen.generate() {
+            Ok(output) => self.output = output,
+            Err(errors) => self.errors.extend(errors),
+        }
+
+        Ok(())
+    }
+
+    pub fn build(&mut self) -> Result<(), BlazeError> {
+        self.compile()?;
+        if !self.errors.is_empty() {
+            return Ok(());
+        }
+
+        let name: String = self.files.last()
+            .and_then(|file| Path::new(file).file_stem())
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| "main".to_string());
+        let build_dir: PathBuf = match &self.build_dir {
+            Some(build_dir) => {
+                std::fs::create_dir_all(build_dir).map_err(|e| BlazeError::IOError(std::rc::Rc::new(e)))?;
+                build_dir.clone()
+            }
+            None => create_temp_dir()?,
+        };
+        let result: Result<(), BlazeError> = self.build_in(&build_dir, &name);
+        if self.build_dir.is_none() {
+            let _ = std::fs::remove_dir_all(&build_dir);
+        }
+        result
+    }
+
+    fn build_in(&mut self, build_dir: &Path, name: &str) -> Result<(), BlazeError> {
+        let c_file: PathBuf = build_dir.join(format!("{}.c", name));
+        std::fs::write(&c_file, &self.output).map_err(|e| BlazeError::IOError(std::rc::Rc::new(e)))?;
+
+        let artifact: PathBuf = match &self.output_file {
+            Some(output_file) => output_file.clone(),
+            None if self.object_only => PathBuf::from(format!("{}.o", name)),
+            None => PathBuf::from(format!("{}{}", name, std::env::consts::EXE_SUFFIX)),
+        };
+
+        let mut arguments: Vec<String> = vec!["-std=c99".to_string()];
+        if self.release {
+            arguments.extend(["-O2".to_string(), "-DNDEBUG".to_string()]);
+        } else {
+            arguments.extend(["-O0".to_string(), "-g".to_string()]);
+        }
+        if self.object_only {
+            arguments.push("-c".to_string());
+        }
+        arguments.extend(self.cc_flags.clone());
+        arguments.push(c_file.to_string_lossy().to_string());
+        arguments.push("-o".to_string());
+        arguments.push(artifact.to_string_lossy().to_string());
+
+        let output: Output = self.run_cc(&arguments)?;
+        if !output.status.success() {
+            let stderr: String = String::from_utf8_lossy(&output.stderr).to_string();
+            let diagnostics: Vec<BlazeError> = self.parse_cc_diagnostics(&stderr);
+            if diagnostics.is_empty() {
+                return Err(BlazeError::IOError(std::rc::Rc::new(std::io::Error::other(
+                    format!("C compiler failed:\n{}", stderr.trim_end())
+                ))));
+            }
+            self.errors.extend(diagnostics);
+            return Ok(());
+        }
+
+        self.artifact = Some(artifact);
+        Ok(())
+    }
+
+    pub fn add_file(&mut self, filename: String) {
+        self.files.push(filename);
+    }
+
+    fn run_cc(&self, arguments: &[String]) -> Result<Output, BlazeError> {
+        let candidates: Vec<String> = match self.cc.clone()
+            .or_else(|| std::env::var("BLAZE_CC").ok())
+            .or_else(|| std::env::var("CC").ok()) {
+            Some(cc) => vec![cc],
+            None => C_COMPILERS.iter().map(|cc| cc.to_string()).collect(),
+        };
+        for cc in &candidates {
+            match Command::new(cc).args(arguments).output() {
+                Ok(output) => return Ok(output),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(BlazeError::IOError(std::rc::Rc::new(e))),
+            }
+        }
+        Err(BlazeError::IOError(std::rc::Rc::new(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("no C compiler found (tried {}), set BLAZE_CC to choose one", candidates.join(", "))
+        ))))
+    }
+
+    fn parse_cc_diagnostics(&mut self, stderr: &str) -> Vec<BlazeError> {
+        let mut errors: Vec<BlazeError> = Vec::new();
+        for line in stderr.lines() {
+            let (location, message) = match line.split_once(": error: ").or_else(|| line.split_once(": fatal error: ")) {
+                Some(parts) => parts,
+                None => continue,
+            };
+            let mut parts = location.rsplitn(3, ':');
+            let column: Option<usize> = parts.next().and_then(|column| column.parse().ok());
+            let line_number: Option<usize> = parts.next().and_then(|line| line.parse().ok());
+            let filename: Option<&str> = parts.next();
+            // The column is one of the generated C, so the error covers the statement on the line `#line` names.
+            if let (Some(filename), Some(line_number), Some(_)) = (filename, line_number, column) {
+                let file: FileId = match self.sources.find(filename) {
+                    Some(file) => file,
+                    None => self.sources.add(filename.to_string(), std::fs::read_to_string(filename).unwrap_or_default()),
+                };
+                let (start, end) = self.sources.files[file].line_span(line_number);
+                errors.push(BlazeError::CodegenError(ErrorCode::CCompiler, message.to_string(), Span { file, start, end }));
+            }
+        }
+        errors
+    }
+
+}
+
+// Creates a directory of its own for one invocation's intermediate files, so that builds running at the same time
+// never write to the same paths.
+pub fn create_temp_dir() -> Result<PathBuf, BlazeError> {
+    for attempt in 0..100 {
+        let dir: PathBuf = std::env::temp_dir().join(format!("blaze-{}-{}", std::process::id(), attempt));
+        match std::fs::create_dir(&dir) {
+            Ok(()) => return Ok(dir),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(BlazeError::IOError(std::rc::Rc::new(e))),
+        }
+    }
+    Err(BlazeError::IOError(std::rc::Rc::new(std::io::Error::new(
+        std::io::ErrorKind::AlreadyExists,
+        format!("cannot create a build directory in '{}'", std::env::temp_dir().display())
+    ))))
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_') && chars.all(|c| c.is_alphanumeric() || c == '_')
 }
\ No newline at end of file
@@ -0,0 +1,401 @@
+use owo_colors::OwoColorize;
+
+use crate::span::{FileId, Span};
+
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+    pub files: Vec<SourceFile>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SourceFile {
+    pub name: String,
+    pub contents: String,
+    pub lines: Vec<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: &'static str,
+    pub message: String,
+    pub labels: Vec<Label>,
+    pub notes: Vec<String>,
+    pub help: Vec<String>,
+    pub suggestions: Vec<Suggestion>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Label {
+    pub span: Span,
+    pub message: String,
+    pub primary: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Suggestion {
+    pub span: Span,
+    pub replacement: String,
+    pub message: String,
+}
+
+impl SourceMap {
+    pub fn new() -> SourceMap {
+        SourceMap { files: Vec::new() }
+    }
+    pub fn add(&mut self, name: String, contents: String) -> FileId {
+        let file: SourceFile = SourceFile::new(name, contents);
+        match self.find(&file.name) {
+            Some(id) => {
+                self.files[id] = file;
+                id
+            }
+            None => {
+                self.files.push(file);
+                self.files.len() - 1
+            }
+        }
+    }
+    pub fn find(&self, name: &str) -> Option<FileId> {
+        self.files.iter().position(|file| file.name == name)
+    }
+    pub fn get(&self, file: FileId) -> Option<&SourceFile> {
+        self.files.get(file)
+    }
+    pub fn name(&self, file: FileId) -> String {
+        match self.get(file) {
+            Some(file) => file.name.clone(),
+            None => "<unknown>".to_string(),
+        }
+    }
+}
+
+impl SourceFile {
+    pub fn new(name: String, contents: String) -> SourceFile {
+        let mut lines: Vec<usize> = vec![0];
+        lines.extend(contents.match_indices('\n').map(|(index, _)| index + 1));
+        SourceFile { name, contents, lines }
+    }
+    // Zero-based index of the line containing `offset`.
+    pub fn line_index(&self, offset: usize) -> usize {
+        self.lines.partition_point(|start| *start <= self.clamp(offset)) - 1
+    }
+    pub fn line(&self, index: usize) -> &str {
+        let start: usize = self.lines[index];
+        let end: usize = match self.lines.get(index + 1) {
+            Some(next) => next - 1,
+            None => self.contents.len(),
+        };
+        self.contents[start..end].trim_end_matches('\r')
+    }
+    // One-based line and column of `offset`, with the column counted in characters.
+    pub fn location(&self, offset: usize) -> (usize, usize) {
+        let offset: usize = self.clamp(offset);
+        let line: usize = self.line_index(offset);
+        (line + 1, self.contents[self.lines[line]..offset].chars().count() + 1)
+    }
+    // The text of a one-based line without its indentation, or an empty span at the end past the last line.
+    pub fn line_span(&self, line: usize) -> (usize, usize) {
+        match self.lines.get(line.saturating_sub(1)) {
+            Some(start) => {
+                let text: &str = self.line(line.saturating_sub(1));
+                let indentation: usize = text.len() - text.trim_start().len();
+                (start + indentation.min(text.trim_end().len()), start + text.trim_end().len())
+            }
+            None => (self.contents.len(), self.contents.len()),
+        }
+    }
+    fn clamp(&self, offset: usize) -> usize {
+        let mut offset: usize = offset.min(self.contents.len());
+        while !self.contents.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        offset
+    }
+}
+
+impl Severity {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        }
+    }
+}
+
+impl Label {
+    pub fn primary(span: Span, message: &str) -> Label {
+        Label { span, message: message.to_string(), primary: true }
+    }
+    pub fn secondary(span: Span, message: &str) -> Label {
+        Label { span, message: message.to_string(), primary: false }
+    }
+}
+
+impl Diagnostic {
+    pub fn new(code: &'static str, message: String) -> Diagnostic {
+        Diagnostic {
+            severity: Severity::Error,
+            code,
+            message,
+            labels: Vec::new(),
+            notes: Vec::new(),
+            help: Vec::new(),
+            suggestions: Vec::new(),
+        }
+    }
+    pub fn primary(&self) -> Option<&Label> {
+        self.labels.iter().find(|label| label.primary).or(self.labels.first())
+    }
+
+    pub fn render(&self, sources: &SourceMap) -> String {
+        let mut out: String = String::new();
+        let header: String = match self.severity {
+            Severity::Error => format!("error[{}]", self.code).red().bold().to_string(),
+            Severity::Warning => format!("warning[{}]", self.code).yellow().bold().to_string(),
+        };
+        out.push_str(&format!("{}: {}\n", header, self.message.bold()));
+        let width: usize = self.gutter_width(sources);
+        let gutter: String = " ".repeat(width);
+
+        let mut files: Vec<FileId> = Vec::new();
+        if let Some(primary) = self.primary() {
+            files.push(primary.span.file);
+        }
+        for label in &self.labels {
+            if !files.contains(&label.span.file) {
+                files.push(label.span.file);
+            }
+        }
+        for (index, file) in files.into_iter().enumerate() {
+            let labels: Vec<&Label> = self.labels.iter().filter(|label| label.span.file == file).collect();
+            let source: &SourceFile = match sources.get(file) {
+                Some(source) => source,
+                None => continue,
+            };
+            let anchor: &Label = match self.primary() {
+                Some(primary) if index == 0 => primary,
+                _ => labels[0],
+            };
+            let (line, column) = source.location(anchor.span.start);
+            let arrow: &str = if index == 0 { "-->" } else { ":::" };
+            out.push_str(&format!("{}{} {}:{}:{}\n", gutter, arrow.bright_blue(), source.name, line, column));
+            out.push_str(&format!("{} {}\n", gutter, "|".bright_blue()));
+            render_snippet(&mut out, source, &labels, width, self.severity);
+        }
+
+        for note in &self.notes {
+            out.push_str(&format!("{} {} {}: {}\n", gutter, "=".bright_blue(), "note".bold(), note));
+        }
+        for help in &self.help {
+            out.push_str(&format!("{} {} {}: {}\n", gutter, "=".bright_blue(), "help".bold(), help));
+        }
+        for suggestion in &self.suggestions {
+            render_suggestion(&mut out, sources, suggestion, width);
+        }
+        out
+    }
+    // One line of JSON with every span resolved to a file name, byte range and line/column.
+    pub fn to_json(&self, sources: &SourceMap) -> String {
+        let location: String = match self.primary() {
+            Some(label) => span_json(&label.span, sources),
+            None => "\"file\":null,\"start\":null,\"end\":null,\"line\":null,\"column\":null,\"end_line\":null,\"end_column\":null".to_string(),
+        };
+        let labels: Vec<String> = self.labels.iter()
+            .map(|label| format!("{{{},\"message\":{},\"primary\":{}}}", span_json(&label.span, sources), json_string(&label.message), label.primary))
+            .collect();
+        let suggestions: Vec<String> = self.suggestions.iter()
+            .map(|suggestion| format!(
+                "{{{},\"replacement\":{},\"message\":{}}}",
+                span_json(&suggestion.span, sources),
+                json_string(&suggestion.replacement),
+                json_string(&suggestion.message),
+            ))
+            .collect();
+        format!(
+            "{{\"severity\":{},\"code\":{},\"message\":{},{},\"labels\":[{}],\"notes\":[{}],\"help\":[{}],\"suggestions\":[{}]}}",
+            json_string(self.severity.name()),
+            json_string(self.code),
+            json_string(&self.message),
+            location,
+            labels.join(","),
+            self.notes.iter().map(|note| json_string(note)).collect::<Vec<String>>().join(","),
+            self.help.iter().map(|help| json_string(help)).collect::<Vec<String>>().join(","),
+            suggestions.join(","),
+        )
+    }
+    fn gutter_width(&self, sources: &SourceMap) -> usize {
+        self.labels.iter().map(|label| &label.span)
+            .chain(self.suggestions.iter().map(|suggestion| &suggestion.span))
+            .filter_map(|span| sources.get(span.file).map(|source| source.location(span.end).0))
+            .max()
+            .unwrap_or(1)
+            .to_string()
+            .len()
+    }
+}
+
+fn render_snippet(out: &mut String, source: &SourceFile, labels: &[&Label], width: usize, severity: Severity) {
+    let gutter: String = " ".repeat(width);
+    let multiline: Vec<&Label> = labels.iter().copied()
+        .filter(|label| source.line_index(label.span.start) != source.line_index(label.span.end))
+        .collect();
+    let margin: &str = if multiline.is_empty() { "" } else { "  " };
+
+    let mut lines: Vec<usize> = Vec::new();
+    for label in labels {
+        let start: usize = source.line_index(label.span.start);
+        let end: usize = source.line_index(label.span.end);
+        // Long multi-line spans only show their first and last two lines.
+        lines.extend(start..=end.min(start + 1));
+        lines.extend(end.saturating_sub(1).max(start)..=end);
+    }
+    lines.sort();
+    lines.dedup();
+
+    let mut previous: Option<usize> = None;
+    for line in lines {
+        if previous.is_some_and(|previous| line > previous + 1) {
+            match multiline.iter().find(|label| covers(source, label, line - 1)) {
+                Some(label) => out.push_str(&format!("{}{}{}\n", "...".bright_blue(), " ".repeat(width), color("|", label.primary, severity))),
+                None => out.push_str(&format!("{}\n", "...".bright_blue())),
+            }
+        }
+        previous = Some(line);
+
+        let mut prefix: String = margin.to_string();
+        for label in &multiline {
+            let start: usize = source.line_index(label.span.start);
+            let end: usize = source.line_index(label.span.end);
+            if line == start {
+                prefix = format!("{} ", color("/", label.primary, severity));
+            } else if line > start && line <= end {
+                prefix = format!("{} ", color("|", label.primary, severity));
+            }
+        }
+        let text: &str = source.line(line);
+        out.push_str(&format!("{} {} {}{}\n", format!("{:>width$}", line + 1, width = width).bright_blue(), "|".bright_blue(), prefix, expand_tabs(text)));
+
+        let mut single: Vec<&&Label> = labels.iter()
+            .filter(|label| source.line_index(label.span.start) == line && !multiline.iter().any(|other| std::ptr::eq(*other, **label)))
+            .collect();
+        single.sort_by_key(|label| std::cmp::Reverse(label.span.start));
+        for label in single {
+            let start: usize = column_width(source, line, label.span.start);
+            let length: usize = column_width(source, line, label.span.end).saturating_sub(start).max(1);
+            let marker: &str = if label.primary { "^" } else { "-" };
+            let underline: String = format!("{}{}", " ".repeat(start), marker.repeat(length));
+            let message: String = if label.message.is_empty() { underline } else { format!("{} {}", underline, label.message) };
+            out.push_str(&format!("{} {} {}{}\n", gutter, "|".bright_blue(), margin, color(&message, label.primary, severity)));
+        }
+        for label in &multiline {
+            if source.line_index(label.span.end) == line {
+                let end: usize = column_width(source, line, label.span.end).max(1);
+                let marker: &str = if label.primary { "^" } else { "-" };
+                let message: String = format!("|{}{} {}", "_".repeat(end), marker, label.message);
+                out.push_str(&format!("{} {} {}\n", gutter, "|".bright_blue(), color(message.trim_end(), label.primary, severity)));
+            }
+        }
+    }
+    out.push_str(&format!("{} {}\n", gutter, "|".bright_blue()));
+}
+
+fn render_suggestion(out: &mut String, sources: &SourceMap, suggestion: &Suggestion, width: usize) {
+    let source: &SourceFile = match sources.get(suggestion.span.file) {
+        Some(source) => source,
+        None => return,
+    };
+    let line: usize = source.line_index(suggestion.span.start);
+    if line != source.line_index(suggestion.span.end) || suggestion.replacement.contains('\n') {
+        out.push_str(&format!("{}: {}: `{}`\n", "help".bold(), suggestion.message, suggestion.replacement));
+        return;
+    }
+    let gutter: String = " ".repeat(width);
+    let start: usize = suggestion.span.start - source.lines[line];
+    let end: usize = suggestion.span.end - source.lines[line];
+    let text: &str = source.line(line);
+    let before: &str = &text[..start.min(text.len())];
+    let after: &str = &text[end.min(text.len())..];
+    let marker: &str = if suggestion.span.start == suggestion.span.end { "+" } else { "~" };
+
+    out.push_str(&format!("{}: {}\n", "help".bold(), suggestion.message));
+    out.push_str(&format!("{} {}\n", gutter, "|".bright_blue()));
+    out.push_str(&format!("{} {} {}{}{}\n", format!("{:>width$}", line + 1, width = width).bright_blue(), "|".bright_blue(), expand_tabs(before), suggestion.replacement, expand_tabs(after)));
+    out.push_str(&format!("{} {} {}{}\n", gutter, "|".bright_blue(), " ".repeat(display_width(before)), marker.repeat(display_width(&suggestion.replacement).max(1)).green()));
+}
+
+fn span_json(span: &Span, sources: &SourceMap) -> String {
+    let (line, column, end_line, end_column) = match sources.get(span.file) {
+        Some(source) => {
+            let (line, column) = source.location(span.start);
+            let (end_line, end_column) = source.location(span.end);
+            (line, column, end_line, end_column)
+        }
+        None => (0, 0, 0, 0),
+    };
+    format!(
+        "\"file\":{},\"start\":{},\"end\":{},\"line\":{},\"column\":{},\"end_line\":{},\"end_column\":{}",
+        json_string(&sources.name(span.file)),
+        span.start,
+        span.end,
+        line,
+        column,
+        end_line,
+        end_column,
+    )
+}
+
+fn json_string(text: &str) -> String {
+    let mut out: String = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn covers(source: &SourceFile, label: &Label, line: usize) -> bool {
+    source.line_index(label.span.start) <= line && line <= source.line_index(label.span.end)
+}
+
+// Width on screen of the text between the start of `line` and `offset`.
+fn column_width(source: &SourceFile, line: usize, offset: usize) -> usize {
+    let start: usize = source.lines[line];
+    let text: &str = source.line(line);
+    let end: usize = source.clamp(offset).saturating_sub(start).min(text.len());
+    let mut end: usize = end;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    display_width(&text[..end])
+}
+
+fn display_width(text: &str) -> usize {
+    text.chars().map(|c| if c == '\t' { 4 } else { 1 }).sum()
+}
+
+fn expand_tabs(text: &str) -> String {
+    text.replace('\t', "    ")
+}
+
+fn color(text: &str, primary: bool, severity: Severity) -> String {
+    match (primary, severity) {
+        (true, Severity::Error) => text.red().bold().to_string(),
+        (true, Severity::Warning) => text.yellow().bold().to_string(),
+        (false, _) => text.bright_blue().bold().to_string(),
+    }
+}
\ No newline at end of file
@@ -1,105 +1,250 @@
-use crate::span::Span;
-use owo_colors::OwoColorize;
-
-#[derive(Debug, Clone)]
-pub enum BlazeError {
-    SyntaxError(String, Span),
-    ParseError(String, Span),
-    TypeError(String, Span),
-    RuntimeError(String, Span),
-    IOError(std::rc::Rc<std::io::Error>),
-}
-
-impl BlazeError {
-    pub fn span(&self) -> Span {
-        match self {
-            BlazeError::SyntaxError(_, s) => s.clone(),
-            BlazeError::ParseError(_, s) => s.clone(),
-            BlazeError::TypeError(_, s) => s.clone(),
-            BlazeError::RuntimeError(_, s) => s.clone(),
-            _ => unreachable!(),
-        }
-    }
-    fn message(&self, show_type: bool) -> String {
-        match self {
-            BlazeError::SyntaxError(msg, _) => format!("{}{}", if show_type { "SyntaxError: " } else { "" }, msg),
-            BlazeError::ParseError(msg, _) => format!("{}{}", if show_type { "ParseError: " } else { "" }, msg),
-            BlazeError::TypeError(msg, _) => format!("{}{}", if show_type { "TypeError: " } else { "" }, msg),
-            BlazeError::RuntimeError(msg, _) => format!("{}{}", if show_type { "RuntimeError: " } else { "" }, msg),
-            _ => unreachable!(),
-        }
-    }
-    pub fn to_string(&self) -> String {
-        let mut out: String = String::new();
-        out.push_str(format!("[{}:{}:{}] {}\n", 
-            self.span().filename.clone(),
-            self.get_line_number(),
-            self.get_column(),
-            self.message(true)).as_str());
-        out.push_str(format!("{}{}\n", 
-            format!("{:>5} | ", self.get_line_number() - 1).bright_blue(),
-            self.get_contents_of_line(self.get_line_number()).unwrap()).as_str());
-        out.push_str(format!("{}{}\n",
-            format!("{:>5} | ", "").bright_blue(),
-            format!("{}- {}",
-                " ".repeat(self.get_column() - 1) + "^".repeat(self.span().end.clone() - self.span().start.clone()).as_str(),
-                self.message(false)).red()).as_str());
-        out
-    }
-    fn get_contents(&self) -> Result<String, BlazeError> {
-        match std::fs::read_to_string(self.span().filename.clone()) {
-            Ok(contents) => Ok(contents),
-            Err(_) => return Err(BlazeError::IOError(std::rc::Rc::new(std::io::Error::new(
-                std::io::ErrorKind::NotFound,
-                format!("file '{}' not found", self.span().filename.clone())
-            )))),
-        }
-    }
-    fn get_contents_of_line(&self, line: usize) -> Result<String, BlazeError> {
-        let contents = self.get_contents().unwrap();
-        let mut line_number: usize = 1;
-        let mut start: usize = 0;
-        let mut end: usize = 0;
-        for (i, c) in contents.chars().enumerate() {
-            if c == '\n' {
-                line_number += 1;
-                if line_number == line {
-                    start = i + 1;
-                }
-                if line_number == line + 1 {
-                    end = i;
-                    break;
-                }
-            }
-        }
-        Ok(contents[start..end].to_string())
-    }
-    fn get_line_number(&self) -> usize {
-        let contents = self.get_contents().unwrap();
-        let mut line_number: usize = 1;
-        for (i, c) in contents.chars().enumerate() {
-            if i == self.span().start.clone() {
-                break;
-            }
-            if c == '\n' {
-                line_number += 1;
-            }
-        }
-        line_number
-    }
-    fn get_column(&self) -> usize {
-        let contents = self.get_contents().unwrap();
-        let mut column: usize = 1;
-        for (i, c) in contents.chars().enumerate() {
-            if i == self.span().start.clone() {
-                break;
-            }
-            if c == '\n' {
-                column = 1;
-            } else {
-                column += 1;
-            }
-        }
-        column
-    }
+use crate::diagnostic::{Diagnostic, Label, Severity, SourceMap, Suggestion};
+use crate::lint::Lint;
+use crate::span::Span;
+
+// What went wrong, which gives every kind of error a code of its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    UnterminatedString,
+    UnterminatedComment,
+    InvalidCharacterLiteral,
+    UnexpectedCharacter,
+    InvalidEscape,
+    UnexpectedToken,
+    UnexpectedEndOfFile,
+    InvalidNumberLiteral,
+    InvalidArrayLength,
+    InvalidVariant,
+    InvalidModulePath,
+    ModuleNotFound,
+    UnreadableModule,
+    ImportCycle,
+    DuplicateDefinition,
+    MisplacedDeclaration,
+    UnknownType,
+    InvalidType,
+    GenericArguments,
+    RecursiveInstance,
+    InvalidMethod,
+    InvalidJump,
+    ValueCount,
+    UnhandledError,
+    UnknownAttribute,
+    VoidValue,
+    CannotInfer,
+    SelfOutsideMethod,
+    LiteralOutOfRange,
+    IndexOutOfBounds,
+    MismatchedTypes,
+    InvalidOperand,
+    InvalidTry,
+    NonExhaustiveMatch,
+    MisplacedRange,
+    InvalidAssignment,
+    UndefinedName,
+    UnknownMember,
+    ExpectedName,
+    NotAValue,
+    NotCallable,
+    ArgumentCount,
+    InvalidStructLiteral,
+    UncheckedOptional,
+    InvalidPattern,
+    Unsupported,
+    RecursiveType,
+    CCompiler,
+    Runtime,
+    Io,
+    MissingMain,
+}
+
+impl ErrorCode {
+    pub fn code(&self) -> &'static str {
+        match self {
+            ErrorCode::UnterminatedString => "E0001",
+            ErrorCode::UnterminatedComment => "E0002",
+            ErrorCode::InvalidCharacterLiteral => "E0003",
+            ErrorCode::UnexpectedCharacter => "E0004",
+            ErrorCode::InvalidEscape => "E0005",
+            ErrorCode::UnexpectedToken => "E0006",
+            ErrorCode::UnexpectedEndOfFile => "E0007",
+            ErrorCode::InvalidNumberLiteral => "E0008",
+            ErrorCode::InvalidArrayLength => "E0009",
+            ErrorCode::InvalidVariant => "E0010",
+            ErrorCode::InvalidModulePath => "E0011",
+            ErrorCode::ModuleNotFound => "E0012",
+            ErrorCode::UnreadableModule => "E0013",
+            ErrorCode::ImportCycle => "E0014",
+            ErrorCode::DuplicateDefinition => "E0015",
+            ErrorCode::MisplacedDeclaration => "E0016",
+            ErrorCode::UnknownType => "E0017",
+            ErrorCode::InvalidType => "E0018",
+            ErrorCode::GenericArguments => "E0019",
+            ErrorCode::RecursiveInstance => "E0020",
+            ErrorCode::InvalidMethod => "E0021",
+            ErrorCode::InvalidJump => "E0022",
+            ErrorCode::ValueCount => "E0023",
+            ErrorCode::UnhandledError => "E0024",
+            ErrorCode::UnknownAttribute => "E0025",
+            ErrorCode::VoidValue => "E0026",
+            ErrorCode::CannotInfer => "E0027",
+            ErrorCode::SelfOutsideMethod => "E0028",
+            ErrorCode::LiteralOutOfRange => "E0029",
+            ErrorCode::IndexOutOfBounds => "E0030",
+            ErrorCode::MismatchedTypes => "E0031",
+            ErrorCode::InvalidOperand => "E0032",
+            ErrorCode::InvalidTry => "E0033",
+            ErrorCode::NonExhaustiveMatch => "E0034",
+            ErrorCode::MisplacedRange => "E0035",
+            ErrorCode::InvalidAssignment => "E0036",
+            ErrorCode::UndefinedName => "E0037",
+            ErrorCode::UnknownMember => "E0038",
+            ErrorCode::ExpectedName => "E0039",
+            ErrorCode::NotAValue => "E0040",
+            ErrorCode::NotCallable => "E0041",
+            ErrorCode::ArgumentCount => "E0042",
+            ErrorCode::InvalidStructLiteral => "E0043",
+            ErrorCode::UncheckedOptional => "E0044",
+            ErrorCode::InvalidPattern => "E0045",
+            ErrorCode::Unsupported => "E0046",
+            ErrorCode::RecursiveType => "E0047",
+            ErrorCode::CCompiler => "E0048",
+            ErrorCode::Runtime => "E0049",
+            ErrorCode::Io => "E0050",
+            ErrorCode::MissingMain => "E0051",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum BlazeError {
+    SyntaxError(ErrorCode, String, Span),
+    ParseError(ErrorCode, String, Span),
+    TypeError(ErrorCode, String, Span),
+    RuntimeError(ErrorCode, String, Span),
+    CodegenError(ErrorCode, String, Span),
+    ImportError(ErrorCode, String, Span),
+    IOError(std::rc::Rc<std::io::Error>),
+    Warning(Lint, String, Span),
+    // An error or warning with labels, notes or suggestions added to it, and the error it was made from.
+    Detailed(Box<BlazeError>, Box<Diagnostic>),
+}
+
+impl BlazeError {
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            BlazeError::SyntaxError(_, _, s)
+            | BlazeError::ParseError(_, _, s)
+            | BlazeError::TypeError(_, _, s)
+            | BlazeError::RuntimeError(_, _, s)
+            | BlazeError::CodegenError(_, _, s)
+            | BlazeError::ImportError(_, _, s)
+            | BlazeError::Warning(_, _, s) => Some(s.clone()),
+            BlazeError::IOError(_) => None,
+            BlazeError::Detailed(_, diagnostic) => diagnostic.primary().map(|label| label.span.clone()),
+        }
+    }
+    pub fn code(&self) -> &'static str {
+        match self {
+            BlazeError::SyntaxError(code, _, _)
+            | BlazeError::ParseError(code, _, _)
+            | BlazeError::TypeError(code, _, _)
+            | BlazeError::RuntimeError(code, _, _)
+            | BlazeError::CodegenError(code, _, _)
+            | BlazeError::ImportError(code, _, _) => code.code(),
+            BlazeError::IOError(_) => ErrorCode::Io.code(),
+            BlazeError::Warning(lint, _, _) => lint.code(),
+            BlazeError::Detailed(_, diagnostic) => diagnostic.code,
+        }
+    }
+    pub fn message(&self) -> String {
+        match self {
+            BlazeError::SyntaxError(_, msg, _)
+            | BlazeError::ParseError(_, msg, _)
+            | BlazeError::TypeError(_, msg, _)
+            | BlazeError::RuntimeError(_, msg, _)
+            | BlazeError::CodegenError(_, msg, _)
+            | BlazeError::ImportError(_, msg, _)
+            | BlazeError::Warning(_, msg, _) => msg.clone(),
+            BlazeError::IOError(error) => error.to_string(),
+            BlazeError::Detailed(_, diagnostic) => diagnostic.message.clone(),
+        }
+    }
+    pub fn is_syntax_error(&self) -> bool {
+        match self {
+            BlazeError::Detailed(error, _) => error.is_syntax_error(),
+            error => matches!(error, BlazeError::SyntaxError(..)),
+        }
+    }
+    pub fn is_import_error(&self) -> bool {
+        match self {
+            BlazeError::Detailed(error, _) => error.is_import_error(),
+            error => matches!(error, BlazeError::ImportError(..)),
+        }
+    }
+
+    pub fn diagnostic(&self) -> Diagnostic {
+        match self {
+            BlazeError::Detailed(_, diagnostic) => (**diagnostic).clone(),
+            BlazeError::IOError(_) => Diagnostic::new(self.code(), self.message()),
+            _ => {
+                let mut diagnostic: Diagnostic = Diagnostic::new(self.code(), self.message());
+                if let Some(span) = self.span() {
+                    diagnostic.labels.push(Label::primary(span, ""));
+                }
+                if let BlazeError::Warning(..) = self {
+                    diagnostic.severity = Severity::Warning;
+                }
+                diagnostic
+            }
+        }
+    }
+    pub fn is_warning(&self) -> bool {
+        self.diagnostic().severity == Severity::Warning
+    }
+    // Turns a warning into an error for a lint raised to `deny`.
+    pub fn denied(self, lint: Lint) -> BlazeError {
+        self.detailed(|diagnostic| {
+            diagnostic.severity = Severity::Error;
+            diagnostic.notes.push(format!("'{}' is set to deny", lint.name()));
+        })
+    }
+    pub fn render(&self, sources: &SourceMap) -> String {
+        self.diagnostic().render(sources)
+    }
+
+    pub fn with_label(self, span: Span, message: &str) -> BlazeError {
+        self.detailed(|diagnostic| diagnostic.labels.push(Label::secondary(span, message)))
+    }
+    pub fn with_note(self, note: &str) -> BlazeError {
+        self.detailed(|diagnostic| diagnostic.notes.push(note.to_string()))
+    }
+    pub fn with_help(self, help: &str) -> BlazeError {
+        self.detailed(|diagnostic| diagnostic.help.push(help.to_string()))
+    }
+    pub fn with_suggestion(self, span: Span, replacement: &str, message: &str) -> BlazeError {
+        self.detailed(|diagnostic| diagnostic.suggestions.push(Suggestion {
+            span,
+            replacement: replacement.to_string(),
+            message: message.to_string(),
+        }))
+    }
+    fn detailed(self, add: impl FnOnce(&mut Diagnostic)) -> BlazeError {
+        let mut diagnostic: Diagnostic = self.diagnostic();
+        add(&mut diagnostic);
+        let error: BlazeError = match self {
+            BlazeError::Detailed(error, _) => *error,
+            error => error,
+        };
+        BlazeError::Detailed(Box::new(error), Box::new(diagnostic))
+    }
+}
+
+impl std::fmt::Display for BlazeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BlazeError::IOError(error) => writeln!(f, "IOError: {}", error),
+            _ => write!(f, "{}[{}]: {}", self.diagnostic().severity.name(), self.code(), self.message()),
+        }
+    }
 }
\ No newline at end of file
@@ -1,819 +1,1003 @@
-use crate::error::BlazeError;
-use crate::span::Span;
-use crate::token::{Token, TokenKind};
-
-#[derive(Debug, Clone)]
-pub struct Lexer {
-    pub filename: String,
-    pub source: String,
-    pub tokens: Vec<Token>,
-    pub current: usize,
-    pub start: usize,
-    pub end: usize,
-}
-
-impl Lexer {
-    pub fn new(filename: String, source: String) -> Self {
-        Self {
-            filename,
-            source,
-            tokens: Vec::new(),
-            current: 0,
-            start: 0,
-            end: 0,
-        }
-    }
-    pub fn lex(&mut self) -> Result<Vec<Token>, Vec<BlazeError>> {
-        let mut errors: Vec<BlazeError> = Vec::new();
-        while self.current < self.source.len() {
-            match self.current().clone() {
-                ' ' | '\r' | '\t' => {
-                    self.advance();
-                    self.start += 1;
-                    self.end = self.start;
-                }
-                '\n' => {
-                    let start: usize = self.start;
-                    self.advance();
-                    self.start += 1;
-                    self.end = start;
-                    // self.tokens.push(Token {
-                    //     kind: TokenKind::Newline,
-                    //     literal: None,
-                    //     span: Span {
-                    //         filename: self.filename.clone(),
-                    //         start,
-                    //         end: self.end,
-                    //     },
-                    // })
-                }
-                'a'..='z' | 'A'..='Z' | '_' => {
-                    let mut literal: String = String::new();
-                    let start: usize = self.start;
-                    literal.push(self.current());
-                    self.start += 1;
-                    self.advance();
-                    while self.current().is_alphanumeric() || self.current() == '_' {
-                        literal.push(self.current());
-                        self.start += 1;
-                        self.advance();
-                    }
-                    self.end = self.start;
-                    let kind: TokenKind = match literal.clone().as_str() {
-                        "i8" => TokenKind::I8,
-                        "i16" => TokenKind::I16,
-                        "i32" => TokenKind::I32,
-                        "i64" => TokenKind::I64,
-                        "u8" => TokenKind::U8,
-                        "u16" => TokenKind::U16,
-                        "u32" => TokenKind::U32,
-                        "u64" => TokenKind::U64,
-                        "f32" => TokenKind::F32,
-                        "f64" => TokenKind::F64,
-                        "char" => TokenKind::Char,
-                        "bool" => TokenKind::Bool,
-                        "void" => TokenKind::Void,
-                        "type" => TokenKind::Type,
-                        "namespace" => TokenKind::Namespace,
-                        "fn" => TokenKind::Fn,
-                        "return" => TokenKind::Return,
-                        "enum" => TokenKind::Enum,
-                        "union" => TokenKind::Union,
-                        "struct" => TokenKind::Struct,
-                        "self" => TokenKind::SelfKeyword,
-                        "while" => TokenKind::While,
-                        "mut" => TokenKind::Mut,
-                        "if" => TokenKind::If,
-                        "else" => TokenKind::Else,
-                        "import" => TokenKind::Import,
-                        "comptime" => TokenKind::Comptime,
-                        "try" => TokenKind::Try,
-                        "null" => TokenKind::Null,
-                        _ => TokenKind::Identifier,
-                    };
-                    self.tokens.push(Token {
-                        kind,
-                        literal: Some(literal),
-                        span: Span {
-                            filename: self.filename.clone(),
-                            start,
-                            end: self.end,
-                        },
-                    })
-                }
-                '0'..='9' => {
-                    let mut literal: String = String::new();
-                    let start: usize = self.start;
-                    let mut is_float: bool = false;
-                    let mut is_hex: bool = false;
-                    let mut is_bin: bool = false;
-                    if self.current() == '0' {
-                        self.advance();
-                        if self.current() == 'x' {
-                            is_hex = true;
-                            self.advance();
-                            while self.current().is_numeric()
-                                || self.current() == 'a'
-                                || self.current() == 'b'
-                                || self.current() == 'c'
-                                || self.current() == 'd'
-                                || self.current() == 'e'
-                                || self.current() == 'f'
-                            {
-                                literal.push(self.current());
-                                self.start += 1;
-                                self.advance();
-                            }
-                        } else if self.current() == 'b' {
-                            is_bin = true;
-                            self.advance();
-                            while self.current() == '0' || self.current() == '1' {
-                                literal.push(self.current());
-                                self.start += 1;
-                                self.advance();
-                            }
-                        } else {
-                            literal.push('0');
-                            while self.current().is_numeric() {
-                                literal.push(self.current());
-                                self.start += 1;
-                                self.advance();
-                            }
-                        }
-                    } else {
-                        while self.current().is_numeric() {
-                            literal.push(self.current());
-                            self.start += 1;
-                            self.advance();
-                        }
-                    }
-                    if self.current() == '.' {
-                        is_float = true;
-                        literal.push(self.current());
-                        self.start += 1;
-                        self.advance();
-                        while self.current().is_numeric() {
-                            literal.push(self.current());
-                            self.start += 1;
-                            self.advance();
-                        }
-                    }
-                    self.end = self.start;
-                    let kind: TokenKind = if is_float {
-                        TokenKind::FloatLiteral
-                    } else if is_hex {
-                        TokenKind::HexadecimalLiteral
-                    } else if is_bin {
-                        TokenKind::BinaryLiteral
-                    } else {
-                        TokenKind::IntegerLiteral
-                    };
-                    self.tokens.push(Token {
-                        kind,
-                        literal: Some(literal),
-                        span: Span {
-                            filename: self.filename.clone(),
-                            start,
-                            end: self.end,
-                        },
-                    })
-                }
-                '"' => {
-                    let mut literal: String = String::new();
-                    self.advance();
-                    self.start += 1;
-                    let start: usize = self.start;
-                    while self.current() != '"' {
-                        literal.push(self.current());
-                        if self.current() == '\\' {
-                            self.start += 1;
-                            self.advance();
-                            literal.push(self.current());
-                        }
-                        self.start += 1;
-                        self.advance();
-                    }
-                    self.end = self.start;
-                    self.advance();
-                    self.start += 1;
-                    self.tokens.push(Token {
-                        kind: TokenKind::StringLiteral,
-                        literal: Some(literal),
-                        span: Span {
-                            filename: self.filename.clone(),
-                            start,
-                            end: self.end,
-                        },
-                    })
-                }
-                '\'' => {
-                    let mut literal: String = String::new();
-                    self.advance();
-                    self.start += 1;
-                    let start: usize = self.start;
-                    if self.current() == '\\' {
-                        self.start += 1;
-                        self.advance();
-                        match self.current() {
-                            'n' => literal.push('\n'),
-                            'r' => literal.push('\r'),
-                            't' => literal.push('\t'),
-                            '\\' => literal.push('\\'),
-                            '\'' => literal.push('\''),
-                            '"' => literal.push('"'),
-                            '0' => literal.push('\0'),
-                            _ => {
-                                errors.push(BlazeError::SyntaxError(
-                                    format!("invalid escape sequence: '\\{}'", self.current()),
-                                    Span {
-                                        filename: self.filename.clone(),
-                                        start: self.start - 1,
-                                        end: self.end,
-                                    },
-                                ));
-                            }
-                        }
-                    } else {
-                        literal.push(self.current());
-                    }
-                    self.start += 1;
-                    self.advance();
-                    self.end = self.start;
-                    self.advance();
-                    self.start += 1;
-                    self.tokens.push(Token {
-                        kind: TokenKind::CharLiteral,
-                        literal: Some(literal),
-                        span: Span {
-                            filename: self.filename.clone(),
-                            start,
-                            end: self.end,
-                        },
-                    })
-                }
-                '(' => {
-                    let start: usize = self.start;
-                    self.advance();
-                    self.start += 1;
-                    self.end = self.start;
-                    self.tokens.push(Token {
-                        kind: TokenKind::OpenParenthesis,
-                        literal: None,
-                        span: Span {
-                            filename: self.filename.clone(),
-                            start,
-                            end: self.end,
-                        },
-                    })
-                }
-                ')' => {
-                    let start: usize = self.start;
-                    self.advance();
-                    self.start += 1;
-                    self.end = self.start;
-                    self.tokens.push(Token {
-                        kind: TokenKind::CloseParenthesis,
-                        literal: None,
-                        span: Span {
-                            filename: self.filename.clone(),
-                            start,
-                            end: self.end,
-                        },
-                    })
-                }
-                '[' => {
-                    let start: usize = self.start;
-                    self.advance();
-                    self.start += 1;
-                    self.end = self.start;
-                    self.tokens.push(Token {
-                        kind: TokenKind::OpenBracket,
-                        literal: None,
-                        span: Span {
-                            filename: self.filename.clone(),
-                            start,
-                            end: self.end,
-                        },
-                    })
-                }
-                ']' => {
-                    let start: usize = self.start;
-                    self.advance();
-                    self.start += 1;
-                    self.end = self.start;
-                    self.tokens.push(Token {
-                        kind: TokenKind::CloseBracket,
-                        literal: None,
-                        span: Span {
-                            filename: self.filename.clone(),
-                            start,
-                            end: self.end,
-                        },
-                    })
-                }
-                '{' => {
-                    let start: usize = self.start;
-                    self.advance();
-                    self.start += 1;
-                    self.end = self.start;
-                    self.tokens.push(Token {
-                        kind: TokenKind::OpenBrace,
-                        literal: None,
-                        span: Span {
-                            filename: self.filename.clone(),
-                            start,
-                            end: self.end,
-                        },
-                    })
-                }
-                '}' => {
-                    let start: usize = self.start;
-                    self.advance();
-                    self.start += 1;
-                    self.end = self.start;
-                    self.tokens.push(Token {
-                        kind: TokenKind::CloseBrace,
-                        literal: None,
-                        span: Span {
-                            filename: self.filename.clone(),
-                            start,
-                            end: self.end,
-                        },
-                    })
-                }
-                ':' => {
-                    let start: usize = self.start;
-                    self.advance();
-                    self.start += 1;
-                    if self.current() == ':' {
-                        self.advance();
-                        self.start += 1;
-                        self.end = self.start;
-                        self.tokens.push(Token {
-                            kind: TokenKind::DoubleColon,
-                            literal: None,
-                            span: Span {
-                                filename: self.filename.clone(),
-                                start,
-                                end: self.end,
-                            },
-                        })
-                    } else if self.current() == '=' {
-                        self.advance();
-                        self.start += 1;
-                        self.end = self.start;
-                        self.tokens.push(Token {
-                            kind: TokenKind::ColonEquals,
-                            literal: None,
-                            span: Span {
-                                filename: self.filename.clone(),
-                                start,
-                                end: self.end,
-                            },
-                        })
-                    } else {
-                        self.end = self.start;
-                        self.tokens.push(Token {
-                            kind: TokenKind::Colon,
-                            literal: None,
-                            span: Span {
-                                filename: self.filename.clone(),
-                                start,
-                                end: self.end,
-                            },
-                        })
-                    }
-                }
-                ';' => {
-                    let start: usize = self.start;
-                    self.advance();
-                    self.start += 1;
-                    self.end = self.start;
-                    self.tokens.push(Token {
-                        kind: TokenKind::Semicolon,
-                        literal: None,
-                        span: Span {
-                            filename: self.filename.clone(),
-                            start,
-                            end: self.end,
-                        },
-                    })
-                }
-                '.' => {
-                    let start: usize = self.start;
-                    self.advance();
-                    self.start += 1;
-                    if self.current() == '.' {
-                        self.advance();
-                        self.start += 1;
-                        if self.current() == '.' {
-                            self.advance();
-                            self.start += 1;
-                            self.end = self.start;
-                            self.tokens.push(Token {
-                                kind: TokenKind::Elipsis,
-                                literal: None,
-                                span: Span {
-                                    filename: self.filename.clone(),
-                                    start,
-                                    end: self.end,
-                                },
-                            })
-                        } else {
-                            self.end = self.start;
-                            self.tokens.push(Token {
-                                kind: TokenKind::DoubleDot,
-                                literal: None,
-                                span: Span {
-                                    filename: self.filename.clone(),
-                                    start,
-                                    end: self.end,
-                                },
-                            })
-                        }
-                    } else {
-                        self.end = self.start;
-                        self.tokens.push(Token {
-                            kind: TokenKind::Dot,
-                            literal: None,
-                            span: Span {
-                                filename: self.filename.clone(),
-                                start,
-                                end: self.end,
-                            },
-                        })
-                    }
-                }
-                ',' => {
-                    let start: usize = self.start;
-                    self.advance();
-                    self.start += 1;
-                    self.end = self.start;
-                    self.tokens.push(Token {
-                        kind: TokenKind::Comma,
-                        literal: None,
-                        span: Span {
-                            filename: self.filename.clone(),
-                            start,
-                            end: self.end,
-                        },
-                    })
-                }
-                '=' => {
-                    let start: usize = self.start;
-                    self.advance();
-                    self.start += 1;
-                    if self.current() == '=' {
-                        self.advance();
-                        self.start += 1;
-                        self.end = self.start;
-                        self.tokens.push(Token {
-                            kind: TokenKind::EqualEqual,
-                            literal: None,
-                            span: Span {
-                                filename: self.filename.clone(),
-                                start,
-                                end: self.end,
-                            },
-                        })
-                    } else {
-                        self.end = self.start;
-                        self.tokens.push(Token {
-                            kind: TokenKind::Equal,
-                            literal: None,
-                            span: Span {
-                                filename: self.filename.clone(),
-                                start,
-                                end: self.end,
-                            },
-                        })
-                    }
-                }
-                '-' => {
-                    let start: usize = self.start;
-                    self.advance();
-                    self.start += 1;
-                    if self.current() == '>' {
-                        self.advance();
-                        self.start += 1;
-                        self.end = self.start;
-                        self.tokens.push(Token {
-                            kind: TokenKind::Arrow,
-                            literal: None,
-                            span: Span {
-                                filename: self.filename.clone(),
-                                start,
-                                end: self.end,
-                            },
-                        })
-                    } else if self.current() == '=' {
-                        self.advance();
-                        self.start += 1;
-                        self.end = self.start;
-                        self.tokens.push(Token {
-                            kind: TokenKind::MinusEquals,
-                            literal: None,
-                            span: Span {
-                                filename: self.filename.clone(),
-                                start,
-                                end: self.end,
-                            },
-                        })
-                    } else {
-                        self.end = self.start;
-                        self.tokens.push(Token {
-                            kind: TokenKind::Minus,
-                            literal: None,
-                            span: Span {
-                                filename: self.filename.clone(),
-                                start,
-                                end: self.end,
-                            },
-                        })
-                    }
-                }
-                '?' => {
-                    let start: usize = self.start;
-                    self.advance();
-                    self.start += 1;
-                    self.end = self.start;
-                    self.tokens.push(Token {
-                        kind: TokenKind::QuestionMark,
-                        literal: None,
-                        span: Span {
-                            filename: self.filename.clone(),
-                            start,
-                            end: self.end,
-                        },
-                    })
-                }
-                '!' => {
-                    let start: usize = self.start;
-                    self.advance();
-                    self.start += 1;
-                    if self.current() == '=' {
-                        self.advance();
-                        self.start += 1;
-                        self.end = self.start;
-                        self.tokens.push(Token {
-                            kind: TokenKind::BangEqual,
-                            literal: None,
-                            span: Span {
-                                filename: self.filename.clone(),
-                                start,
-                                end: self.end,
-                            },
-                        })
-                    } else {
-                        self.end = self.start;
-                        self.tokens.push(Token {
-                            kind: TokenKind::Bang,
-                            literal: None,
-                            span: Span {
-                                filename: self.filename.clone(),
-                                start,
-                                end: self.end,
-                            },
-                        })
-                    }
-                }
-                '>' => {
-                    let start: usize = self.start;
-                    self.advance();
-                    self.start += 1;
-                    if self.current() == '=' {
-                        self.advance();
-                        self.start += 1;
-                        self.end = self.start;
-                        self.tokens.push(Token {
-                            kind: TokenKind::GreaterEqual,
-                            literal: None,
-                            span: Span {
-                                filename: self.filename.clone(),
-                                start,
-                                end: self.end,
-                            },
-                        })
-                    } else {
-                        self.end = self.start;
-                        self.tokens.push(Token {
-                            kind: TokenKind::Greater,
-                            literal: None,
-                            span: Span {
-                                filename: self.filename.clone(),
-                                start,
-                                end: self.end,
-                            },
-                        })
-                    }
-                }
-                '<' => {
-                    let start: usize = self.start;
-                    self.advance();
-                    self.start += 1;
-                    if self.current() == '=' {
-                        self.advance();
-                        self.start += 1;
-                        self.end = self.start;
-                        self.tokens.push(Token {
-                            kind: TokenKind::LessEqual,
-                            literal: None,
-                            span: Span {
-                                filename: self.filename.clone(),
-                                start,
-                                end: self.end,
-                            },
-                        })
-                    } else {
-                        self.end = self.start;
-                        self.tokens.push(Token {
-                            kind: TokenKind::Less,
-                            literal: None,
-                            span: Span {
-                                filename: self.filename.clone(),
-                                start,
-                                end: self.end,
-                            },
-                        })
-                    }
-                }
-                '&' => {
-                    let start: usize = self.start;
-                    self.advance();
-                    self.start += 1;
-                    self.end = self.start;
-                    self.tokens.push(Token {
-                        kind: TokenKind::Ampersand,
-                        literal: None,
-                        span: Span {
-                            filename: self.filename.clone(),
-                            start,
-                            end: self.end,
-                        },
-                    })
-                }
-                '$' => {
-                    let start: usize = self.start;
-                    self.advance();
-                    self.start += 1;
-                    self.end = self.start;
-                    self.tokens.push(Token {
-                        kind: TokenKind::Dollar,
-                        literal: None,
-                        span: Span {
-                            filename: self.filename.clone(),
-                            start,
-                            end: self.end,
-                        },
-                    })
-                }
-                '+' => {
-                    let start: usize = self.start;
-                    self.advance();
-                    self.start += 1;
-                    if self.current() == '=' {
-                        self.advance();
-                        self.start += 1;
-                        self.end = self.start;
-                        self.tokens.push(Token {
-                            kind: TokenKind::PlusEquals,
-                            literal: None,
-                            span: Span {
-                                filename: self.filename.clone(),
-                                start,
-                                end: self.end,
-                            },
-                        })
-                    } else {
-                        self.end = self.start;
-                        self.tokens.push(Token {
-                            kind: TokenKind::Plus,
-                            literal: None,
-                            span: Span {
-                                filename: self.filename.clone(),
-                                start,
-                                end: self.end,
-                            },
-                        })
-                    }
-                }
-                '*' => {
-                    let start: usize = self.start;
-                    self.advance();
-                    self.start += 1;
-                    if self.current() == '=' {
-                        self.advance();
-                        self.start += 1;
-                        self.end = self.start;
-                        self.tokens.push(Token {
-                            kind: TokenKind::AsteriskEquals,
-                            literal: None,
-                            span: Span {
-                                filename: self.filename.clone(),
-                                start,
-                                end: self.end,
-                            },
-                        })
-                    } else {
-                        self.end = self.start;
-                        self.tokens.push(Token {
-                            kind: TokenKind::Asterisk,
-                            literal: None,
-                            span: Span {
-                                filename: self.filename.clone(),
-                                start,
-                                end: self.end,
-                            },
-                        })
-                    }
-                }
-                '/' => {
-                    let start: usize = self.start;
-                    self.advance();
-                    self.start += 1;
-                    if self.current() == '=' {
-                        self.advance();
-                        self.start += 1;
-                        self.end = self.start;
-                        self.tokens.push(Token {
-                            kind: TokenKind::SlashEquals,
-                            literal: None,
-                            span: Span {
-                                filename: self.filename.clone(),
-                                start,
-                                end: self.end,
-                            },
-                        })
-                    } else {
-                        self.end = self.start;
-                        self.tokens.push(Token {
-                            kind: TokenKind::Slash,
-                            literal: None,
-                            span: Span {
-                                filename: self.filename.clone(),
-                                start,
-                                end: self.end,
-                            },
-                        })
-                    }
-                }
-                '%' => {
-                    let start: usize = self.start;
-                    self.advance();
-                    self.start += 1;
-                    if self.current() == '=' {
-                        self.advance();
-                        self.start += 1;
-                        self.end = self.start;
-                        self.tokens.push(Token {
-                            kind: TokenKind::PercentEquals,
-                            literal: None,
-                            span: Span {
-                                filename: self.filename.clone(),
-                                start,
-                                end: self.end,
-                            },
-                        })
-                    } else {
-                        self.end = self.start;
-                        self.tokens.push(Token {
-                            kind: TokenKind::Percent,
-                            literal: None,
-                            span: Span {
-                                filename: self.filename.clone(),
-                                start,
-                                end: self.end,
-                            },
-                        })
-                    }
-                }
-                _ => {
-                    let c: char = self.current();
-                    self.advance();
-                    self.start += 1;
-                    self.end = self.start;
-                    errors.push(BlazeError::SyntaxError(
-                        format!("Unexpected character: '{}'", c),
-                        Span {
-                            filename: self.filename.clone(),
-                            start: self.start - 1,
-                            end: self.end,
-                        },
-                    ));
-                }
-            }
-        }
-        if errors.len() > 0 {
-            Err(errors)
-        } else {
-            Ok(self.tokens.clone())
-        }
-    }
-    fn current(&self) -> char {
-        self.source.chars().nth(self.current).unwrap()
-    }
-    fn advance(&mut self) -> char {
-        self.current += 1;
-        self.source.chars().nth(self.current - 1).unwrap()
-    }
-}
+use crate::error::{BlazeError, ErrorCode};
+use crate::span::{FileId, Span};
+use crate::token::{Token, TokenKind};
+
+#[derive(Debug, Clone)]
+pub struct Lexer {
+    pub file: FileId,
+    pub source: String,
+    pub tokens: Vec<Token>,
+    pub current: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Lexer {
+    pub fn new(file: FileId, source: String) -> Self {
+        Self {
+            file,
+            source,
+            tokens: Vec::new(),
+            current: 0,
+            start: 0,
+            end: 0,
+        }
+    }
+    pub fn lex(&mut self) -> Result<Vec<Token>, Vec<BlazeError>> {
+        let mut errors: Vec<BlazeError> = Vec::new();
+        while !self.is_at_end() {
+            match self.current() {
+                ' ' | '\r' | '\t' => {
+                    self.advance();
+                    self.end = self.start;
+                }
+                '\n' => {
+                    let start: usize = self.start;
+                    self.advance();
+                    self.end = start;
+                    // self.tokens.push(Token {
+                    //     kind: TokenKind::Newline,
+                    //     literal: None,
+                    //     span: Span {
+                    //         file: self.file,
+                    //         start,
+                    //         end: self.end,
+                    //     },
+                    // })
+                }
+                // Raw strings, like `r"C:\path"` or `r#"say "hi""#`, keep their contents as written, escapes and newlines included.
+                'r' if self.raw_hashes().is_some() => {
+                    let quote: usize = self.start;
+                    let hashes: usize = self.raw_hashes().unwrap();
+                    for _ in 0..hashes + 2 {
+                        self.advance();
+                    }
+                    let start: usize = self.start;
+                    let closing: String = format!("\"{}", "#".repeat(hashes));
+                    let length: usize = match self.source[self.current..].find(closing.as_str()) {
+                        Some(length) => length,
+                        None => {
+                            errors.push(BlazeError::SyntaxError(
+                                ErrorCode::UnterminatedString,
+                                "unterminated raw string literal".to_string(),
+                                Span {
+                                    file: self.file,
+                                    start: quote,
+                                    end: self.source.len(),
+                                },
+                            ));
+                            self.source.len() - self.current
+                        }
+                    };
+                    let literal: String = self.source[start..start + length].replace("\r\n", "\n");
+                    self.current = start + length;
+                    self.start = self.current;
+                    self.end = self.start;
+                    if !self.is_at_end() {
+                        self.current += closing.len();
+                        self.start = self.current;
+                    }
+                    self.tokens.push(Token {
+                        kind: TokenKind::StringLiteral,
+                        literal: Some(literal),
+                        span: Span {
+                            file: self.file,
+                            start,
+                            end: self.end,
+                        },
+                    })
+                }
+                c if c.is_alphabetic() || c == '_' => {
+                    let mut literal: String = String::new();
+                    let start: usize = self.start;
+                    literal.push(self.advance());
+                    while self.current().is_alphanumeric() || self.current() == '_' {
+                        literal.push(self.advance());
+                    }
+                    self.end = self.start;
+                    let kind: TokenKind = match literal.clone().as_str() {
+                        "i8" => TokenKind::I8,
+                        "i16" => TokenKind::I16,
+                        "i32" => TokenKind::I32,
+                        "i64" => TokenKind::I64,
+                        "u8" => TokenKind::U8,
+                        "u16" => TokenKind::U16,
+                        "u32" => TokenKind::U32,
+                        "u64" => TokenKind::U64,
+                        "f32" => TokenKind::F32,
+                        "f64" => TokenKind::F64,
+                        "char" => TokenKind::Char,
+                        "str" => TokenKind::Str,
+                        "bool" => TokenKind::Bool,
+                        "void" => TokenKind::Void,
+                        "type" => TokenKind::Type,
+                        "namespace" => TokenKind::Namespace,
+                        "fn" => TokenKind::Fn,
+                        "return" => TokenKind::Return,
+                        "enum" => TokenKind::Enum,
+                        "error" => TokenKind::Error,
+                        "union" => TokenKind::Union,
+                        "struct" => TokenKind::Struct,
+                        "self" => TokenKind::SelfKeyword,
+                        "while" => TokenKind::While,
+                        "for" => TokenKind::For,
+                        "in" => TokenKind::In,
+                        "break" => TokenKind::Break,
+                        "continue" => TokenKind::Continue,
+                        "defer" => TokenKind::Defer,
+                        "match" => TokenKind::Match,
+                        "mut" => TokenKind::Mut,
+                        "if" => TokenKind::If,
+                        "else" => TokenKind::Else,
+                        "import" => TokenKind::Import,
+                        "comptime" => TokenKind::Comptime,
+                        "try" => TokenKind::Try,
+                        "catch" => TokenKind::Catch,
+                        "orelse" => TokenKind::Orelse,
+                        "null" => TokenKind::Null,
+                        "true" => TokenKind::True,
+                        "false" => TokenKind::False,
+                        _ => TokenKind::Identifier,
+                    };
+                    self.tokens.push(Token {
+                        kind,
+                        literal: Some(literal),
+                        span: Span {
+                            file: self.file,
+                            start,
+                            end: self.end,
+                        },
+                    })
+                }
+                '0'..='9' => {
+                    let mut literal: String = String::new();
+                    let start: usize = self.start;
+                    let mut kind: TokenKind = TokenKind::IntegerLiteral;
+                    let mut radix: u32 = 10;
+                    if self.next_is('0') {
+                        (kind, radix) = match self.peek(1) {
+                            Some('x') | Some('X') => (TokenKind::HexadecimalLiteral, 16),
+                            Some('b') | Some('B') => (TokenKind::BinaryLiteral, 2),
+                            Some('o') | Some('O') => (TokenKind::OctalLiteral, 8),
+                            _ => (kind, radix),
+                        };
+                        if radix != 10 {
+                            self.advance();
+                            self.advance();
+                        }
+                    }
+                    self.digits(&mut literal, radix);
+                    if radix == 10 && self.next_is('.') && self.peek(1).is_some_and(|c| c.is_ascii_digit()) {
+                        kind = TokenKind::FloatLiteral;
+                        literal.push(self.advance());
+                        self.digits(&mut literal, radix);
+                    }
+                    if radix == 10 && (self.next_is('e') || self.next_is('E')) {
+                        let mut exponent: std::iter::Skip<std::str::Chars> = self.source[self.current..].chars().skip(1);
+                        let has_digits: bool = match exponent.next() {
+                            Some('+') | Some('-') => exponent.next().is_some_and(|c| c.is_ascii_digit()),
+                            c => c.is_some_and(|c| c.is_ascii_digit()),
+                        };
+                        if has_digits {
+                            kind = TokenKind::FloatLiteral;
+                            literal.push(self.advance());
+                            if self.next_is('+') || self.next_is('-') {
+                                literal.push(self.advance());
+                            }
+                            self.digits(&mut literal, radix);
+                        }
+                    }
+                    while !self.is_at_end() && (self.current().is_alphanumeric() || self.current() == '_') {
+                        literal.push(self.advance());
+                    }
+                    self.end = self.start;
+                    self.tokens.push(Token {
+                        kind,
+                        literal: Some(literal),
+                        span: Span {
+                            file: self.file,
+                            start,
+                            end: self.end,
+                        },
+                    })
+                }
+                '"' => {
+                    let mut literal: String = String::new();
+                    let quote: usize = self.start;
+                    self.advance();
+                    let start: usize = self.start;
+                    let mut terminated: bool = false;
+                    while !self.is_at_end() {
+                        if self.current() == '"' {
+                            terminated = true;
+                            break;
+                        }
+                        if self.current() == '\r' && self.peek(1) == Some('\n') {
+                            self.advance();
+                            continue;
+                        }
+                        if self.current() != '\\' {
+                            literal.push(self.advance());
+                            continue;
+                        }
+                        let escape: usize = self.start;
+                        self.advance();
+                        match self.current() {
+                            _ if self.is_at_end() => {}
+                            // A backslash at the end of a line continues the string after the next line's indentation.
+                            '\r' | '\n' => {
+                                while !self.is_at_end() && self.current().is_whitespace() {
+                                    self.advance();
+                                }
+                            }
+                            'u' => {
+                                self.advance();
+                                match self.unicode_escape(escape) {
+                                    Ok(c) => literal.push(c),
+                                    Err(error) => errors.push(error),
+                                }
+                            }
+                            c => {
+                                match unescape(c) {
+                                    Some(c) => literal.push(c),
+                                    None => errors.push(self.invalid_escape(escape)),
+                                }
+                                self.advance();
+                            }
+                        }
+                    }
+                    self.end = self.start;
+                    if terminated {
+                        self.advance();
+                    } else {
+                        errors.push(BlazeError::SyntaxError(
+                            ErrorCode::UnterminatedString,
+                            "unterminated string literal".to_string(),
+                            Span {
+                                file: self.file,
+                                start: quote,
+                                end: self.end,
+                            },
+                        ));
+                    }
+                    self.tokens.push(Token {
+                        kind: TokenKind::StringLiteral,
+                        literal: Some(literal),
+                        span: Span {
+                            file: self.file,
+                            start,
+                            end: self.end,
+                        },
+                    })
+                }
+                '\'' => {
+                    let quote: usize = self.start;
+                    self.advance();
+                    let start: usize = self.start;
+                    let mut literal: String = String::new();
+                    if self.eat('\'') {
+                        self.end = start;
+                        errors.push(BlazeError::SyntaxError(
+                            ErrorCode::InvalidCharacterLiteral,
+                            "empty character literal".to_string(),
+                            Span {
+                                file: self.file,
+                                start: quote,
+                                end: self.start,
+                            },
+                        ));
+                    } else {
+                        if self.next_is('\\') {
+                            let escape: usize = self.start;
+                            self.advance();
+                            match unescape(self.current()) {
+                                Some(c) => literal.push(c),
+                                None if self.is_at_end() || self.current() == '\n' => {}
+                                None => errors.push(self.invalid_escape(escape)),
+                            }
+                        }
+                        if literal.is_empty() && !self.is_at_end() && self.current() != '\n' {
+                            literal.push(self.current());
+                        }
+                        if !self.is_at_end() && self.current() != '\n' {
+                            self.advance();
+                        }
+                        self.end = self.start;
+                        if !self.eat('\'') {
+                            let message: &str = match self.source[self.current..].chars().take_while(|c| *c != '\n').position(|c| c == '\'') {
+                                Some(offset) => {
+                                    for _ in 0..=offset {
+                                        self.advance();
+                                    }
+                                    "character literal may only contain one character"
+                                }
+                                None => "unterminated character literal",
+                            };
+                            errors.push(BlazeError::SyntaxError(
+                                ErrorCode::InvalidCharacterLiteral,
+                                message.to_string(),
+                                Span {
+                                    file: self.file,
+                                    start: quote,
+                                    end: self.start,
+                                },
+                            ));
+                        } else if !literal.is_ascii() {
+                            // A 'char' is a single byte, which only holds ASCII characters.
+                            errors.push(BlazeError::SyntaxError(
+                                ErrorCode::InvalidCharacterLiteral,
+                                "character literal must be ASCII".to_string(),
+                                Span {
+                                    file: self.file,
+                                    start: quote,
+                                    end: self.start,
+                                },
+                            ));
+                        }
+                    }
+                    self.tokens.push(Token {
+                        kind: TokenKind::CharLiteral,
+                        literal: Some(literal),
+                        span: Span {
+                            file: self.file,
+                            start,
+                            end: self.end,
+                        },
+                    })
+                }
+                '(' => {
+                    let start: usize = self.start;
+                    self.advance();
+                    self.end = self.start;
+                    self.tokens.push(Token {
+                        kind: TokenKind::OpenParenthesis,
+                        literal: None,
+                        span: Span {
+                            file: self.file,
+                            start,
+                            end: self.end,
+                        },
+                    })
+                }
+                ')' => {
+                    let start: usize = self.start;
+                    self.advance();
+                    self.end = self.start;
+                    self.tokens.push(Token {
+                        kind: TokenKind::CloseParenthesis,
+                        literal: None,
+                        span: Span {
+                            file: self.file,
+                            start,
+                            end: self.end,
+                        },
+                    })
+                }
+                '[' => {
+                    let start: usize = self.start;
+                    self.advance();
+                    self.end = self.start;
+                    self.tokens.push(Token {
+                        kind: TokenKind::OpenBracket,
+                        literal: None,
+                        span: Span {
+                            file: self.file,
+                            start,
+                            end: self.end,
+                        },
+                    })
+                }
+                ']' => {
+                    let start: usize = self.start;
+                    self.advance();
+                    self.end = self.start;
+                    self.tokens.push(Token {
+                        kind: TokenKind::CloseBracket,
+                        literal: None,
+                        span: Span {
+                            file: self.file,
+                            start,
+                            end: self.end,
+                        },
+                    })
+                }
+                '{' => {
+                    let start: usize = self.start;
+                    self.advance();
+                    self.end = self.start;
+                    self.tokens.push(Token {
+                        kind: TokenKind::OpenBrace,
+                        literal: None,
+                        span: Span {
+                            file: self.file,
+                            start,
+                            end: self.end,
+                        },
+                    })
+                }
+                '}' => {
+                    let start: usize = self.start;
+                    self.advance();
+                    self.end = self.start;
+                    self.tokens.push(Token {
+                        kind: TokenKind::CloseBrace,
+                        literal: None,
+                        span: Span {
+                            file: self.file,
+                            start,
+                            end: self.end,
+                        },
+                    })
+                }
+                ':' => {
+                    let start: usize = self.start;
+                    self.advance();
+                    if self.current() == ':' {
+                        self.advance();
+                        self.end = self.start;
+                        self.tokens.push(Token {
+                            kind: TokenKind::DoubleColon,
+                            literal: None,
+                            span: Span {
+                                file: self.file,
+                                start,
+                                end: self.end,
+                            },
+                        })
+                    } else if self.current() == '=' {
+                        self.advance();
+                        self.end = self.start;
+                        self.tokens.push(Token {
+                            kind: TokenKind::ColonEquals,
+                            literal: None,
+                            span: Span {
+                                file: self.file,
+                                start,
+                                end: self.end,
+                            },
+                        })
+                    } else {
+                        self.end = self.start;
+                        self.tokens.push(Token {
+                            kind: TokenKind::Colon,
+                            literal: None,
+                            span: Span {
+                                file: self.file,
+                                start,
+                                end: self.end,
+                            },
+                        })
+                    }
+                }
+                ';' => {
+                    let start: usize = self.start;
+                    self.advance();
+                    self.end = self.start;
+                    self.tokens.push(Token {
+                        kind: TokenKind::Semicolon,
+                        literal: None,
+                        span: Span {
+                            file: self.file,
+                            start,
+                            end: self.end,
+                        },
+                    })
+                }
+                '.' => {
+                    let start: usize = self.start;
+                    self.advance();
+                    if self.current() == '.' {
+                        self.advance();
+                        if self.current() == '.' {
+                            self.advance();
+                            self.end = self.start;
+                            self.tokens.push(Token {
+                                kind: TokenKind::Elipsis,
+                                literal: None,
+                                span: Span {
+                                    file: self.file,
+                                    start,
+                                    end: self.end,
+                                },
+                            })
+                        } else {
+                            self.end = self.start;
+                            self.tokens.push(Token {
+                                kind: TokenKind::DoubleDot,
+                                literal: None,
+                                span: Span {
+                                    file: self.file,
+                                    start,
+                                    end: self.end,
+                                },
+                            })
+                        }
+                    } else {
+                        self.end = self.start;
+                        self.tokens.push(Token {
+                            kind: TokenKind::Dot,
+                            literal: None,
+                            span: Span {
+                                file: self.file,
+                                start,
+                                end: self.end,
+                            },
+                        })
+                    }
+                }
+                ',' => {
+                    let start: usize = self.start;
+                    self.advance();
+                    self.end = self.start;
+                    self.tokens.push(Token {
+                        kind: TokenKind::Comma,
+                        literal: None,
+                        span: Span {
+                            file: self.file,
+                            start,
+                            end: self.end,
+                        },
+                    })
+                }
+                '=' => {
+                    let start: usize = self.start;
+                    self.advance();
+                    if self.current() == '=' {
+                        self.advance();
+                        self.end = self.start;
+                        self.tokens.push(Token {
+                            kind: TokenKind::EqualEqual,
+                            literal: None,
+                            span: Span {
+                                file: self.file,
+                                start,
+                                end: self.end,
+                            },
+                        })
+                    } else if self.current() == '>' {
+                        self.advance();
+                        self.end = self.start;
+                        self.tokens.push(Token {
+                            kind: TokenKind::FatArrow,
+                            literal: None,
+                            span: Span {
+                                file: self.file,
+                                start,
+                                end: self.end,
+                            },
+                        })
+                    } else {
+                        self.end = self.start;
+                        self.tokens.push(Token {
+                            kind: TokenKind::Equal,
+                            literal: None,
+                            span: Span {
+                                file: self.file,
+                                start,
+                                end: self.end,
+                            },
+                        })
+                    }
+                }
+                '-' => {
+                    let start: usize = self.start;
+                    self.advance();
+                    if self.current() == '>' {
+                        self.advance();
+                        self.end = self.start;
+                        self.tokens.push(Token {
+                            kind: TokenKind::Arrow,
+                            literal: None,
+                            span: Span {
+                                file: self.file,
+                                start,
+                                end: self.end,
+                            },
+                        })
+                    } else if self.current() == '=' {
+                        self.advance();
+                        self.end = self.start;
+                        self.tokens.push(Token {
+                            kind: TokenKind::MinusEquals,
+                            literal: None,
+                            span: Span {
+                                file: self.file,
+                                start,
+                                end: self.end,
+                            },
+                        })
+                    } else {
+                        self.end = self.start;
+                        self.tokens.push(Token {
+                            kind: TokenKind::Minus,
+                            literal: None,
+                            span: Span {
+                                file: self.file,
+                                start,
+                                end: self.end,
+                            },
+                        })
+                    }
+                }
+                '?' => {
+                    let start: usize = self.start;
+                    self.advance();
+                    self.end = self.start;
+                    self.tokens.push(Token {
+                        kind: TokenKind::QuestionMark,
+                        literal: None,
+                        span: Span {
+                            file: self.file,
+                            start,
+                            end: self.end,
+                        },
+                    })
+                }
+                '!' => {
+                    let start: usize = self.start;
+                    self.advance();
+                    if self.current() == '=' {
+                        self.advance();
+                        self.end = self.start;
+                        self.tokens.push(Token {
+                            kind: TokenKind::BangEqual,
+                            literal: None,
+                            span: Span {
+                                file: self.file,
+                                start,
+                                end: self.end,
+                            },
+                        })
+                    } else {
+                        self.end = self.start;
+                        self.tokens.push(Token {
+                            kind: TokenKind::Bang,
+                            literal: None,
+                            span: Span {
+                                file: self.file,
+                                start,
+                                end: self.end,
+                            },
+                        })
+                    }
+                }
+                '>' => {
+                    let start: usize = self.start;
+                    self.advance();
+                    if self.eat('>') {
+                        let kind: TokenKind = if self.eat('=') { TokenKind::GreaterGreaterEquals } else { TokenKind::GreaterGreater };
+                        self.push_operator(kind, start);
+                    } else if self.eat('=') {
+                        self.push_operator(TokenKind::GreaterEqual, start);
+                    } else {
+                        self.push_operator(TokenKind::Greater, start);
+                    }
+                }
+                '<' => {
+                    let start: usize = self.start;
+                    self.advance();
+                    if self.eat('<') {
+                        let kind: TokenKind = if self.eat('=') { TokenKind::LessLessEquals } else { TokenKind::LessLess };
+                        self.push_operator(kind, start);
+                    } else if self.eat('=') {
+                        self.push_operator(TokenKind::LessEqual, start);
+                    } else {
+                        self.push_operator(TokenKind::Less, start);
+                    }
+                }
+                '&' => {
+                    let start: usize = self.start;
+                    self.advance();
+                    if self.eat('&') {
+                        self.push_operator(TokenKind::AmpersandAmpersand, start);
+                    } else if self.eat('=') {
+                        self.push_operator(TokenKind::AmpersandEquals, start);
+                    } else {
+                        self.push_operator(TokenKind::Ampersand, start);
+                    }
+                }
+                '|' => {
+                    let start: usize = self.start;
+                    self.advance();
+                    if self.eat('|') {
+                        self.push_operator(TokenKind::PipePipe, start);
+                    } else if self.eat('=') {
+                        self.push_operator(TokenKind::PipeEquals, start);
+                    } else {
+                        self.push_operator(TokenKind::Pipe, start);
+                    }
+                }
+                '^' => {
+                    let start: usize = self.start;
+                    self.advance();
+                    if self.eat('=') {
+                        self.push_operator(TokenKind::CaretEquals, start);
+                    } else {
+                        self.push_operator(TokenKind::Caret, start);
+                    }
+                }
+                '~' => {
+                    let start: usize = self.start;
+                    self.advance();
+                    self.push_operator(TokenKind::Tilde, start);
+                }
+                '#' => {
+                    let start: usize = self.start;
+                    self.advance();
+                    self.push_operator(TokenKind::Hash, start);
+                }
+                '$' => {
+                    let start: usize = self.start;
+                    self.advance();
+                    self.end = self.start;
+                    self.tokens.push(Token {
+                        kind: TokenKind::Dollar,
+                        literal: None,
+                        span: Span {
+                            file: self.file,
+                            start,
+                            end: self.end,
+                        },
+                    })
+                }
+                '+' => {
+                    let start: usize = self.start;
+                    self.advance();
+                    if self.current() == '=' {
+                        self.advance();
+                        self.end = self.start;
+                        self.tokens.push(Token {
+                            kind: TokenKind::PlusEquals,
+                            literal: None,
+                            span: Span {
+                                file: self.file,
+                                start,
+                                end: self.end,
+                            },
+                        })
+                    } else {
+                        self.end = self.start;
+                        self.tokens.push(Token {
+                            kind: TokenKind::Plus,
+                            literal: None,
+                            span: Span {
+                                file: self.file,
+                                start,
+                                end: self.end,
+                            },
+                        })
+                    }
+                }
+                '*' => {
+                    let start: usize = self.start;
+                    self.advance();
+                    if self.current() == '=' {
+                        self.advance();
+                        self.end = self.start;
+                        self.tokens.push(Token {
+                            kind: TokenKind::AsteriskEquals,
+                            literal: None,
+                            span: Span {
+                                file: self.file,
+                                start,
+                                end: self.end,
+                            },
+                        })
+                    } else {
+                        self.end = self.start;
+                        self.tokens.push(Token {
+                            kind: TokenKind::Asterisk,
+                            literal: None,
+                            span: Span {
+                                file: self.file,
+                                start,
+                                end: self.end,
+                            },
+                        })
+                    }
+                }
+                '/' => {
+                    let start: usize = self.start;
+                    self.advance();
+                    if self.eat('/') {
+                        let is_doc: bool = self.eat('/') && !self.next_is('/');
+                        let mut text: String = String::new();
+                        while !self.is_at_end() && self.current() != '\n' {
+                            text.push(self.advance());
+                        }
+                        self.end = self.start;
+                        if is_doc {
+                            let text: &str = text.strip_suffix('\r').unwrap_or(&text);
+                            self.tokens.push(Token {
+                                kind: TokenKind::DocComment,
+                                literal: Some(text.strip_prefix(' ').unwrap_or(text).to_string()),
+                                span: Span {
+                                    file: self.file,
+                                    start,
+                                    end: self.end,
+                                },
+                            })
+                        }
+                    } else if self.eat('*') {
+                        let mut depth: usize = 1;
+                        while depth > 0 {
+                            if self.is_at_end() {
+                                errors.push(BlazeError::SyntaxError(
+                                    ErrorCode::UnterminatedComment,
+                                    "unterminated block comment".to_string(),
+                                    Span {
+                                        file: self.file,
+                                        start,
+                                        end: start + 2,
+                                    },
+                                ));
+                                break;
+                            }
+                            if self.eat('/') {
+                                if self.eat('*') {
+                                    depth += 1;
+                                }
+                            } else if self.eat('*') {
+                                if self.eat('/') {
+                                    depth -= 1;
+                                }
+                            } else {
+                                self.advance();
+                            }
+                        }
+                        self.end = self.start;
+                    } else if self.eat('=') {
+                        self.push_operator(TokenKind::SlashEquals, start);
+                    } else {
+                        self.push_operator(TokenKind::Slash, start);
+                    }
+                }
+                '%' => {
+                    let start: usize = self.start;
+                    self.advance();
+                    if self.current() == '=' {
+                        self.advance();
+                        self.end = self.start;
+                        self.tokens.push(Token {
+                            kind: TokenKind::PercentEquals,
+                            literal: None,
+                            span: Span {
+                                file: self.file,
+                                start,
+                                end: self.end,
+                            },
+                        })
+                    } else {
+                        self.end = self.start;
+                        self.tokens.push(Token {
+                            kind: TokenKind::Percent,
+                            literal: None,
+                            span: Span {
+                                file: self.file,
+                                start,
+                                end: self.end,
+                            },
+                        })
+                    }
+                }
+                _ => {
+                    let start: usize = self.start;
+                    let c: char = self.advance();
+                    self.end = self.start;
+                    errors.push(BlazeError::SyntaxError(
+                        ErrorCode::UnexpectedCharacter,
+                        format!("Unexpected character: '{}'", c),
+                        Span {
+                            file: self.file,
+                            start,
+                            end: self.end,
+                        },
+                    ));
+                }
+            }
+        }
+        if !errors.is_empty() {
+            Err(errors)
+        } else {
+            Ok(self.tokens.clone())
+        }
+    }
+    fn digits(&mut self, literal: &mut String, radix: u32) {
+        while !self.is_at_end() && (self.current().is_digit(radix) || self.current() == '_') {
+            let c: char = self.advance();
+            if c != '_' {
+                literal.push(c);
+            }
+        }
+    }
+    fn next_is(&self, c: char) -> bool {
+        !self.is_at_end() && self.current() == c
+    }
+    fn eat(&mut self, c: char) -> bool {
+        if self.next_is(c) {
+            self.advance();
+            true
+        } else {
+            false
+        }
+    }
+    fn push_operator(&mut self, kind: TokenKind, start: usize) {
+        self.end = self.start;
+        self.tokens.push(Token {
+            kind,
+            literal: None,
+            span: Span {
+                file: self.file,
+                start,
+                end: self.end,
+            },
+        })
+    }
+    fn invalid_escape(&self, start: usize) -> BlazeError {
+        BlazeError::SyntaxError(
+            ErrorCode::InvalidEscape,
+            format!("invalid escape sequence: '\\{}'", self.current()),
+            Span {
+                file: self.file,
+                start,
+                end: start + 1 + self.current().len_utf8(),
+            },
+        )
+    }
+    // Reads the `{...}` of a `\u{...}` escape that starts at `start`, 1 to 6 hexadecimal digits of a code point.
+    fn unicode_escape(&mut self, start: usize) -> Result<char, BlazeError> {
+        let mut digits: String = String::new();
+        let braced: bool = self.eat('{');
+        while braced && !self.is_at_end() && self.current().is_ascii_hexdigit() {
+            digits.push(self.advance());
+        }
+        let closed: bool = braced && self.eat('}');
+        u32::from_str_radix(&digits, 16).ok()
+            .filter(|_| closed && digits.len() <= 6)
+            .and_then(char::from_u32)
+            .ok_or_else(|| BlazeError::SyntaxError(
+                ErrorCode::InvalidEscape,
+                format!("invalid unicode escape: '{}'", &self.source[start..self.current]),
+                Span {
+                    file: self.file,
+                    start,
+                    end: self.current,
+                },
+            ).with_help("a unicode escape is the hexadecimal code point of a character in braces, like '\\u{1F525}'"))
+    }
+    // The number of `#`s of a raw string starting at the current `r`, if it is one.
+    fn raw_hashes(&self) -> Option<usize> {
+        let hashes: usize = self.source[self.current + 1..].chars().take_while(|c| *c == '#').count();
+        (self.peek(hashes + 1) == Some('"')).then_some(hashes)
+    }
+    fn is_at_end(&self) -> bool {
+        self.current >= self.source.len()
+    }
+    fn current(&self) -> char {
+        self.source[self.current..].chars().next().unwrap_or('\0')
+    }
+    fn peek(&self, offset: usize) -> Option<char> {
+        self.source[self.current..].chars().nth(offset)
+    }
+    fn advance(&mut self) -> char {
+        let c: char = self.current();
+        if !self.is_at_end() {
+            self.current += c.len_utf8();
+            self.start = self.current;
+        }
+        c
+    }
+}
+
+fn unescape(c: char) -> Option<char> {
+    match c {
+        'n' => Some('\n'),
+        'r' => Some('\r'),
+        't' => Some('\t'),
+        '\\' => Some('\\'),
+        '\'' => Some('\''),
+        '"' => Some('"'),
+        '0' => Some('\0'),
+        _ => None,
+    }
+}
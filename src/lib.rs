@@ -1,9 +1,13 @@
-pub mod scope;
-pub mod error;
-pub mod span;
-pub mod token;
-pub mod lexer;
-pub mod ast;
-pub mod parser;
-pub mod codegen;
+pub mod scope;
+pub mod error;
+pub mod diagnostic;
+pub mod lint;
+pub mod span;
+pub mod token;
+pub mod lexer;
+pub mod ast;
+pub mod parser;
+pub mod checker;
+pub mod mangle;
+pub mod codegen;
 pub mod compiler;
\ No newline at end of file
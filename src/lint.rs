@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+
+// Lint levels set by attributes, innermost last.
+pub type LintLevels = Vec<(Lint, Level)>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Lint {
+    UnusedVariables,
+    UnusedParameters,
+    UnusedImports,
+    UnreachableCode,
+    Shadowing,
+    UnusedMut,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Level {
+    Allow,
+    Warn,
+    Deny,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Lints {
+    pub levels: HashMap<Lint, Level>,
+}
+
+impl Lint {
+    pub const ALL: [Lint; 6] = [
+        Lint::UnusedVariables,
+        Lint::UnusedParameters,
+        Lint::UnusedImports,
+        Lint::UnreachableCode,
+        Lint::Shadowing,
+        Lint::UnusedMut,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Lint::UnusedVariables => "unused_variables",
+            Lint::UnusedParameters => "unused_parameters",
+            Lint::UnusedImports => "unused_imports",
+            Lint::UnreachableCode => "unreachable_code",
+            Lint::Shadowing => "shadowing",
+            Lint::UnusedMut => "unused_mut",
+        }
+    }
+    pub fn code(&self) -> &'static str {
+        match self {
+            Lint::UnusedVariables => "W0001",
+            Lint::UnusedParameters => "W0002",
+            Lint::UnusedImports => "W0003",
+            Lint::UnreachableCode => "W0004",
+            Lint::Shadowing => "W0005",
+            Lint::UnusedMut => "W0006",
+        }
+    }
+    // Resolves a lint name as written in `-W`/`-A`/`-D` or an attribute; `warnings` means every lint.
+    pub fn parse(name: &str) -> Option<Vec<Lint>> {
+        if name == "warnings" {
+            return Some(Lint::ALL.to_vec());
+        }
+        Lint::ALL.iter().find(|lint| lint.name() == name).map(|lint| vec![*lint])
+    }
+}
+
+impl Level {
+    pub fn parse(name: &str) -> Option<Level> {
+        match name {
+            "allow" => Some(Level::Allow),
+            "warn" => Some(Level::Warn),
+            "deny" => Some(Level::Deny),
+            _ => None,
+        }
+    }
+}
+
+impl Lints {
+    pub fn new() -> Lints {
+        Lints { levels: HashMap::new() }
+    }
+    pub fn set(&mut self, name: &str, level: Level) -> Result<(), String> {
+        let lints: Vec<Lint> = Lint::parse(name).ok_or_else(|| format!("unknown lint '{}'", name))?;
+        for lint in lints {
+            self.levels.insert(lint, level);
+        }
+        Ok(())
+    }
+    pub fn level(&self, lint: Lint) -> Level {
+        self.levels.get(&lint).copied().unwrap_or(Level::Warn)
+    }
+}
\ No newline at end of file
@@ -1,32 +1,306 @@
-use blaze::compiler::Compiler;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use blaze::ast::Statement;
+use blaze::compiler::{create_temp_dir, Compiler};
 use blaze::error::BlazeError;
+use blaze::lint::{Level, Lints};
+use blaze::token::Token;
+
+const USAGE: &str = "usage: blaze <command> [options] <files...> [-- <args...>]
+
+commands:
+    build               compile the files into an executable
+    run                 build, then run the executable with <args...>
+    check               type check the files without generating code
+    emit                print the output of a pipeline stage
+
+options:
+    -o <file>           write the executable to <file>
+    -c                  produce an object file instead of an executable
+    --release           build with optimizations
+    --stage <stage>     stage printed by `emit`: tokens, ast or c (default: c)
+    --cc <compiler>     C compiler to use (default: $BLAZE_CC, $CC, cc, gcc, clang)
+    --std <dir>         standard library root that `std/...` imports and the prelude are loaded from
+                        (default: $BLAZE_STD, or the runtime directory blaze was built with)
+    --error-format <format>
+                        how errors are printed: human or json, one object per line (default: human)
+    -W <lint>           warn about <lint> (the default for every lint)
+    -A <lint>           allow <lint>, silencing it
+    -D <lint>           deny <lint>, turning it into an error
+                        lints: unused_variables, unused_parameters, unused_imports,
+                        unreachable_code, shadowing, unused_mut, or warnings for all of them
+    -h, --help          print this message";
+
+#[derive(Debug, Clone, PartialEq)]
+enum Command {
+    Build,
+    Run,
+    Check,
+    Emit,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Stage {
+    Tokens,
+    Ast,
+    C,
+}
 
-fn main() {
-    let mut args: Vec<String> = std::env::args().collect();
-    args.remove(0);
+#[derive(Debug, Clone, PartialEq)]
+enum ErrorFormat {
+    Human,
+    Json,
+}
 
-    if args.len() == 0 {
-        println!("No input files!");
-        return;
+#[derive(Debug, Clone)]
+struct Options {
+    command: Command,
+    files: Vec<String>,
+    output_file: Option<PathBuf>,
+    object_only: bool,
+    release: bool,
+    stage: Stage,
+    cc: Option<String>,
+    std_root: Option<PathBuf>,
+    error_format: ErrorFormat,
+    lints: Lints,
+    arguments: Vec<String>,
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
     }
 
+    let options: Options = match parse_args(args) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
     let mut compiler: Compiler = Compiler::new();
-    compiler.add_file("runtime/prelude.bl".to_string());
-    for arg in args {
-        compiler.add_file(arg);
+    compiler.std_root = options.std_root.clone();
+    if options.command != Command::Emit || options.stage == Stage::C {
+        compiler.add_file(compiler.std_root().join("prelude.bl").to_string_lossy().to_string());
     }
-    let result: Result<(), BlazeError> = compiler.compile();
+    for file in &options.files {
+        compiler.add_file(file.clone());
+    }
+    compiler.cc = options.cc.clone();
+    compiler.lints = options.lints.clone();
+    compiler.release = options.release;
+    compiler.object_only = options.object_only;
+    compiler.output_file = options.output_file.clone();
+    // `run` builds into a directory of its own, removed once the program exits.
+    let mut run_dir: Option<PathBuf> = None;
+    let result: Result<(), BlazeError> = match options.command {
+        Command::Run if options.output_file.is_none() => create_temp_dir().and_then(|dir| {
+            compiler.output_file = Some(dir.join(executable_name(&options.files)));
+            run_dir = Some(dir);
+            compiler.build()
+        }),
+        Command::Build | Command::Run => compiler.build(),
+        Command::Check => compiler.check().map(|_| ()),
+        Command::Emit => emit(&mut compiler, &options.stage),
+    };
 
     if let Err(error) = result {
-        println!("{}", error.to_string());
+        compiler.errors.push(error);
     }
 
-    if compiler.errors.len() > 0 {
-        println!("{} errors found!", compiler.errors.len());
-        for error in compiler.errors {
-            println!("{}", error.to_string());
+    for error in compiler.warnings.iter().chain(&compiler.errors) {
+        match options.error_format {
+            ErrorFormat::Human => eprintln!("{}", error.render(&compiler.sources)),
+            ErrorFormat::Json => eprintln!("{}", error.diagnostic().to_json(&compiler.sources)),
+        }
+    }
+    if options.error_format == ErrorFormat::Human && !compiler.warnings.is_empty() {
+        eprintln!("{} warnings found!", compiler.warnings.len());
+    }
+    if !compiler.errors.is_empty() {
+        if options.error_format == ErrorFormat::Human {
+            eprintln!("{} errors found!", compiler.errors.len());
+        }
+        remove_run_dir(&run_dir);
+        return ExitCode::FAILURE;
+    }
+
+    match options.command {
+        Command::Build => {
+            if let Some(artifact) = &compiler.artifact {
+                println!("Built {}", artifact.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Command::Run => {
+            let code: ExitCode = run(compiler.artifact.unwrap(), &options.arguments);
+            remove_run_dir(&run_dir);
+            code
+        }
+        _ => ExitCode::SUCCESS,
+    }
+}
+
+fn parse_args(args: Vec<String>) -> Result<Options, String> {
+    let mut args = args.into_iter();
+    let command: Command = match args.next().as_deref() {
+        Some("build") => Command::Build,
+        Some("run") => Command::Run,
+        Some("check") => Command::Check,
+        Some("emit") => Command::Emit,
+        Some(command) => return Err(format!("unknown command '{}'", command)),
+        None => return Err("no command given".to_string()),
+    };
+    let mut options: Options = Options {
+        command,
+        files: Vec::new(),
+        output_file: None,
+        object_only: false,
+        release: false,
+        stage: Stage::C,
+        cc: None,
+        std_root: None,
+        error_format: ErrorFormat::Human,
+        lints: Lints::new(),
+        arguments: Vec::new(),
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-o" => options.output_file = Some(PathBuf::from(args.next().ok_or("expected a file after '-o'")?)),
+            "-c" => options.object_only = true,
+            "--release" => options.release = true,
+            "--cc" => options.cc = Some(args.next().ok_or("expected a compiler after '--cc'")?),
+            "--std" => options.std_root = Some(PathBuf::from(args.next().ok_or("expected a directory after '--std'")?)),
+            "--stage" => {
+                options.stage = match args.next().as_deref() {
+                    Some("tokens") => Stage::Tokens,
+                    Some("ast") => Stage::Ast,
+                    Some("c") => Stage::C,
+                    Some(stage) => return Err(format!("unknown stage '{}', expected tokens, ast or c", stage)),
+                    None => return Err("expected a stage after '--stage'".to_string()),
+                }
+            }
+            "--error-format" => options.error_format = parse_error_format(args.next().as_deref())?,
+            arg if arg.starts_with("--error-format=") => options.error_format = parse_error_format(arg.strip_prefix("--error-format="))?,
+            "-W" | "-A" | "-D" => {
+                let level: Level = lint_level(&arg);
+                let lint: String = args.next().ok_or_else(|| format!("expected a lint after '{}'", arg))?;
+                options.lints.set(&lint, level)?;
+            }
+            arg if arg.len() > 2 && ["-W", "-A", "-D"].iter().any(|flag| arg.starts_with(flag)) => {
+                options.lints.set(&arg[2..], lint_level(arg))?
+            }
+            "--" => {
+                options.arguments = args.by_ref().collect();
+            }
+            arg if arg.starts_with('-') => return Err(format!("unknown option '{}'", arg)),
+            _ => options.files.push(arg),
         }
     }
 
-    println!("Done!");
-}
\ No newline at end of file
+    if options.files.is_empty() {
+        return Err("no input files".to_string());
+    }
+    if options.command == Command::Run && options.object_only {
+        return Err("cannot run an object file".to_string());
+    }
+    Ok(options)
+}
+
+fn parse_error_format(format: Option<&str>) -> Result<ErrorFormat, String> {
+    match format {
+        Some("human") => Ok(ErrorFormat::Human),
+        Some("json") => Ok(ErrorFormat::Json),
+        Some(format) => Err(format!("unknown error format '{}', expected human or json", format)),
+        None => Err("expected a format after '--error-format'".to_string()),
+    }
+}
+
+fn lint_level(flag: &str) -> Level {
+    match &flag[..2] {
+        "-A" => Level::Allow,
+        "-D" => Level::Deny,
+        _ => Level::Warn,
+    }
+}
+
+fn emit(compiler: &mut Compiler, stage: &Stage) -> Result<(), BlazeError> {
+    let mut out = std::io::stdout().lock();
+    let result: std::io::Result<()> = match stage {
+        Stage::Tokens => {
+            let files: Vec<(String, Vec<Token>)> = compiler.lex()?;
+            files.into_iter()
+                .flat_map(|(_, tokens)| tokens)
+                .filter(|_| compiler.errors.is_empty())
+                .try_for_each(|token| writeln!(out, "{}:{}..{} {:?} {}",
+                    compiler.sources.name(token.span.file),
+                    token.span.start,
+                    token.span.end,
+                    token.kind,
+                    token.literal.unwrap_or_default()))
+        }
+        Stage::Ast => {
+            let statements: Vec<Statement> = compiler.parse()?;
+            if compiler.errors.is_empty() { writeln!(out, "{:#?}", statements) } else { Ok(()) }
+        }
+        Stage::C => {
+            compiler.compile()?;
+            if compiler.errors.is_empty() { write!(out, "{}", compiler.output) } else { Ok(()) }
+        }
+    };
+    match result {
+        Err(error) if error.kind() != std::io::ErrorKind::BrokenPipe => Err(BlazeError::IOError(std::rc::Rc::new(error))),
+        _ => Ok(()),
+    }
+}
+
+fn run(executable: PathBuf, arguments: &[String]) -> ExitCode {
+    // A relative path without a directory, like `-o program`, would otherwise be looked up on PATH.
+    let executable: PathBuf = std::path::absolute(&executable).unwrap_or(executable);
+    match std::process::Command::new(&executable).args(arguments).status() {
+        Ok(status) => match status.code() {
+            Some(code) => ExitCode::from(code as u8),
+            None => killed(&status),
+        },
+        Err(error) => {
+            eprintln!("error: failed to run {}: {}", executable.display(), error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// A program killed by a signal exits like it would from a shell, with 128 plus the signal number.
+#[cfg(unix)]
+fn killed(status: &std::process::ExitStatus) -> ExitCode {
+    use std::os::unix::process::ExitStatusExt;
+    match status.signal() {
+        Some(signal) => ExitCode::from((128 + signal) as u8),
+        None => ExitCode::FAILURE,
+    }
+}
+
+#[cfg(not(unix))]
+fn killed(_status: &std::process::ExitStatus) -> ExitCode {
+    ExitCode::FAILURE
+}
+
+fn remove_run_dir(dir: &Option<PathBuf>) {
+    if let Some(dir) = dir {
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
+
+fn executable_name(files: &[String]) -> String {
+    let stem: String = files.last()
+        .and_then(|file| std::path::Path::new(file).file_stem())
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "main".to_string());
+    format!("{}{}", stem, std::env::consts::EXE_SUFFIX)
+}
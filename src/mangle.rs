@@ -0,0 +1,89 @@
+use crate::ast::Type;
+
+// The keywords of C and the names the headers of the generated code declare, which get a trailing underscore.
+const RESERVED: &[&str] = &[
+    "auto", "break", "case", "char", "const", "continue", "default", "do", "double", "else", "enum", "extern",
+    "float", "for", "goto", "if", "inline", "int", "long", "register", "restrict", "return", "short", "signed",
+    "sizeof", "static", "struct", "switch", "typedef", "union", "unsigned", "void", "volatile", "while", "_Alignas",
+    "_Alignof", "_Atomic", "_Bool", "_Complex", "_Generic", "_Imaginary", "_Noreturn", "_Static_assert",
+    "_Thread_local", "alignas", "alignof", "bool", "constexpr", "false", "nullptr", "static_assert", "thread_local",
+    "true", "typeof", "typeof_unqual",
+    // <stddef.h> and <stdint.h>
+    "NULL", "offsetof", "size_t", "ptrdiff_t", "wchar_t", "max_align_t", "nullptr_t", "unreachable",
+    "int8_t", "int16_t", "int32_t", "int64_t", "uint8_t", "uint16_t", "uint32_t", "uint64_t",
+    "int_least8_t", "int_least16_t", "int_least32_t", "int_least64_t",
+    "uint_least8_t", "uint_least16_t", "uint_least32_t", "uint_least64_t",
+    "int_fast8_t", "int_fast16_t", "int_fast32_t", "int_fast64_t",
+    "uint_fast8_t", "uint_fast16_t", "uint_fast32_t", "uint_fast64_t",
+    "intptr_t", "uintptr_t", "intmax_t", "uintmax_t",
+    "INT8_C", "INT16_C", "INT32_C", "INT64_C", "UINT8_C", "UINT16_C", "UINT32_C", "UINT64_C", "INTMAX_C", "UINTMAX_C",
+    "INT8_MIN", "INT16_MIN", "INT32_MIN", "INT64_MIN", "INT8_MAX", "INT16_MAX", "INT32_MAX", "INT64_MAX",
+    "UINT8_MAX", "UINT16_MAX", "UINT32_MAX", "UINT64_MAX", "INTPTR_MIN", "INTPTR_MAX", "UINTPTR_MAX",
+    "INTMAX_MIN", "INTMAX_MAX", "UINTMAX_MAX", "PTRDIFF_MIN", "PTRDIFF_MAX", "SIZE_MAX",
+    // <stdio.h> and <stdlib.h>, which debug builds include for their runtime checks
+    "FILE", "fpos_t", "EOF", "BUFSIZ", "FILENAME_MAX", "FOPEN_MAX", "L_tmpnam", "SEEK_SET", "SEEK_CUR", "SEEK_END",
+    "TMP_MAX", "stdin", "stdout", "stderr", "printf", "fprintf", "sprintf", "snprintf", "vprintf", "vfprintf",
+    "vsprintf", "vsnprintf", "scanf", "fscanf", "sscanf", "vscanf", "vfscanf", "vsscanf", "puts", "fputs",
+    "putchar", "fputc", "putc", "getchar", "fgetc", "getc", "gets", "fgets", "ungetc", "fopen", "freopen", "fclose",
+    "fflush", "fread", "fwrite", "fseek", "ftell", "rewind", "fgetpos", "fsetpos", "clearerr", "feof", "ferror",
+    "perror", "remove", "rename", "tmpfile", "tmpnam", "setbuf", "setvbuf",
+    "div_t", "ldiv_t", "lldiv_t", "EXIT_SUCCESS", "EXIT_FAILURE", "RAND_MAX", "MB_CUR_MAX", "abort", "exit",
+    "_Exit", "quick_exit", "at_quick_exit", "atexit", "malloc", "calloc", "realloc", "free", "aligned_alloc",
+    "atoi", "atol", "atoll", "atof", "strtol", "strtoll", "strtoul", "strtoull", "strtod", "strtof", "strtold",
+    "rand", "srand", "abs", "labs", "llabs", "div", "ldiv", "lldiv", "qsort", "bsearch", "getenv", "system",
+    "mblen", "mbtowc", "wctomb", "mbstowcs", "wcstombs",
+];
+
+// The C name of a qualified Blaze name, like `geometry__Point` for `geometry::Point`. The `/` of a module path
+// becomes `_2` and an underscore that is not followed by a letter becomes `_1`, so that every name gets a C name of
+// its own and none of them starts with `__`, which is left to the names the C backend makes up. Since no C name
+// ends with an underscore either, one is added to the names in `RESERVED`, like `int_` for `int`. An instance of a
+// generic function keeps its mangled arguments after the `$`.
+pub fn mangle(name: &str) -> String {
+    let (name, arguments) = name.split_once('$').unwrap_or((name, ""));
+    let mut mangled: String = String::new();
+    let mut characters = name.chars().peekable();
+    while let Some(character) = characters.next() {
+        match character {
+            ':' => {
+                characters.next();
+                mangled.push_str("__");
+            }
+            '/' => mangled.push_str("_2"),
+            '_' if characters.peek().is_some_and(|next| next.is_ascii_alphabetic()) => mangled.push('_'),
+            '_' => mangled.push_str("_1"),
+            character => mangled.push(character),
+        }
+    }
+    mangled.push_str(arguments);
+    if RESERVED.contains(&mangled.as_str()) {
+        mangled.push('_');
+    }
+    mangled
+}
+
+// The C name of a type that needs a definition of its own, like `__blz_slice_i32` for `[]i32`. Generic instances
+// are named after their arguments, like `Box__3i32` for `Box<i32>`.
+pub fn mangle_type(ty: &Type) -> String {
+    match ty {
+        Type::Array(ty, _) => format!("__blz_slice_{}", mangle_type(ty)),
+        Type::FixedArray(ty, length, _) => format!("__blz_array_{}_{}", length, mangle_type(ty)),
+        Type::Pointer(ty, _) => format!("__blz_ptr_{}", mangle_type(ty)),
+        Type::Optional(ty, _) => format!("__blz_opt_{}", mangle_type(ty)),
+        Type::ErrorUnion(_, ty, _) => format!("__blz_result_{}", mangle_type(ty)),
+        Type::ErrorSet(_, _) => "error".to_string(),
+        Type::Struct(name, _) | Type::Enum(name, _) | Type::Union(name, _) => mangle(name),
+        Type::GenericInstance(name, arguments, _) => format!("{}{}", mangle(name), mangle_arguments(arguments)),
+        Type::AwaitingInference => "_".to_string(),
+        ty => ty.name(),
+    }
+}
+
+// The arguments of a generic instance or the values a function returns, each prefixed with the length of its name
+// so that they cannot run into each other, like `__3i32__4bool` for `i32, bool`.
+pub fn mangle_arguments(arguments: &[Type]) -> String {
+    arguments.iter()
+        .map(mangle_type)
+        .map(|argument| format!("__{}{}", argument.len(), argument))
+        .collect()
+}
\ No newline at end of file
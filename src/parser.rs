@@ -1,634 +1,1226 @@
-use crate::token::{Token, TokenKind};
-use crate::error::BlazeError;
-use crate::ast::{Statement, Expression, Type, StructField, EnumVariant};
-use crate::span::Span;
-
-#[derive(Debug, Clone)]
-pub struct Parser {
-    pub tokens: Vec<Token>,
-    pub errors: Vec<BlazeError>,
-    pub statements: Vec<Statement>,
-    pub current: usize,
-}
-
-impl Parser {
-    pub fn new(tokens: Vec<Token>) -> Parser {
-        Parser {
-            tokens: tokens,
-            errors: Vec::new(),
-            statements: Vec::new(),
-            current: 0,
-        }
-    }
-    pub fn parse(&mut self) -> Result<Vec<Statement>, Vec<BlazeError>> {
-        while self.current < self.tokens.len() {
-            let statement: Result<Statement, BlazeError> = self.parse_statement();
-            match statement {
-                Ok(statement) => self.statements.push(statement),
-                Err(error) => {
-                    self.errors.push(error);
-                    self.synchronize();
-                }
-            }
-        }
-
-        if self.errors.len() == 0 {
-            Ok(self.statements.clone())
-        } else {
-            Err(self.errors.clone())
-        }
-    }
-    fn synchronize(&mut self) {
-        while self.current < self.tokens.len() {
-            // if self.tokens[self.current].kind == TokenKind::Newline {
-            //     self.current += 1;
-            //     return;
-            // }
-            self.current += 1;
-        }
-    }
-
-    fn parse_statement(&mut self) -> Result<Statement, BlazeError> {
-        match self.current()?.kind {
-            TokenKind::Identifier => self.parse_identifier(),
-            TokenKind::Return => self.parse_return(),
-            TokenKind::Mut => self.parse_mut(),
-            TokenKind::While => self.parse_while(),
-            TokenKind::If => self.parse_if(),
-            _ => {
-                let span: Span = self.current()?.span;
-                let expression: Expression = self.parse_expression()?;
-                // self.expect(TokenKind::Newline)?;
-                self.expect(TokenKind::Semicolon)?;
-                Ok(Statement::Expression(expression, span))
-            }
-        }
-    }
-
-    fn parse_identifier(&mut self) -> Result<Statement, BlazeError> {
-        let span: Span = self.current()?.span;
-        if self.peek()?.kind == TokenKind::DoubleColon {
-            let identifier: String = self.expect(TokenKind::Identifier)?.literal.unwrap();
-            self.expect(TokenKind::DoubleColon)?;
-            if self.current()?.kind == TokenKind::Namespace {
-                self.parse_namespace(identifier, span)
-            } else if self.current()?.kind == TokenKind::Struct {
-                self.parse_struct(identifier, span)
-            } else if self.current()?.kind == TokenKind::Enum {
-                self.parse_enum(identifier, span)
-            } else if self.current()?.kind == TokenKind::Union {
-                self.parse_union(identifier, span)
-            } else if self.current()?.kind == TokenKind::Fn {
-                self.parse_fn(identifier, span)
-            } else if self.current()?.kind == TokenKind::Import {
-                self.parse_import(identifier, span)
-            } else {
-                let value: Expression = self.parse_expression()?;
-                Ok(Statement::ConstantDeclaration(identifier, Type::AwaitingInference, value, span))
-            }
-        } else if self.peek()?.kind == TokenKind::Colon {
-            let identifier: String = self.expect(TokenKind::Identifier)?.literal.unwrap();
-            self.expect(TokenKind::Colon)?;
-            let ty: Type = self.parse_type()?;
-            self.expect(TokenKind::Equal)?;
-            let value: Expression = self.parse_expression()?;
-            self.expect(TokenKind::Semicolon)?;
-            Ok(Statement::VariableDeclaration(identifier, ty, value, span))
-        } else if self.peek()?.kind == TokenKind::ColonEquals {
-            let identifier: String = self.expect(TokenKind::Identifier)?.literal.unwrap();
-            self.expect(TokenKind::ColonEquals)?;
-            let value: Expression = self.parse_expression()?;
-            self.expect(TokenKind::Semicolon)?;
-            Ok(Statement::VariableDeclaration(identifier, Type::AwaitingInference, value, span))
-        } else {
-            let expression: Expression = self.parse_expression()?;
-            self.expect(TokenKind::Semicolon)?;
-            Ok(Statement::Expression(expression, span))
-        }
-    }
-    fn parse_namespace(&mut self, identifier: String, span: Span) -> Result<Statement, BlazeError> {
-        self.expect(TokenKind::Namespace)?;
-        let mut statements: Vec<Statement> = Vec::new();
-        self.expect(TokenKind::OpenBrace)?;
-        
-        while self.current()?.kind != TokenKind::CloseBrace {
-            let statement: Statement = self.parse_statement()?;
-            statements.push(statement);
-        }
-        
-        self.expect(TokenKind::CloseBrace)?;
-        
-        Ok(Statement::Namespace(identifier, statements, span))
-    }
-    fn parse_struct(&mut self, identifier: String, span: Span) -> Result<Statement, BlazeError> {
-        self.expect(TokenKind::Struct)?;
-        let mut generic_parameters: Vec<Type> = Vec::new();
-        if self.current()?.kind == TokenKind::Less {
-            self.expect(TokenKind::Less)?;
-            generic_parameters.push(self.parse_type()?);
-            while self.current()?.kind == TokenKind::Comma {
-                self.expect(TokenKind::Comma)?;
-                generic_parameters.push(self.parse_type()?);
-            }
-            self.expect(TokenKind::Greater)?;
-        }
-        let inherits: Vec<String> = Vec::new();
-        let mut fields: Vec<StructField> = Vec::new();
-        self.expect(TokenKind::OpenBrace)?;
-        
-        while self.current()?.kind != TokenKind::CloseBrace {
-            let span: Span = self.current()?.span;
-            let identifier: String = self.expect(TokenKind::Identifier)?.literal.unwrap();
-            self.expect(TokenKind::Colon)?;
-            let ty: Type = self.parse_type()?;
-            fields.push(StructField {
-                name: identifier,
-                ty: ty,
-                span: span,
-            });
-            if self.current()?.kind == TokenKind::Comma {
-                self.expect(TokenKind::Comma)?;
-            } else {
-                break;
-            }
-        }
-        
-        self.expect(TokenKind::CloseBrace)?;
-        
-        Ok(Statement::Struct(identifier, generic_parameters, inherits, fields, span))
-    }
-    fn parse_enum(&mut self, identifier: String, span: Span) -> Result<Statement, BlazeError> {
-        self.expect(TokenKind::Enum)?;
-        let mut inner_ty: Option<Type> = None;
-        if self.current()?.kind == TokenKind::OpenParenthesis {
-            self.expect(TokenKind::OpenParenthesis)?;
-            inner_ty = Some(self.parse_type()?);
-            self.expect(TokenKind::CloseParenthesis)?;
-        }
-        let mut variants: Vec<EnumVariant> = Vec::new();
-        self.expect(TokenKind::OpenBrace)?;
-        
-        while self.current()?.kind != TokenKind::CloseBrace {
-            let span: Span = self.current()?.span;
-            let identifier: String = self.expect(TokenKind::Identifier)?.literal.unwrap();
-            if self.current()?.kind == TokenKind::Comma {
-                self.expect(TokenKind::Comma)?;
-                variants.push(EnumVariant::Unit(identifier, span));
-                continue;
-            } else if self.current()?.kind == TokenKind::CloseBrace {
-                variants.push(EnumVariant::Unit(identifier, span));
-                break;
-            }
-            if self.current()?.kind == TokenKind::Equal && inner_ty.is_none() {
-                return Err(BlazeError::ParseError(format!("cannot assign value to enum variant without inner type"), span));
-            }
-            self.expect(TokenKind::Equal)?;
-            let expression: Expression = self.parse_expression()?;
-            variants.push(EnumVariant::Expression(identifier, expression, span));
-            if self.current()?.kind == TokenKind::Comma {
-                self.expect(TokenKind::Comma)?;
-            } else {
-                break;
-            }
-        }
-        
-        self.expect(TokenKind::CloseBrace)?;
-        
-        if inner_ty.is_some() {
-            Ok(Statement::TypedEnum(identifier, inner_ty.unwrap(), variants, span))
-        } else {
-            Ok(Statement::Enum(identifier, variants, span))
-        }
-    }
-    fn parse_union(&mut self, identifier: String, span: Span) -> Result<Statement, BlazeError> {
-        self.expect(TokenKind::Union)?;
-        let mut types: Vec<Type> = Vec::new();
-        self.expect(TokenKind::OpenBrace)?;
-        
-        while self.current()?.kind != TokenKind::CloseBrace {
-            let ty: Type = self.parse_type()?;
-            if self.current()?.kind == TokenKind::Comma {
-                self.expect(TokenKind::Comma)?;
-                types.push(ty);
-            } else {
-                types.push(ty);
-                break;
-            }
-        }
-        
-        self.expect(TokenKind::CloseBrace)?;
-        
-        Ok(Statement::Union(identifier, types, span))
-    }
-    fn parse_fn(&mut self, identifier: String, span: Span) -> Result<Statement, BlazeError> {
-        self.expect(TokenKind::Fn)?;
-        let mut parameters: Vec<(String, Type, bool, Span)> = Vec::new();
-        let mut returns: Vec<Type> = vec![Type::Void(span.clone())];
-        let mut struct_name: Option<Type> = None;
-        self.expect(TokenKind::OpenParenthesis)?;
-        
-        while self.current()?.kind != TokenKind::CloseParenthesis {
-            let span: Span = self.current()?.span;
-            let comptime: bool = if self.current()?.kind == TokenKind::Comptime {
-                self.expect(TokenKind::Comptime)?;
-                true
-            } else { false };
-            if self.current()?.kind == TokenKind::SelfKeyword {
-                self.expect(TokenKind::SelfKeyword)?;
-                self.expect(TokenKind::Colon)?;
-                let ty: Type = self.parse_type()?;
-                struct_name = Some(ty);
-                if self.current()?.kind != TokenKind::CloseParenthesis {
-                    self.expect(TokenKind::Comma)?;
-                }
-                continue;
-            }
-            let identifier: String = self.expect(TokenKind::Identifier)?.literal.unwrap();
-            self.expect(TokenKind::Colon)?;
-            if self.current()?.kind == TokenKind::Elipsis {
-                let var_args_span: Span = self.current()?.span;
-                self.expect(TokenKind::Elipsis)?;
-                parameters.push((identifier, Type::VarArgs(Box::new(None), var_args_span.clone()), comptime, span));
-                break;
-            }
-            let ty: Type = self.parse_type()?;
-            if self.current()?.kind == TokenKind::Elipsis {
-                let var_args_span: Span = self.current()?.span;
-                self.expect(TokenKind::Elipsis)?;
-                parameters.push((identifier, Type::VarArgs(Box::new(Some(ty)), var_args_span.clone()), comptime, span));
-                break;
-            }
-            if self.current()?.kind != TokenKind::CloseParenthesis {
-                self.expect(TokenKind::Comma)?;
-            }
-            parameters.push((identifier, ty, comptime, span));
-        }
-        
-        self.expect(TokenKind::CloseParenthesis)?;
-        
-        if self.current()?.kind == TokenKind::Arrow {
-            self.expect(TokenKind::Arrow)?;
-            returns.push(self.parse_type()?);
-            while self.current()?.kind == TokenKind::Comma {
-                self.expect(TokenKind::Comma)?;
-                returns.push(self.parse_type()?);
-            }
-        }
-        
-        let mut statements: Vec<Statement> = Vec::new();
-        self.expect(TokenKind::OpenBrace)?;
-        
-        while self.current()?.kind != TokenKind::CloseBrace {
-            let statement: Statement = self.parse_statement()?;
-            statements.push(statement);
-        }
-        
-        self.expect(TokenKind::CloseBrace)?;
-        
-        if struct_name.is_some() {
-            Ok(Statement::StructFunction(struct_name.unwrap(), identifier, parameters, returns, statements, span))
-        } else {
-            Ok(Statement::Function(identifier, parameters, returns, statements, span))
-        }
-    }
-    fn parse_import(&mut self, identifier: String, span: Span) -> Result<Statement, BlazeError> {
-        self.expect(TokenKind::Import)?;
-        let path: String = self.expect(TokenKind::StringLiteral)?.literal.unwrap();
-        
-        Ok(Statement::Import(identifier, path, span))
-    }
-    fn parse_return(&mut self) -> Result<Statement, BlazeError> {
-        let span: Span = self.current()?.span;
-        self.expect(TokenKind::Return)?;
-        if self.current()?.kind == TokenKind::Semicolon {
-            self.expect(TokenKind::Semicolon)?;
-            Ok(Statement::Return(vec![], span))
-        } else {
-            let mut expressions: Vec<Expression> = vec![];
-            expressions.push(self.parse_expression()?);
-            while self.current()?.kind == TokenKind::Comma {
-                self.expect(TokenKind::Comma)?;
-                expressions.push(self.parse_expression()?);
-            }
-            self.expect(TokenKind::Semicolon)?;
-            Ok(Statement::Return(expressions, span))
-        }
-    }
-    fn parse_mut(&mut self) -> Result<Statement, BlazeError> {
-        self.expect(TokenKind::Mut)?;
-        let span: Span = self.current()?.span;
-        let identifier: String = self.expect(TokenKind::Identifier)?.literal.unwrap();
-        let mut ty: Type = Type::AwaitingInference;
-        if self.current()?.kind == TokenKind::ColonEquals {
-            self.expect(TokenKind::ColonEquals)?;
-        } else {
-            self.expect(TokenKind::Colon)?;
-            ty = self.parse_type()?;
-            self.expect(TokenKind::Equal)?;
-        }
-        let value: Expression = self.parse_expression()?;
-        self.expect(TokenKind::Semicolon)?;
-        Ok(Statement::MutableDeclaration(identifier, ty, value, span))
-    }
-    fn parse_while(&mut self) -> Result<Statement, BlazeError> {
-        let span: Span = self.current()?.span;
-        self.expect(TokenKind::While)?;
-        let expression: Expression = self.parse_expression()?;
-        let mut statements: Vec<Statement> = Vec::new();
-        self.expect(TokenKind::OpenBrace)?;
-        
-        while self.current()?.kind != TokenKind::CloseBrace {
-            let statement: Statement = self.parse_statement()?;
-            statements.push(statement);
-        }
-        
-        self.expect(TokenKind::CloseBrace)?;
-        
-        Ok(Statement::While(expression, statements, span))
-    }
-
-    fn parse_if(&mut self) -> Result<Statement, BlazeError> {
-        self.expect(TokenKind::If)?;
-        let span: Span = self.current()?.span;
-        let expression: Expression = self.parse_expression()?;
-        let mut if_statements: Vec<Statement> = Vec::new();
-        let mut else_statements: Vec<Statement> = Vec::new();
-        self.expect(TokenKind::OpenBrace)?;
-        
-        while self.current()?.kind != TokenKind::CloseBrace {
-            let statement: Statement = self.parse_statement()?;
-            if_statements.push(statement);
-        }
-        
-        self.expect(TokenKind::CloseBrace)?;
-        
-        if self.current()?.kind == TokenKind::Else {
-            self.expect(TokenKind::Else)?;
-            self.expect(TokenKind::OpenBrace)?;
-            
-            while self.current()?.kind != TokenKind::CloseBrace {
-                let statement: Statement = self.parse_statement()?;
-                else_statements.push(statement);
-            }
-            
-            self.expect(TokenKind::CloseBrace)?;
-            
-        }
-        Ok(Statement::If(expression, if_statements, else_statements, span))
-    }
-
-    fn parse_expression(&mut self) -> Result<Expression, BlazeError> {
-        self.parse_member_access()
-    }
-    fn parse_member_access(&mut self) -> Result<Expression, BlazeError> {
-        let span: Span = self.current()?.span;
-        let mut expression: Expression = self.parse_binary_operation()?;
-        while self.current()?.kind == TokenKind::Dot || self.current()?.kind == TokenKind::DoubleColon {
-            let mut is_static: bool = false;
-            if self.current()?.kind == TokenKind::Dot {
-                self.expect(TokenKind::Dot)?;
-            } else {
-                self.expect(TokenKind::DoubleColon)?;
-                is_static = true;
-            }
-            let member: Expression = self.parse_expression()?;
-            if is_static {
-                expression = Expression::StaticMemberAccess(Box::new(expression), Box::new(member), span.clone());
-            } else {
-                expression = Expression::MemberAccess(Box::new(expression), Box::new(member), span.clone());
-            }
-        }
-        Ok(expression)
-    }
-    fn parse_binary_operation(&mut self) -> Result<Expression, BlazeError> {
-        let span: Span = self.current()?.span;
-        let mut expression: Expression = self.parse_primary()?;
-        while self.current()?.kind == TokenKind::Plus
-                || self.current()?.kind == TokenKind::Minus
-                || self.current()?.kind == TokenKind::Asterisk
-                || self.current()?.kind == TokenKind::Slash
-                || self.current()?.kind == TokenKind::Percent
-                || self.current()?.kind == TokenKind::EqualEqual
-                || self.current()?.kind == TokenKind::BangEqual
-                || self.current()?.kind == TokenKind::Greater
-                || self.current()?.kind == TokenKind::GreaterEqual
-                || self.current()?.kind == TokenKind::Less
-                || self.current()?.kind == TokenKind::LessEqual
-                || self.current()?.kind == TokenKind::PlusEquals
-                || self.current()?.kind == TokenKind::MinusEquals
-                || self.current()?.kind == TokenKind::AsteriskEquals
-                || self.current()?.kind == TokenKind::SlashEquals
-                || self.current()?.kind == TokenKind::PercentEquals {
-            let operator: Token = self.current()?.clone();
-            self.advance()?;
-            let right: Expression = self.parse_expression()?;
-            expression = Expression::BinaryOperation(Box::new(expression), operator.kind, Box::new(right), span.clone());
-        }
-        Ok(expression)
-    }
-    fn parse_primary(&mut self) -> Result<Expression, BlazeError> {
-        let span = self.current()?.span;
-        match self.current()?.kind {
-            TokenKind::Identifier => {
-                let identifier: String = self.expect(TokenKind::Identifier)?.literal.unwrap();
-                if self.current()?.kind == TokenKind::OpenBracket {
-                    self.expect(TokenKind::OpenBracket)?;
-                    let index: Expression = self.parse_expression()?;
-                    self.expect(TokenKind::CloseBracket)?;
-                    Ok(Expression::ArrayAccess(identifier, Box::new(index), span))
-                } else if self.current()?.kind == TokenKind::OpenBrace {
-                    self.expect(TokenKind::OpenBrace)?;
-                    let mut fields: Vec<(Option<String>, Expression, Span)> = Vec::new();
-                    while self.current()?.kind != TokenKind::CloseBrace {
-                        let span: Span = self.current()?.span;
-                        let mut identifier: Option<String> = None;
-                        if self.peek()?.kind == TokenKind::Colon {
-                            identifier = Some(self.expect(TokenKind::Identifier)?.literal.unwrap());
-                            self.expect(TokenKind::Colon)?;
-                        }
-                        let expression: Expression = self.parse_expression()?;
-                        if self.current()?.kind == TokenKind::Comma {
-                            self.expect(TokenKind::Comma)?;
-                        } else {
-                            break;
-                        }
-                        fields.push((identifier, expression, span));
-                    }
-                    
-                    self.expect(TokenKind::CloseBrace)?;
-                    Ok(Expression::StructLiteral(identifier, fields, span))
-                } else {
-                    Ok(Expression::Identifier(identifier, span))
-                }
-            }
-            TokenKind::IntegerLiteral => {
-                let integer: i64 = self.expect(TokenKind::IntegerLiteral)?.literal.unwrap().parse::<i64>().unwrap();
-                Ok(Expression::Integer(integer, span))
-            }
-            TokenKind::CharLiteral => {
-                let character: char = self.expect(TokenKind::CharLiteral)?.literal.unwrap().parse::<char>().unwrap();
-                Ok(Expression::Char(character, span))
-            }
-            TokenKind::StringLiteral => {
-                let string: String = self.expect(TokenKind::StringLiteral)?.literal.unwrap();
-                Ok(Expression::String(string, span))
-            }
-            TokenKind::SelfKeyword => {
-                self.expect(TokenKind::SelfKeyword)?;
-                Ok(Expression::SelfLiteral(span))
-            }
-            TokenKind::Ampersand => {
-                self.expect(TokenKind::Ampersand)?;
-                let expression: Expression = self.parse_expression()?;
-                Ok(Expression::AddressOf(Box::new(expression), span))
-            }
-            TokenKind::Asterisk => {
-                self.expect(TokenKind::Asterisk)?;
-                let expression: Expression = self.parse_expression()?;
-                Ok(Expression::Dereference(Box::new(expression), span))
-            }
-            TokenKind::Try => {
-                self.expect(TokenKind::Try)?;
-                let expression: Expression = self.parse_expression()?;
-                Ok(Expression::Try(Box::new(expression), span))
-            }
-            TokenKind::Null => {
-                self.expect(TokenKind::Null)?;
-                Ok(Expression::Null(span))
-            }
-            _ => {
-                Err(BlazeError::ParseError(format!("expected expression, but got {:?}", self.current()?.kind), span))
-            }
-        }
-    }
-
-    fn parse_type(&mut self) -> Result<Type, BlazeError> {
-        let span: Span = self.current()?.span;
-        let t: Type = match self.current()?.kind.clone() {
-            TokenKind::I8 => {
-                self.expect(TokenKind::I8)?;
-                Type::I8(span.clone())
-            }
-            TokenKind::I16 => {
-                self.expect(TokenKind::I16)?;
-                Type::I16(span.clone())
-            }
-            TokenKind::I32 => {
-                self.expect(TokenKind::I32)?;
-                Type::I32(span.clone())
-            }
-            TokenKind::I64 => {
-                self.expect(TokenKind::I64)?;
-                Type::I64(span.clone())
-            }
-            TokenKind::U8 => {
-                self.expect(TokenKind::U8)?;
-                Type::U8(span.clone())
-            }
-            TokenKind::U16 => {
-                self.expect(TokenKind::U16)?;
-                Type::U16(span.clone())
-            }
-            TokenKind::U32 => {
-                self.expect(TokenKind::U32)?;
-                Type::U32(span.clone())
-            }
-            TokenKind::U64 => {
-                self.expect(TokenKind::U64)?;
-                Type::U64(span.clone())
-            }
-            TokenKind::F32 => {
-                self.expect(TokenKind::F32)?;
-                Type::F32(span.clone())
-            }
-            TokenKind::F64 => {
-                self.expect(TokenKind::F64)?;
-                Type::F64(span.clone())
-            }
-            TokenKind::Bool => {
-                self.expect(TokenKind::Bool)?;
-                Type::Bool(span.clone())
-            }
-            TokenKind::Char => {
-                self.expect(TokenKind::Char)?;
-                Type::Char(span.clone())
-            }
-            TokenKind::Void => {
-                self.expect(TokenKind::Void)?;
-                Type::Void(span.clone())
-            }
-            TokenKind::Type => {
-                self.expect(TokenKind::Type)?;
-                Type::Type(span.clone())
-            }
-            TokenKind::Asterisk => {
-                self.expect(TokenKind::Asterisk)?;
-                let t: Type = self.parse_type()?;
-                Type::Pointer(Box::new(t), span.clone())
-            }
-            TokenKind::OpenBracket => {
-                self.expect(TokenKind::OpenBracket)?;
-                self.expect(TokenKind::CloseBracket)?;
-                let t: Type = self.parse_type()?;
-                Type::Array(Box::new(t), span.clone())
-            }
-            TokenKind::QuestionMark => {
-                self.expect(TokenKind::QuestionMark)?;
-                let t: Type = self.parse_type()?;
-                Type::Optional(Box::new(t), span.clone())
-            }
-            TokenKind::Dollar => {
-                self.expect(TokenKind::Dollar)?;
-                let identifier: String = self.expect(TokenKind::Identifier)?.literal.unwrap();
-                Type::Generic(identifier, span.clone())
-            }
-            _ => {
-                let identifier: String = self.expect(TokenKind::Identifier)?.literal.unwrap();
-                if self.current()?.kind == TokenKind::Less {
-                    self.expect(TokenKind::Less)?;
-                    let mut types: Vec<Type> = vec![self.parse_type()?];
-                    while self.current()?.kind == TokenKind::Comma {
-                        self.expect(TokenKind::Comma)?;
-                        types.push(self.parse_type()?);
-                    }
-                    self.expect(TokenKind::Greater)?;
-                    return Ok(Type::GenericInstance(identifier, types, span));
-                }
-                Type::Unknown(identifier, span.clone())
-            }
-        };
-        Ok(t)
-    }
-
-    fn peek(&mut self) -> Result<Token, BlazeError> {
-        if self.current + 1 >= self.tokens.len() {
-            return Err(BlazeError::ParseError(format!("unexpected end of file"), self.tokens[self.current].span.clone()));
-        }
-        Ok(self.tokens[self.current + 1].clone())
-    }
-    fn expect(&mut self, kind: TokenKind) -> Result<Token, BlazeError> {
-        let token: Token = self.current()?;
-        if token.kind != kind {
-            return Err(BlazeError::ParseError(format!("unexpected token: {:?} ({:?}), expected: {:?}", token.kind, token.literal, kind), token.span.clone()));
-        }
-        self.advance()?;
-        Ok(token)
-    }
-    fn current(&mut self) -> Result<Token, BlazeError> {
-        if self.current >= self.tokens.len() {
-            return Err(BlazeError::ParseError(format!("unexpected end of file"), self.tokens[self.current - 1].span.clone()));
-        }
-        let curr_token: Token = self.tokens[self.current].clone();
-        if self.current < self.tokens.len() {
-            return Ok(self.tokens[self.current].clone());
-        }
-        Err(BlazeError::ParseError(format!("unexpected end of file"), curr_token.span.clone()))
-    }
-    fn advance(&mut self) -> Result<(), BlazeError> {
-        if self.current >= self.tokens.len() {
-            return Err(BlazeError::ParseError(format!("unexpected end of file"), self.tokens[self.current - 1].span.clone()));
-        }
-        self.current += 1;
-        Ok(())
-    }
+use std::collections::HashMap;
+
+use crate::token::{Token, TokenKind};
+use crate::error::{BlazeError, ErrorCode};
+use crate::ast::{Statement, Expression, Type, StructField, EnumVariant, Pattern};
+use crate::span::Span;
+
+#[derive(Debug, Clone)]
+pub struct Parser {
+    pub tokens: Vec<Token>,
+    pub errors: Vec<BlazeError>,
+    pub statements: Vec<Statement>,
+    pub documentation: HashMap<usize, (String, Span)>,
+    pub current: usize,
+    struct_literals: bool,
+    functions: usize,
+    // Whether the function being parsed is `#extern`, which ends with a `;` in place of a body.
+    external: bool,
+}
+
+impl Parser {
+    pub fn new(tokens: Vec<Token>) -> Parser {
+        let mut documentation: HashMap<usize, (String, Span)> = HashMap::new();
+        let mut code: Vec<Token> = Vec::new();
+        for token in tokens {
+            if token.kind != TokenKind::DocComment {
+                code.push(token);
+                continue;
+            }
+            let line: String = token.literal.unwrap_or_default();
+            documentation.entry(code.len())
+                .and_modify(|(text, _)| {
+                    text.push('\n');
+                    text.push_str(&line);
+                })
+                .or_insert((line.clone(), token.span));
+        }
+        Parser {
+            tokens: code,
+            errors: Vec::new(),
+            statements: Vec::new(),
+            documentation,
+            current: 0,
+            struct_literals: true,
+            functions: 0,
+            external: false,
+        }
+    }
+    pub fn parse(&mut self) -> Result<Vec<Statement>, Vec<BlazeError>> {
+        while self.current < self.tokens.len() {
+            let statement: Statement = self.parse_recovering_statement();
+            self.statements.push(statement);
+        }
+
+        if self.errors.is_empty() {
+            Ok(self.statements.clone())
+        } else {
+            Err(self.errors.clone())
+        }
+    }
+    fn parse_recovering_statement(&mut self) -> Statement {
+        let start: usize = self.current;
+        match self.parse_statement() {
+            Ok(statement) => statement,
+            Err(error) => {
+                // An error that bubbles out of a nested block is usually reported again at
+                // the same place by every enclosing block (e.g. an unexpected end of file).
+                if !self.errors.last().is_some_and(|last| last.message() == error.message() && last.span() == error.span()) {
+                    self.errors.push(error);
+                }
+                self.synchronize(start);
+                let end: usize = self.current.min(self.tokens.len()).max(start + 1);
+                Statement::Error(Span {
+                    file: self.tokens[start].span.file,
+                    start: self.tokens[start].span.start,
+                    end: self.tokens[end - 1].span.end,
+                })
+            }
+        }
+    }
+    // Skips to the end of the statement that started at `start`: past the next `;`, past the
+    // `}` closing a block the statement opened, or up to the `}` of the enclosing block or the
+    // next `name ::` declaration, whichever comes first.
+    fn synchronize(&mut self, start: usize) {
+        let mut depth: usize = 0;
+        for token in &self.tokens[start..self.current.min(self.tokens.len())] {
+            match token.kind {
+                TokenKind::OpenBrace => depth += 1,
+                TokenKind::CloseBrace => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+        while self.current < self.tokens.len() {
+            match self.tokens[self.current].kind {
+                TokenKind::Semicolon if depth == 0 => {
+                    self.current += 1;
+                    return;
+                }
+                TokenKind::OpenBrace => depth += 1,
+                TokenKind::CloseBrace if depth == 0 => {
+                    if self.current == start {
+                        self.current += 1;
+                    }
+                    return;
+                }
+                TokenKind::CloseBrace => {
+                    depth -= 1;
+                    if depth == 0 {
+                        self.current += 1;
+                        if self.current < self.tokens.len() && self.tokens[self.current].kind == TokenKind::Semicolon {
+                            self.current += 1;
+                        }
+                        return;
+                    }
+                }
+                TokenKind::Identifier if depth == 0 && self.current != start && self.is_declaration_start() => return,
+                _ => {}
+            }
+            self.current += 1;
+        }
+    }
+
+    fn parse_statement(&mut self) -> Result<Statement, BlazeError> {
+        let documentation: Option<(String, Span)> = self.documentation.get(&self.current).cloned();
+        let statement: Statement = if self.current()?.kind == TokenKind::Hash {
+            self.parse_attribute()?
+        } else {
+            self.parse_undocumented_statement()?
+        };
+        match documentation {
+            Some((documentation, span)) if is_declaration(&statement) => {
+                Ok(Statement::Documented(documentation, Box::new(statement), span))
+            }
+            _ => Ok(statement),
+        }
+    }
+    fn parse_undocumented_statement(&mut self) -> Result<Statement, BlazeError> {
+        match self.current()?.kind {
+            TokenKind::Identifier => self.parse_identifier(),
+            TokenKind::Return => self.parse_return(),
+            TokenKind::Mut if self.is_destructuring() => self.parse_destructuring(),
+            TokenKind::Mut => self.parse_mut(),
+            TokenKind::While => self.parse_while(),
+            TokenKind::For => self.parse_for(),
+            TokenKind::Break | TokenKind::Continue => self.parse_jump(),
+            TokenKind::If => self.parse_if(),
+            TokenKind::Match => self.parse_match(),
+            TokenKind::OpenBrace => {
+                let span: Span = self.current()?.span;
+                Ok(Statement::Block(self.parse_block()?, span))
+            }
+            TokenKind::Defer => {
+                let span: Span = self.expect(TokenKind::Defer)?.span;
+                Ok(Statement::Defer(Box::new(self.parse_statement()?), span))
+            }
+            _ => {
+                let span: Span = self.current()?.span;
+                let expression: Expression = self.parse_expression()?;
+                // self.expect(TokenKind::Newline)?;
+                self.expect(TokenKind::Semicolon)?;
+                Ok(Statement::Expression(expression, span))
+            }
+        }
+    }
+
+    fn parse_attribute(&mut self) -> Result<Statement, BlazeError> {
+        let span: Span = self.expect(TokenKind::Hash)?.span;
+        let name: String = self.expect(TokenKind::Identifier)?.literal.unwrap();
+        let mut arguments: Vec<String> = Vec::new();
+        if self.current()?.kind == TokenKind::OpenParenthesis {
+            self.expect(TokenKind::OpenParenthesis)?;
+            while self.current()?.kind != TokenKind::CloseParenthesis {
+                arguments.push(self.expect(TokenKind::Identifier)?.literal.unwrap());
+                if self.current()?.kind != TokenKind::CloseParenthesis {
+                    self.expect(TokenKind::Comma)?;
+                }
+            }
+            self.expect(TokenKind::CloseParenthesis)?;
+        }
+        self.external = name == "extern";
+        let statement: Result<Statement, BlazeError> = self.parse_statement();
+        self.external = false;
+        Ok(Statement::Attribute(name, arguments, Box::new(statement?), span))
+    }
+    fn parse_identifier(&mut self) -> Result<Statement, BlazeError> {
+        let span: Span = self.current()?.span;
+        if self.is_declaration_start() {
+            let identifier: String = self.expect(TokenKind::Identifier)?.literal.unwrap();
+            self.expect(TokenKind::DoubleColon)?;
+            if self.current()?.kind == TokenKind::Namespace {
+                self.parse_namespace(identifier, span)
+            } else if self.current()?.kind == TokenKind::Struct {
+                self.parse_struct(identifier, span)
+            } else if self.current()?.kind == TokenKind::Enum {
+                self.parse_enum(identifier, span)
+            } else if self.current()?.kind == TokenKind::Error {
+                self.parse_error_set(identifier, span)
+            } else if self.current()?.kind == TokenKind::Union {
+                self.parse_union(identifier, span)
+            } else if self.current()?.kind == TokenKind::Fn {
+                self.parse_fn(identifier, span)
+            } else if self.current()?.kind == TokenKind::Import {
+                self.parse_import(identifier, span)
+            } else {
+                let value: Expression = self.parse_expression()?;
+                self.expect(TokenKind::Semicolon)?;
+                Ok(Statement::ConstantDeclaration(identifier, Type::AwaitingInference, value, span))
+            }
+        } else if self.peek()?.kind == TokenKind::Colon && self.is_label() {
+            let label: String = self.expect(TokenKind::Identifier)?.literal.unwrap();
+            self.expect(TokenKind::Colon)?;
+            let statement: Statement = match self.current()?.kind {
+                TokenKind::For => self.parse_for()?,
+                _ => self.parse_while()?,
+            };
+            Ok(Statement::Labeled(label, Box::new(statement), span))
+        } else if self.peek()?.kind == TokenKind::Colon {
+            let identifier: String = self.expect(TokenKind::Identifier)?.literal.unwrap();
+            self.expect(TokenKind::Colon)?;
+            let ty: Type = self.parse_type()?;
+            self.expect(TokenKind::Equal)?;
+            let value: Expression = self.parse_expression()?;
+            self.expect(TokenKind::Semicolon)?;
+            Ok(Statement::VariableDeclaration(identifier, ty, value, span))
+        } else if self.is_destructuring() {
+            self.parse_destructuring()
+        } else if self.peek()?.kind == TokenKind::ColonEquals {
+            let identifier: String = self.expect(TokenKind::Identifier)?.literal.unwrap();
+            self.expect(TokenKind::ColonEquals)?;
+            let value: Expression = self.parse_expression()?;
+            self.expect(TokenKind::Semicolon)?;
+            Ok(Statement::VariableDeclaration(identifier, Type::AwaitingInference, value, span))
+        } else {
+            let expression: Expression = self.parse_expression()?;
+            self.expect(TokenKind::Semicolon)?;
+            Ok(Statement::Expression(expression, span))
+        }
+    }
+    fn parse_namespace(&mut self, identifier: String, span: Span) -> Result<Statement, BlazeError> {
+        self.expect(TokenKind::Namespace)?;
+        let statements: Vec<Statement> = self.parse_block()?;
+        
+        Ok(Statement::Namespace(identifier, statements, span))
+    }
+    fn parse_struct(&mut self, identifier: String, span: Span) -> Result<Statement, BlazeError> {
+        self.expect(TokenKind::Struct)?;
+        let mut generic_parameters: Vec<Type> = Vec::new();
+        if self.current()?.kind == TokenKind::Less {
+            self.expect(TokenKind::Less)?;
+            generic_parameters.push(self.parse_type()?);
+            while self.current()?.kind == TokenKind::Comma {
+                self.expect(TokenKind::Comma)?;
+                generic_parameters.push(self.parse_type()?);
+            }
+            self.expect_closing_angle()?;
+        }
+        let inherits: Vec<String> = Vec::new();
+        let mut fields: Vec<StructField> = Vec::new();
+        self.expect(TokenKind::OpenBrace)?;
+        
+        while self.current()?.kind != TokenKind::CloseBrace {
+            let span: Span = self.current()?.span;
+            let identifier: String = self.expect(TokenKind::Identifier)?.literal.unwrap();
+            self.expect(TokenKind::Colon)?;
+            let ty: Type = self.parse_type()?;
+            fields.push(StructField {
+                name: identifier,
+                ty,
+                span,
+            });
+            if self.current()?.kind == TokenKind::Comma {
+                self.expect(TokenKind::Comma)?;
+            } else {
+                break;
+            }
+        }
+        
+        self.expect(TokenKind::CloseBrace)?;
+        
+        Ok(Statement::Struct(identifier, generic_parameters, inherits, fields, span))
+    }
+    fn parse_enum(&mut self, identifier: String, span: Span) -> Result<Statement, BlazeError> {
+        self.expect(TokenKind::Enum)?;
+        let mut inner_ty: Option<Type> = None;
+        if self.current()?.kind == TokenKind::OpenParenthesis {
+            self.expect(TokenKind::OpenParenthesis)?;
+            inner_ty = Some(self.parse_type()?);
+            self.expect(TokenKind::CloseParenthesis)?;
+        }
+        let mut variants: Vec<EnumVariant> = Vec::new();
+        self.expect(TokenKind::OpenBrace)?;
+        
+        while self.current()?.kind != TokenKind::CloseBrace {
+            let span: Span = self.current()?.span;
+            let identifier: String = self.expect(TokenKind::Identifier)?.literal.unwrap();
+            if self.current()?.kind == TokenKind::OpenParenthesis {
+                if inner_ty.is_some() {
+                    return Err(BlazeError::ParseError(ErrorCode::InvalidVariant, "variants of an enum with an inner type cannot have a payload".to_string(), span));
+                }
+                let fields: Vec<StructField> = self.parse_payload()?;
+                variants.push(EnumVariant::Payload(identifier, fields, span));
+                if self.current()?.kind == TokenKind::Comma {
+                    self.expect(TokenKind::Comma)?;
+                    continue;
+                }
+                break;
+            }
+            if self.current()?.kind == TokenKind::Comma {
+                self.expect(TokenKind::Comma)?;
+                variants.push(EnumVariant::Unit(identifier, span));
+                continue;
+            } else if self.current()?.kind == TokenKind::CloseBrace {
+                variants.push(EnumVariant::Unit(identifier, span));
+                break;
+            }
+            if self.current()?.kind == TokenKind::Equal && inner_ty.is_none() {
+                return Err(BlazeError::ParseError(ErrorCode::InvalidVariant, "cannot assign value to enum variant without inner type".to_string(), span));
+            }
+            self.expect(TokenKind::Equal)?;
+            let expression: Expression = self.parse_expression()?;
+            variants.push(EnumVariant::Expression(identifier, expression, span));
+            if self.current()?.kind == TokenKind::Comma {
+                self.expect(TokenKind::Comma)?;
+            } else {
+                break;
+            }
+        }
+        
+        self.expect(TokenKind::CloseBrace)?;
+        
+        if let Some(inner_ty) = inner_ty {
+            Ok(Statement::TypedEnum(identifier, inner_ty, variants, span))
+        } else {
+            Ok(Statement::Enum(identifier, variants, span))
+        }
+    }
+    // `Name :: error { First, Second }`.
+    fn parse_error_set(&mut self, identifier: String, span: Span) -> Result<Statement, BlazeError> {
+        self.expect(TokenKind::Error)?;
+        self.expect(TokenKind::OpenBrace)?;
+        let mut errors: Vec<(String, Span)> = Vec::new();
+        while self.current()?.kind != TokenKind::CloseBrace {
+            let token: Token = self.expect(TokenKind::Identifier)?;
+            errors.push((token.literal.unwrap(), token.span));
+            if self.current()?.kind == TokenKind::Comma {
+                self.expect(TokenKind::Comma)?;
+            } else {
+                break;
+            }
+        }
+        self.expect(TokenKind::CloseBrace)?;
+        Ok(Statement::ErrorSet(identifier, errors, span))
+    }
+    fn parse_payload(&mut self) -> Result<Vec<StructField>, BlazeError> {
+        let open: Span = self.expect(TokenKind::OpenParenthesis)?.span;
+        let mut fields: Vec<StructField> = Vec::new();
+        while self.current()?.kind != TokenKind::CloseParenthesis {
+            let span: Span = self.current()?.span;
+            let name: String = self.expect(TokenKind::Identifier)?.literal.unwrap();
+            self.expect(TokenKind::Colon)?;
+            let ty: Type = self.parse_type()?;
+            fields.push(StructField { name, ty, span });
+            if self.current()?.kind == TokenKind::Comma {
+                self.expect(TokenKind::Comma)?;
+            } else {
+                break;
+            }
+        }
+        self.expect(TokenKind::CloseParenthesis)?;
+        if fields.is_empty() {
+            return Err(BlazeError::ParseError(ErrorCode::InvalidVariant, "expected at least one payload field".to_string(), open)
+                .with_help("leave out the parentheses for a variant without a payload"));
+        }
+        Ok(fields)
+    }
+    fn parse_union(&mut self, identifier: String, span: Span) -> Result<Statement, BlazeError> {
+        self.expect(TokenKind::Union)?;
+        let mut types: Vec<Type> = Vec::new();
+        self.expect(TokenKind::OpenBrace)?;
+        
+        while self.current()?.kind != TokenKind::CloseBrace {
+            let ty: Type = self.parse_type()?;
+            if self.current()?.kind == TokenKind::Comma {
+                self.expect(TokenKind::Comma)?;
+                types.push(ty);
+            } else {
+                types.push(ty);
+                break;
+            }
+        }
+        
+        self.expect(TokenKind::CloseBrace)?;
+        
+        Ok(Statement::Union(identifier, types, span))
+    }
+    fn parse_fn(&mut self, identifier: String, span: Span) -> Result<Statement, BlazeError> {
+        self.expect(TokenKind::Fn)?;
+        let mut parameters: Vec<(String, Type, bool, Span)> = Vec::new();
+        let mut returns: Vec<Type> = Vec::new();
+        let mut struct_name: Option<Type> = None;
+        self.expect(TokenKind::OpenParenthesis)?;
+        
+        while self.current()?.kind != TokenKind::CloseParenthesis {
+            let span: Span = self.current()?.span;
+            let comptime: bool = if self.current()?.kind == TokenKind::Comptime {
+                self.expect(TokenKind::Comptime)?;
+                true
+            } else { false };
+            if self.current()?.kind == TokenKind::SelfKeyword {
+                self.expect(TokenKind::SelfKeyword)?;
+                self.expect(TokenKind::Colon)?;
+                let ty: Type = self.parse_type()?;
+                struct_name = Some(ty);
+                if self.current()?.kind != TokenKind::CloseParenthesis {
+                    self.expect(TokenKind::Comma)?;
+                }
+                continue;
+            }
+            let identifier: String = self.expect(TokenKind::Identifier)?.literal.unwrap();
+            self.expect(TokenKind::Colon)?;
+            if self.current()?.kind == TokenKind::Elipsis {
+                let var_args_span: Span = self.current()?.span;
+                self.expect(TokenKind::Elipsis)?;
+                parameters.push((identifier, Type::VarArgs(Box::new(None), var_args_span.clone()), comptime, span));
+                break;
+            }
+            let ty: Type = self.parse_type()?;
+            if self.current()?.kind == TokenKind::Elipsis {
+                let var_args_span: Span = self.current()?.span;
+                self.expect(TokenKind::Elipsis)?;
+                parameters.push((identifier, Type::VarArgs(Box::new(Some(ty)), var_args_span.clone()), comptime, span));
+                break;
+            }
+            if self.current()?.kind != TokenKind::CloseParenthesis {
+                self.expect(TokenKind::Comma)?;
+            }
+            parameters.push((identifier, ty, comptime, span));
+        }
+        
+        self.expect(TokenKind::CloseParenthesis)?;
+        
+        if self.current()?.kind == TokenKind::Arrow {
+            self.expect(TokenKind::Arrow)?;
+            returns.push(self.parse_type()?);
+            while self.current()?.kind == TokenKind::Comma {
+                self.expect(TokenKind::Comma)?;
+                returns.push(self.parse_type()?);
+            }
+        }
+        
+        let statements: Vec<Statement> = if self.external {
+            self.external = false;
+            self.expect(TokenKind::Semicolon)?;
+            Vec::new()
+        } else {
+            self.functions += 1;
+            let statements: Result<Vec<Statement>, BlazeError> = self.parse_block();
+            self.functions -= 1;
+            statements?
+        };
+        
+        if let Some(struct_name) = struct_name {
+            Ok(Statement::StructFunction(struct_name, identifier, parameters, returns, statements, span))
+        } else {
+            Ok(Statement::Function(identifier, parameters, returns, statements, span))
+        }
+    }
+    fn parse_import(&mut self, identifier: String, span: Span) -> Result<Statement, BlazeError> {
+        self.expect(TokenKind::Import)?;
+        let path: String = self.expect(TokenKind::StringLiteral)?.literal.unwrap();
+        
+        Ok(Statement::Import(identifier, path, span))
+    }
+    fn parse_return(&mut self) -> Result<Statement, BlazeError> {
+        let span: Span = self.current()?.span;
+        self.expect(TokenKind::Return)?;
+        if self.current()?.kind == TokenKind::Semicolon {
+            self.expect(TokenKind::Semicolon)?;
+            Ok(Statement::Return(vec![], span))
+        } else {
+            let mut expressions: Vec<Expression> = vec![];
+            expressions.push(self.parse_expression()?);
+            while self.current()?.kind == TokenKind::Comma {
+                self.expect(TokenKind::Comma)?;
+                expressions.push(self.parse_expression()?);
+            }
+            self.expect(TokenKind::Semicolon)?;
+            Ok(Statement::Return(expressions, span))
+        }
+    }
+    fn parse_mut(&mut self) -> Result<Statement, BlazeError> {
+        self.expect(TokenKind::Mut)?;
+        let span: Span = self.current()?.span;
+        let identifier: String = self.expect(TokenKind::Identifier)?.literal.unwrap();
+        let mut ty: Type = Type::AwaitingInference;
+        if self.current()?.kind == TokenKind::ColonEquals {
+            self.expect(TokenKind::ColonEquals)?;
+        } else {
+            self.expect(TokenKind::Colon)?;
+            ty = self.parse_type()?;
+            self.expect(TokenKind::Equal)?;
+        }
+        let value: Expression = self.parse_expression()?;
+        self.expect(TokenKind::Semicolon)?;
+        Ok(Statement::MutableDeclaration(identifier, ty, value, span))
+    }
+    // `a, mut b, _ := f();` declares a variable for each value the function returns, and `_` discards one.
+    fn parse_destructuring(&mut self) -> Result<Statement, BlazeError> {
+        let span: Span = self.current()?.span;
+        let mut names: Vec<(String, bool, Span)> = Vec::new();
+        loop {
+            let mutable: bool = self.current()?.kind == TokenKind::Mut;
+            if mutable {
+                self.expect(TokenKind::Mut)?;
+            }
+            let name: Token = self.expect(TokenKind::Identifier)?;
+            names.push((name.literal.unwrap(), mutable, name.span));
+            if self.current()?.kind != TokenKind::Comma {
+                break;
+            }
+            self.expect(TokenKind::Comma)?;
+        }
+        self.expect(TokenKind::ColonEquals)?;
+        let value: Expression = self.parse_expression()?;
+        self.expect(TokenKind::Semicolon)?;
+        Ok(Statement::Destructuring(names, value, span))
+    }
+    fn is_destructuring(&self) -> bool {
+        let start: usize = self.current + (self.tokens[self.current].kind == TokenKind::Mut) as usize;
+        matches!(
+            (self.tokens.get(start).map(|token| &token.kind), self.tokens.get(start + 1).map(|token| &token.kind)),
+            (Some(TokenKind::Identifier), Some(TokenKind::Comma)),
+        )
+    }
+    fn parse_while(&mut self) -> Result<Statement, BlazeError> {
+        let span: Span = self.current()?.span;
+        self.expect(TokenKind::While)?;
+        let expression: Expression = self.parse_condition()?;
+        let capture: Option<(String, Span)> = self.parse_capture()?;
+        let statements: Vec<Statement> = self.parse_block()?;
+        
+        Ok(Statement::While(expression, capture, statements, span))
+    }
+    // `for name in start..end { ... }` or `for name in array { ... }`.
+    fn parse_for(&mut self) -> Result<Statement, BlazeError> {
+        let span: Span = self.expect(TokenKind::For)?.span;
+        let token: Token = self.expect(TokenKind::Identifier)?;
+        let name: (String, Span) = (token.literal.unwrap(), token.span);
+        self.expect(TokenKind::In)?;
+        let mut iterable: Expression = self.parse_condition()?;
+        if self.current()?.kind == TokenKind::DoubleDot {
+            let range_span: Span = self.expect(TokenKind::DoubleDot)?.span;
+            let end: Expression = self.parse_condition()?;
+            iterable = Expression::Range(Box::new(iterable), Box::new(end), range_span);
+        }
+        let statements: Vec<Statement> = self.parse_block()?;
+        Ok(Statement::For(name, iterable, statements, span))
+    }
+    fn parse_jump(&mut self) -> Result<Statement, BlazeError> {
+        let token: Token = self.current()?;
+        self.current += 1;
+        let mut label: Option<String> = None;
+        if self.current()?.kind == TokenKind::Identifier {
+            label = self.expect(TokenKind::Identifier)?.literal;
+        }
+        self.expect(TokenKind::Semicolon)?;
+        match token.kind {
+            TokenKind::Break => Ok(Statement::Break(label, token.span)),
+            _ => Ok(Statement::Continue(label, token.span)),
+        }
+    }
+    // A label is a name and a colon in front of a loop, like `outer: for ...`.
+    fn is_label(&self) -> bool {
+        matches!(self.tokens.get(self.current + 2).map(|token| &token.kind), Some(TokenKind::For | TokenKind::While))
+    }
+
+    fn parse_if(&mut self) -> Result<Statement, BlazeError> {
+        self.expect(TokenKind::If)?;
+        let span: Span = self.current()?.span;
+        let expression: Expression = self.parse_condition()?;
+        let capture: Option<(String, Span)> = self.parse_capture()?;
+        let if_statements: Vec<Statement> = self.parse_block()?;
+        let mut else_statements: Vec<Statement> = Vec::new();
+        
+        if self.current()?.kind == TokenKind::Else {
+            self.expect(TokenKind::Else)?;
+            else_statements = match self.current()?.kind {
+                TokenKind::If => vec![self.parse_if()?],
+                _ => self.parse_block()?,
+            };
+        }
+        Ok(Statement::If(expression, capture, if_statements, else_statements, span))
+    }
+    fn parse_match(&mut self) -> Result<Statement, BlazeError> {
+        let span: Span = self.expect(TokenKind::Match)?.span;
+        let value: Expression = self.parse_condition()?;
+        let mut arms: Vec<(Pattern, Vec<Statement>, Span)> = Vec::new();
+        self.expect(TokenKind::OpenBrace)?;
+        while self.current()?.kind != TokenKind::CloseBrace {
+            let arm_span: Span = self.current()?.span;
+            let pattern: Pattern = self.parse_pattern()?;
+            self.expect(TokenKind::FatArrow)?;
+            let body: Vec<Statement> = if self.current()?.kind == TokenKind::OpenBrace {
+                self.parse_block()?
+            } else {
+                vec![self.parse_statement()?]
+            };
+            arms.push((pattern, body, arm_span));
+            if self.current()?.kind == TokenKind::Comma {
+                self.expect(TokenKind::Comma)?;
+            }
+        }
+        self.expect(TokenKind::CloseBrace)?;
+        Ok(Statement::Match(value, arms, span))
+    }
+    fn parse_match_expression(&mut self) -> Result<Expression, BlazeError> {
+        let span: Span = self.expect(TokenKind::Match)?.span;
+        let value: Expression = self.parse_condition()?;
+        let mut arms: Vec<(Pattern, Expression, Span)> = Vec::new();
+        self.expect(TokenKind::OpenBrace)?;
+        while self.current()?.kind != TokenKind::CloseBrace {
+            let arm_span: Span = self.current()?.span;
+            let pattern: Pattern = self.parse_pattern()?;
+            self.expect(TokenKind::FatArrow)?;
+            let body: Expression = self.parse_expression()?;
+            arms.push((pattern, body, arm_span));
+            if self.current()?.kind == TokenKind::Comma {
+                self.expect(TokenKind::Comma)?;
+            } else {
+                break;
+            }
+        }
+        self.expect(TokenKind::CloseBrace)?;
+        Ok(Expression::Match(Box::new(value), arms, span))
+    }
+    // `_`, `Enum::Variant` or `Enum::Variant(name, _)`.
+    fn parse_pattern(&mut self) -> Result<Pattern, BlazeError> {
+        let mut span: Span = self.current()?.span;
+        let mut segments: Vec<String> = vec![self.expect(TokenKind::Identifier)?.literal.unwrap()];
+        if segments[0] == "_" && self.current()?.kind != TokenKind::DoubleColon {
+            return Ok(Pattern::Wildcard(span));
+        }
+        while self.current()?.kind == TokenKind::DoubleColon {
+            self.expect(TokenKind::DoubleColon)?;
+            let token: Token = self.expect(TokenKind::Identifier)?;
+            span.end = token.span.end;
+            segments.push(token.literal.unwrap());
+        }
+        let mut bindings: Vec<(String, Span)> = Vec::new();
+        if self.current()?.kind == TokenKind::OpenParenthesis {
+            self.expect(TokenKind::OpenParenthesis)?;
+            while self.current()?.kind != TokenKind::CloseParenthesis {
+                let token: Token = self.expect(TokenKind::Identifier)?;
+                bindings.push((token.literal.unwrap(), token.span));
+                if self.current()?.kind == TokenKind::Comma {
+                    self.expect(TokenKind::Comma)?;
+                } else {
+                    break;
+                }
+            }
+            self.expect(TokenKind::CloseParenthesis)?;
+        }
+        Ok(Pattern::Variant(segments.join("::"), bindings, span))
+    }
+    fn parse_block(&mut self) -> Result<Vec<Statement>, BlazeError> {
+        let mut statements: Vec<Statement> = Vec::new();
+        let open: Span = self.expect(TokenKind::OpenBrace)?.span;
+        
+        while self.current().map_err(|error| error.with_label(open.clone(), "this block is never closed"))?.kind != TokenKind::CloseBrace {
+            let statement: Statement = self.parse_recovering_statement();
+            statements.push(statement);
+        }
+        
+        self.expect(TokenKind::CloseBrace)?;
+        
+        Ok(statements)
+    }
+
+    fn parse_expression(&mut self) -> Result<Expression, BlazeError> {
+        self.parse_binary_operation(0)
+    }
+    // Parses the expression before the block of an `if`, `while` or `match`, where `name {` starts the block
+    // rather than a struct literal unless it is wrapped in parentheses.
+    fn parse_condition(&mut self) -> Result<Expression, BlazeError> {
+        self.with_struct_literals(false, Parser::parse_expression)
+    }
+    fn with_struct_literals(&mut self, allowed: bool, parse: fn(&mut Parser) -> Result<Expression, BlazeError>) -> Result<Expression, BlazeError> {
+        let previous: bool = std::mem::replace(&mut self.struct_literals, allowed);
+        let result: Result<Expression, BlazeError> = parse(self);
+        self.struct_literals = previous;
+        result
+    }
+    fn parse_binary_operation(&mut self, min_precedence: u8) -> Result<Expression, BlazeError> {
+        let span: Span = self.current()?.span;
+        let mut expression: Expression = self.parse_unary()?;
+        while let Some((precedence, right_associative)) = binding_power(&self.current()?.kind) {
+            if precedence < min_precedence || self.is_capture() {
+                break;
+            }
+            let operator: Token = self.current()?.clone();
+            self.advance()?;
+            let next_precedence: u8 = if right_associative { precedence } else { precedence + 1 };
+            if operator.kind == TokenKind::Catch {
+                let capture: Option<(String, Span)> = self.parse_capture()?;
+                let fallback: Expression = self.parse_binary_operation(next_precedence)?;
+                expression = Expression::Catch(Box::new(expression), capture, Box::new(fallback), operator.span);
+                continue;
+            }
+            if operator.kind == TokenKind::Orelse {
+                let fallback: Expression = self.parse_binary_operation(next_precedence)?;
+                expression = Expression::Orelse(Box::new(expression), Box::new(fallback), operator.span);
+                continue;
+            }
+            let right: Expression = self.parse_binary_operation(next_precedence)?;
+            // The span runs from the first token of the left operand to the last one of the right.
+            let end: usize = self.tokens[self.current - 1].span.end;
+            expression = Expression::BinaryOperation(Box::new(expression), operator.kind, Box::new(right), Span { end, ..span.clone() });
+        }
+        Ok(expression)
+    }
+    // In a condition, `|name| {` starts the capture of an `if` rather than a bitwise or.
+    fn is_capture(&self) -> bool {
+        let kinds: Vec<&TokenKind> = self.tokens.iter().skip(self.current).take(4).map(|token| &token.kind).collect();
+        !self.struct_literals && kinds == [&TokenKind::Pipe, &TokenKind::Identifier, &TokenKind::Pipe, &TokenKind::OpenBrace]
+    }
+    // An optional `|name|` that binds the value a `catch` handles or an `if` unwraps.
+    fn parse_capture(&mut self) -> Result<Option<(String, Span)>, BlazeError> {
+        if self.current()?.kind != TokenKind::Pipe {
+            return Ok(None);
+        }
+        self.expect(TokenKind::Pipe)?;
+        let token: Token = self.expect(TokenKind::Identifier)?;
+        self.expect(TokenKind::Pipe)?;
+        Ok(Some((token.literal.unwrap(), token.span)))
+    }
+    fn parse_unary(&mut self) -> Result<Expression, BlazeError> {
+        let span: Span = self.current()?.span;
+        match self.current()?.kind {
+            TokenKind::Ampersand => {
+                self.expect(TokenKind::Ampersand)?;
+                let expression: Expression = self.parse_unary()?;
+                Ok(Expression::AddressOf(Box::new(expression), span))
+            }
+            TokenKind::Asterisk => {
+                self.expect(TokenKind::Asterisk)?;
+                let expression: Expression = self.parse_unary()?;
+                Ok(Expression::Dereference(Box::new(expression), span))
+            }
+            TokenKind::Try => {
+                self.expect(TokenKind::Try)?;
+                let expression: Expression = self.parse_unary()?;
+                Ok(Expression::Try(Box::new(expression), span))
+            }
+            TokenKind::Bang | TokenKind::Minus | TokenKind::Tilde => {
+                let operator: Token = self.current()?.clone();
+                self.advance()?;
+                let expression: Expression = self.parse_unary()?;
+                Ok(Expression::UnaryOperation(operator.kind, Box::new(expression), span))
+            }
+            _ => self.parse_postfix(),
+        }
+    }
+    fn parse_postfix(&mut self) -> Result<Expression, BlazeError> {
+        let span: Span = self.current()?.span;
+        let mut expression: Expression = self.parse_primary()?;
+        loop {
+            match self.current()?.kind {
+                TokenKind::Dot if self.tokens.get(self.current + 1).is_some_and(|token| token.kind == TokenKind::QuestionMark) => {
+                    let dot: Span = self.expect(TokenKind::Dot)?.span;
+                    self.expect(TokenKind::QuestionMark)?;
+                    expression = Expression::Unwrap(Box::new(expression), dot);
+                }
+                TokenKind::Dot => {
+                    self.expect(TokenKind::Dot)?;
+                    let member: Expression = self.parse_primary()?;
+                    expression = Expression::MemberAccess(Box::new(expression), Box::new(member), span.clone());
+                }
+                TokenKind::DoubleColon => {
+                    self.expect(TokenKind::DoubleColon)?;
+                    let member: Expression = self.parse_primary()?;
+                    expression = Expression::StaticMemberAccess(Box::new(expression), Box::new(member), span.clone());
+                }
+                TokenKind::OpenParenthesis => {
+                    self.expect(TokenKind::OpenParenthesis)?;
+                    let mut arguments: Vec<Expression> = Vec::new();
+                    while self.current()?.kind != TokenKind::CloseParenthesis {
+                        arguments.push(self.with_struct_literals(true, Parser::parse_expression)?);
+                        if self.current()?.kind == TokenKind::Comma {
+                            self.expect(TokenKind::Comma)?;
+                        } else {
+                            break;
+                        }
+                    }
+                    self.expect(TokenKind::CloseParenthesis)?;
+                    expression = Expression::Call(Box::new(expression), arguments, span.clone());
+                }
+                TokenKind::OpenBracket => {
+                    self.expect(TokenKind::OpenBracket)?;
+                    let start: Option<Expression> = match self.current()?.kind {
+                        TokenKind::DoubleDot => None,
+                        _ => Some(self.with_struct_literals(true, Parser::parse_expression)?),
+                    };
+                    let slicing: bool = self.current()?.kind == TokenKind::DoubleDot;
+                    expression = match start {
+                        Some(index) if !slicing => {
+                            self.expect(TokenKind::CloseBracket)?;
+                            Expression::ArrayAccess(Box::new(expression), Box::new(index), span.clone())
+                        }
+                        start => {
+                            self.expect(TokenKind::DoubleDot)?;
+                            let end: Option<Expression> = match self.current()?.kind {
+                                TokenKind::CloseBracket => None,
+                                _ => Some(self.with_struct_literals(true, Parser::parse_expression)?),
+                            };
+                            self.expect(TokenKind::CloseBracket)?;
+                            Expression::Slice(Box::new(expression), start.map(Box::new), end.map(Box::new), span.clone())
+                        }
+                    };
+                }
+                _ => break,
+            }
+        }
+        Ok(expression)
+    }
+    fn parse_primary(&mut self) -> Result<Expression, BlazeError> {
+        let span = self.current()?.span;
+        match self.current()?.kind {
+            TokenKind::Identifier => {
+                let identifier: String = self.expect(TokenKind::Identifier)?.literal.unwrap();
+                if self.current()?.kind == TokenKind::OpenBrace && self.struct_literals {
+                    self.expect(TokenKind::OpenBrace)?;
+                    let mut fields: Vec<(Option<String>, Expression, Span)> = Vec::new();
+                    while self.current()?.kind != TokenKind::CloseBrace {
+                        let span: Span = self.current()?.span;
+                        let mut identifier: Option<String> = None;
+                        if self.peek()?.kind == TokenKind::Colon {
+                            identifier = Some(self.expect(TokenKind::Identifier)?.literal.unwrap());
+                            self.expect(TokenKind::Colon)?;
+                        }
+                        let expression: Expression = self.parse_expression()?;
+                        fields.push((identifier, expression, span));
+                        if self.current()?.kind == TokenKind::Comma {
+                            self.expect(TokenKind::Comma)?;
+                        } else {
+                            break;
+                        }
+                    }
+                    
+                    self.expect(TokenKind::CloseBrace)?;
+                    Ok(Expression::StructLiteral(identifier, Vec::new(), fields, span))
+                } else {
+                    Ok(Expression::Identifier(identifier, span))
+                }
+            }
+            TokenKind::IntegerLiteral
+            | TokenKind::HexadecimalLiteral
+            | TokenKind::BinaryLiteral
+            | TokenKind::OctalLiteral
+            | TokenKind::FloatLiteral => self.parse_number(),
+            TokenKind::True => {
+                self.expect(TokenKind::True)?;
+                Ok(Expression::Bool(true, span))
+            }
+            TokenKind::False => {
+                self.expect(TokenKind::False)?;
+                Ok(Expression::Bool(false, span))
+            }
+            TokenKind::CharLiteral => {
+                let literal: String = self.expect(TokenKind::CharLiteral)?.literal.unwrap_or_default();
+                match literal.parse::<char>() {
+                    Ok(character) => Ok(Expression::Char(character, span)),
+                    Err(_) => Err(BlazeError::ParseError(ErrorCode::InvalidCharacterLiteral, format!("invalid character literal '{}'", literal), span)),
+                }
+            }
+            TokenKind::StringLiteral => {
+                let string: String = self.expect(TokenKind::StringLiteral)?.literal.unwrap();
+                Ok(Expression::String(string, span))
+            }
+            TokenKind::SelfKeyword => {
+                self.expect(TokenKind::SelfKeyword)?;
+                Ok(Expression::SelfLiteral(span))
+            }
+            TokenKind::Null => {
+                self.expect(TokenKind::Null)?;
+                Ok(Expression::Null(span))
+            }
+            TokenKind::OpenParenthesis => {
+                self.expect(TokenKind::OpenParenthesis)?;
+                let expression: Expression = self.with_struct_literals(true, Parser::parse_expression)?;
+                self.expect(TokenKind::CloseParenthesis)?;
+                Ok(expression)
+            }
+            TokenKind::OpenBracket => {
+                self.expect(TokenKind::OpenBracket)?;
+                let mut elements: Vec<Expression> = Vec::new();
+                while self.current()?.kind != TokenKind::CloseBracket {
+                    elements.push(self.with_struct_literals(true, Parser::parse_expression)?);
+                    if self.current()?.kind == TokenKind::Comma {
+                        self.expect(TokenKind::Comma)?;
+                    } else {
+                        break;
+                    }
+                }
+                self.expect(TokenKind::CloseBracket)?;
+                Ok(Expression::ArrayLiteral(elements, span))
+            }
+            TokenKind::Match => self.parse_match_expression(),
+            _ => {
+                Err(BlazeError::ParseError(ErrorCode::UnexpectedToken, format!("expected an expression, found {}", self.current()?.description()), span))
+            }
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<Expression, BlazeError> {
+        let token: Token = self.current()?;
+        self.advance()?;
+        let span: Span = token.span.clone();
+        let literal: String = token.literal.unwrap_or_default();
+        let radix: u32 = match token.kind {
+            TokenKind::HexadecimalLiteral => 16,
+            TokenKind::BinaryLiteral => 2,
+            TokenKind::OctalLiteral => 8,
+            _ => 10,
+        };
+        let split: usize = literal.char_indices()
+            .find(|(_, c)| !(c.is_digit(radix) || token.kind == TokenKind::FloatLiteral && ".eE+-".contains(*c)))
+            .map(|(index, _)| index)
+            .unwrap_or(literal.len());
+        let (digits, suffix) = literal.split_at(split);
+        if digits.is_empty() {
+            return Err(BlazeError::ParseError(ErrorCode::InvalidNumberLiteral, "expected digits in number literal".to_string(), span));
+        }
+        let suffix: Option<Type> = match suffix {
+            "" => None,
+            "i8" => Some(Type::I8(span.clone())),
+            "i16" => Some(Type::I16(span.clone())),
+            "i32" => Some(Type::I32(span.clone())),
+            "i64" => Some(Type::I64(span.clone())),
+            "u8" => Some(Type::U8(span.clone())),
+            "u16" => Some(Type::U16(span.clone())),
+            "u32" => Some(Type::U32(span.clone())),
+            "u64" => Some(Type::U64(span.clone())),
+            "f32" => Some(Type::F32(span.clone())),
+            "f64" => Some(Type::F64(span.clone())),
+            suffix if suffix.starts_with(|c: char| c.is_ascii_digit()) => {
+                return Err(BlazeError::ParseError(ErrorCode::InvalidNumberLiteral, format!("invalid digit '{}' in base {} literal", &suffix[..1], radix), span));
+            }
+            suffix => return Err(BlazeError::ParseError(ErrorCode::InvalidNumberLiteral, format!("invalid suffix '{}' for number literal", suffix), span)),
+        };
+
+        if token.kind == TokenKind::FloatLiteral || matches!(suffix, Some(Type::F32(_)) | Some(Type::F64(_))) {
+            if radix != 10 {
+                return Err(BlazeError::ParseError(ErrorCode::InvalidNumberLiteral, format!("base {} literals cannot be floats", radix), span));
+            }
+            if suffix.as_ref().is_some_and(|suffix| suffix.is_integer()) {
+                return Err(BlazeError::ParseError(ErrorCode::InvalidNumberLiteral, format!("invalid suffix '{}' for float literal", literal.split_at(split).1), span));
+            }
+            let value: f64 = digits.parse::<f64>()
+                .map_err(|_| BlazeError::ParseError(ErrorCode::InvalidNumberLiteral, format!("invalid float literal '{}'", digits), span.clone()))?;
+            Ok(Expression::Float(value, suffix, span))
+        } else {
+            let value: u64 = u64::from_str_radix(digits, radix)
+                .map_err(|_| BlazeError::ParseError(ErrorCode::InvalidNumberLiteral, "integer literal is too large".to_string(), span.clone()))?;
+            Ok(Expression::Integer(value, suffix, span))
+        }
+    }
+
+    fn parse_type(&mut self) -> Result<Type, BlazeError> {
+        let span: Span = self.current()?.span;
+        let t: Type = match self.current()?.kind.clone() {
+            TokenKind::I8 => {
+                self.expect(TokenKind::I8)?;
+                Type::I8(span.clone())
+            }
+            TokenKind::I16 => {
+                self.expect(TokenKind::I16)?;
+                Type::I16(span.clone())
+            }
+            TokenKind::I32 => {
+                self.expect(TokenKind::I32)?;
+                Type::I32(span.clone())
+            }
+            TokenKind::I64 => {
+                self.expect(TokenKind::I64)?;
+                Type::I64(span.clone())
+            }
+            TokenKind::U8 => {
+                self.expect(TokenKind::U8)?;
+                Type::U8(span.clone())
+            }
+            TokenKind::U16 => {
+                self.expect(TokenKind::U16)?;
+                Type::U16(span.clone())
+            }
+            TokenKind::U32 => {
+                self.expect(TokenKind::U32)?;
+                Type::U32(span.clone())
+            }
+            TokenKind::U64 => {
+                self.expect(TokenKind::U64)?;
+                Type::U64(span.clone())
+            }
+            TokenKind::F32 => {
+                self.expect(TokenKind::F32)?;
+                Type::F32(span.clone())
+            }
+            TokenKind::F64 => {
+                self.expect(TokenKind::F64)?;
+                Type::F64(span.clone())
+            }
+            TokenKind::Bool => {
+                self.expect(TokenKind::Bool)?;
+                Type::Bool(span.clone())
+            }
+            TokenKind::Char => {
+                self.expect(TokenKind::Char)?;
+                Type::Char(span.clone())
+            }
+            TokenKind::Str => {
+                self.expect(TokenKind::Str)?;
+                Type::Str(span.clone())
+            }
+            TokenKind::Void => {
+                self.expect(TokenKind::Void)?;
+                Type::Void(span.clone())
+            }
+            TokenKind::Type => {
+                self.expect(TokenKind::Type)?;
+                Type::Type(span.clone())
+            }
+            TokenKind::Asterisk => {
+                self.expect(TokenKind::Asterisk)?;
+                let t: Type = self.parse_type()?;
+                Type::Pointer(Box::new(t), span.clone())
+            }
+            TokenKind::OpenBracket => {
+                self.expect(TokenKind::OpenBracket)?;
+                if self.current()?.kind == TokenKind::CloseBracket {
+                    self.expect(TokenKind::CloseBracket)?;
+                    let t: Type = self.parse_type()?;
+                    Type::Array(Box::new(t), span.clone())
+                } else {
+                    let length: usize = self.parse_array_length()?;
+                    self.expect(TokenKind::CloseBracket)?;
+                    let t: Type = self.parse_type()?;
+                    Type::FixedArray(Box::new(t), length, span.clone())
+                }
+            }
+            TokenKind::QuestionMark => {
+                self.expect(TokenKind::QuestionMark)?;
+                let t: Type = self.parse_type()?;
+                Type::Optional(Box::new(t), span.clone())
+            }
+            TokenKind::Error => {
+                self.expect(TokenKind::Error)?;
+                Type::ErrorSet(None, span.clone())
+            }
+            TokenKind::Bang => {
+                self.expect(TokenKind::Bang)?;
+                let t: Type = self.parse_type()?;
+                Type::ErrorUnion(Box::new(Type::ErrorSet(None, span.clone())), Box::new(t), span.clone())
+            }
+            TokenKind::Dollar => {
+                self.expect(TokenKind::Dollar)?;
+                let identifier: String = self.expect(TokenKind::Identifier)?.literal.unwrap();
+                Type::Generic(identifier, span.clone())
+            }
+            _ => {
+                let mut identifier: String = self.expect(TokenKind::Identifier)?.literal.unwrap();
+                let mut span: Span = span.clone();
+                while self.current()?.kind == TokenKind::DoubleColon && self.peek()?.kind == TokenKind::Identifier {
+                    self.expect(TokenKind::DoubleColon)?;
+                    let segment: Token = self.expect(TokenKind::Identifier)?;
+                    identifier = format!("{}::{}", identifier, segment.literal.unwrap());
+                    span.end = segment.span.end;
+                }
+                if self.current()?.kind == TokenKind::Less {
+                    self.expect(TokenKind::Less)?;
+                    let mut types: Vec<Type> = vec![self.parse_type()?];
+                    while self.current()?.kind == TokenKind::Comma {
+                        self.expect(TokenKind::Comma)?;
+                        types.push(self.parse_type()?);
+                    }
+                    self.expect_closing_angle()?;
+                    return Ok(Type::GenericInstance(identifier, types, span));
+                }
+                if self.current()?.kind == TokenKind::Bang {
+                    self.expect(TokenKind::Bang)?;
+                    let t: Type = self.parse_type()?;
+                    return Ok(Type::ErrorUnion(Box::new(Type::Unknown(identifier, span.clone())), Box::new(t), span));
+                }
+                Type::Unknown(identifier, span.clone())
+            }
+        };
+        Ok(t)
+    }
+
+    // The `N` of a fixed-size array type `[N]T`.
+    fn parse_array_length(&mut self) -> Result<usize, BlazeError> {
+        let token: Token = self.current()?;
+        if !matches!(token.kind, TokenKind::IntegerLiteral | TokenKind::HexadecimalLiteral | TokenKind::BinaryLiteral | TokenKind::OctalLiteral) {
+            return Err(BlazeError::ParseError(ErrorCode::InvalidArrayLength, format!("expected an array length, found {}", token.description()), token.span));
+        }
+        match self.parse_number()? {
+            Expression::Integer(0, None, span) => Err(BlazeError::ParseError(ErrorCode::InvalidArrayLength, "an array cannot have a length of zero".to_string(), span)),
+            Expression::Integer(length, None, _) => Ok(length as usize),
+            length => Err(BlazeError::ParseError(ErrorCode::InvalidArrayLength, "an array length cannot have a type suffix".to_string(), length.span())),
+        }
+    }
+
+    // Whether the current `name ::` declares `name`. Outside of functions every statement is a declaration, while in
+    // a function body a name after the `::` continues a path like `math::add`.
+    fn is_declaration_start(&self) -> bool {
+        match (self.tokens.get(self.current), self.tokens.get(self.current + 1)) {
+            (Some(name), Some(colons)) if name.kind == TokenKind::Identifier && colons.kind == TokenKind::DoubleColon => {
+                self.functions == 0 || self.tokens.get(self.current + 2).is_none_or(|next| next.kind != TokenKind::Identifier)
+            }
+            _ => false,
+        }
+    }
+    fn peek(&mut self) -> Result<Token, BlazeError> {
+        if self.current + 1 >= self.tokens.len() {
+            return Err(BlazeError::ParseError(ErrorCode::UnexpectedEndOfFile, "unexpected end of file".to_string(), self.tokens[self.current].span.clone()));
+        }
+        Ok(self.tokens[self.current + 1].clone())
+    }
+    fn expect(&mut self, kind: TokenKind) -> Result<Token, BlazeError> {
+        let token: Token = self.current()?;
+        if token.kind != kind {
+            let error: BlazeError = BlazeError::ParseError(ErrorCode::UnexpectedToken, format!("expected {}, found {}", kind.description(), token.description()), token.span.clone());
+            if kind == TokenKind::Semicolon && self.current > 0 {
+                let previous: Span = self.tokens[self.current - 1].span.clone();
+                let end: Span = Span { file: previous.file, start: previous.end, end: previous.end };
+                return Err(error.with_suggestion(end, ";", "add a semicolon"));
+            }
+            return Err(error);
+        }
+        self.advance()?;
+        Ok(token)
+    }
+    // Closes a list of generic arguments. The lexer reads the end of `Box<Box<i32>>` as a shift, so its first
+    // `>` closes this list and the rest of the token is left for the enclosing one.
+    fn expect_closing_angle(&mut self) -> Result<(), BlazeError> {
+        let rest: TokenKind = match self.current()?.kind {
+            TokenKind::GreaterGreater => TokenKind::Greater,
+            TokenKind::GreaterGreaterEquals => TokenKind::GreaterEqual,
+            TokenKind::GreaterEqual => TokenKind::Equal,
+            _ => return self.expect(TokenKind::Greater).map(|_| ()),
+        };
+        let token: &mut Token = &mut self.tokens[self.current];
+        token.kind = rest;
+        token.span.start += 1;
+        Ok(())
+    }
+    fn current(&mut self) -> Result<Token, BlazeError> {
+        if self.current >= self.tokens.len() {
+            return Err(BlazeError::ParseError(ErrorCode::UnexpectedEndOfFile, "unexpected end of file".to_string(), self.tokens[self.current - 1].span.clone()));
+        }
+        let curr_token: Token = self.tokens[self.current].clone();
+        if self.current < self.tokens.len() {
+            return Ok(self.tokens[self.current].clone());
+        }
+        Err(BlazeError::ParseError(ErrorCode::UnexpectedEndOfFile, "unexpected end of file".to_string(), curr_token.span.clone()))
+    }
+    fn advance(&mut self) -> Result<(), BlazeError> {
+        if self.current >= self.tokens.len() {
+            return Err(BlazeError::ParseError(ErrorCode::UnexpectedEndOfFile, "unexpected end of file".to_string(), self.tokens[self.current - 1].span.clone()));
+        }
+        self.current += 1;
+        Ok(())
+    }
+}
+
+fn is_declaration(statement: &Statement) -> bool {
+    match statement {
+        Statement::Attribute(_, _, statement, _) => is_declaration(statement),
+        _ => !matches!(statement, Statement::Return(..) | Statement::While(..) | Statement::For(..) | Statement::Break(..) | Statement::Continue(..)
+            | Statement::Labeled(..) | Statement::If(..) | Statement::Match(..) | Statement::Block(..) | Statement::Defer(..)
+            | Statement::Expression(..)),
+    }
+}
+
+// Binding power of the binary operators, from loosest to tightest:
+//
+//     1   = += -= *= /= %= &= |= ^= <<= >>=   assignment       right
+//     2   ||                                  logical or       left
+//     3   &&                                  logical and      left
+//     4   == !=                               equality         left
+//     5   < <= > >=                           comparison       left
+//     6   catch orelse                        fallback         left
+//     7   |                                   bitwise or       left
+//     8   ^                                   bitwise xor      left
+//     9   &                                   bitwise and      left
+//     10  << >>                               shift            left
+//     11  + -                                 additive         left
+//     12  * / %                               multiplicative   left
+//
+// Unary operators (& * - ! ~ try) bind tighter than any of these, and member
+// access (. ::), unwrapping (.?), calls, indexing and slicing ([i] [lo..hi]) bind tightest of all.
+fn binding_power(kind: &TokenKind) -> Option<(u8, bool)> {
+    match kind {
+        TokenKind::Equal
+        | TokenKind::PlusEquals
+        | TokenKind::MinusEquals
+        | TokenKind::AsteriskEquals
+        | TokenKind::SlashEquals
+        | TokenKind::PercentEquals
+        | TokenKind::AmpersandEquals
+        | TokenKind::PipeEquals
+        | TokenKind::CaretEquals
+        | TokenKind::LessLessEquals
+        | TokenKind::GreaterGreaterEquals => Some((1, true)),
+        TokenKind::PipePipe => Some((2, false)),
+        TokenKind::AmpersandAmpersand => Some((3, false)),
+        TokenKind::EqualEqual | TokenKind::BangEqual => Some((4, false)),
+        TokenKind::Less | TokenKind::LessEqual | TokenKind::Greater | TokenKind::GreaterEqual => Some((5, false)),
+        TokenKind::Catch | TokenKind::Orelse => Some((6, false)),
+        TokenKind::Pipe => Some((7, false)),
+        TokenKind::Caret => Some((8, false)),
+        TokenKind::Ampersand => Some((9, false)),
+        TokenKind::LessLess | TokenKind::GreaterGreater => Some((10, false)),
+        TokenKind::Plus | TokenKind::Minus => Some((11, false)),
+        TokenKind::Asterisk | TokenKind::Slash | TokenKind::Percent => Some((12, false)),
+        _ => None,
+    }
 }
\ No newline at end of file
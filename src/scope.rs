@@ -1,79 +1,199 @@
-use std::collections::HashMap;
-use crate::{ast::{Expression, Type, Statement, StructField, EnumVariant}, span::Span};
-
-#[derive(Debug, Clone)]
-pub struct Scope {
-    pub parent: Box<Option<Scope>>,
-    pub namespaces: HashMap<String, Vec<Statement>>,
-    pub structs: HashMap<String, Vec<StructField>>,
-    pub enums: HashMap<String, Vec<EnumVariant>>,
-    pub unions: HashMap<String, Vec<Type>>,
-    pub functions: HashMap<String, (Vec<(String, Type)>, Vec<Type>)>,
-    pub consts: HashMap<String, (Type, Expression)>,
-    pub variables: HashMap<String, (Type, Expression)>,
-    pub mutables: HashMap<String, (Type, Expression)>,
-    pub parameters: HashMap<String, (Type, bool, Span)>,
-}
-
-impl Scope {
-    pub fn new(parent: Option<Scope>) -> Scope {
-        Scope {
-            parent: Box::new(parent),
-            namespaces: HashMap::new(),
-            structs: HashMap::new(),
-            enums: HashMap::new(),
-            unions: HashMap::new(),
-            functions: HashMap::new(),
-            consts: HashMap::new(),
-            variables: HashMap::new(),
-            mutables: HashMap::new(),
-            parameters: HashMap::new(),
-        }
-    }
-
-    pub fn locate(&self, identifier: String, span: Span) -> Option<(Type, Expression)> {
-        for (const_id, (ty, expr)) in self.consts.clone() {
-            if const_id == identifier {
-                return Some((ty, expr));
-            }
-        }
-        for (var_id, (ty, expr)) in self.variables.clone() {
-            if var_id == identifier {
-                return Some((ty, expr));
-            }
-        }
-        for (mut_id, (ty, expr)) in self.mutables.clone() {
-            if mut_id == identifier {
-                return Some((ty, expr));
-            }
-        }
-        for (param_id, (ty, _, _)) in self.parameters.clone() {
-            if param_id == identifier {
-                return Some((ty, Expression::Identifier(identifier, span)));
-            }
-        }
-        if let Some(parent) = &*self.parent {
-            return parent.locate(identifier, span);
-        }
-        None
-    }
-
-    pub fn get_type(&self, identifier: String, span: Span) -> Option<Type> {
-        for (struct_id, _) in self.structs.clone() {
-            if struct_id == identifier {
-                return Some(Type::Struct(struct_id, span));
-            }
-        }
-        for (enum_id, _) in self.enums.clone() {
-            if enum_id == identifier {
-                return Some(Type::Enum(enum_id, span));
-            }
-        }
-        for (union_id, _) in self.unions.clone() {
-            if union_id == identifier {
-                return Some(Type::Union(union_id, span));
-            }
-        }
-        None
-    }
+use std::collections::HashMap;
+use crate::{ast::{Expression, Type, StructField, EnumVariant}, lint::LintLevels, span::Span};
+
+pub type Signature = (Vec<(String, Type)>, Vec<Type>);
+
+#[derive(Debug, Clone)]
+pub struct Binding {
+    pub span: Span,
+    pub parameter: bool,
+    pub mutable: bool,
+    pub used: bool,
+    pub mutated: bool,
+    pub lints: LintLevels,
+}
+
+#[derive(Debug, Clone)]
+pub struct Scope {
+    pub parent: Box<Option<Scope>>,
+    pub path: Vec<String>,
+    pub namespaces: HashMap<String, Scope>,
+    pub aliases: HashMap<String, String>,
+    pub structs: HashMap<String, (Vec<Type>, Vec<StructField>)>,
+    pub enums: HashMap<String, (Option<Type>, Vec<EnumVariant>)>,
+    pub unions: HashMap<String, Vec<Type>>,
+    pub errors: HashMap<String, Vec<(String, Span)>>,
+    pub functions: HashMap<String, Signature>,
+    pub consts: HashMap<String, (Type, Expression)>,
+    pub variables: HashMap<String, (Type, Expression)>,
+    pub mutables: HashMap<String, (Type, Expression)>,
+    pub parameters: HashMap<String, (Type, bool, Span)>,
+    pub narrowed: HashMap<String, Type>,
+    pub bindings: HashMap<String, Binding>,
+}
+
+impl Scope {
+    pub fn new(parent: Option<Scope>) -> Scope {
+        Scope {
+            path: parent.as_ref().map(|parent| parent.path.clone()).unwrap_or_default(),
+            parent: Box::new(parent),
+            namespaces: HashMap::new(),
+            aliases: HashMap::new(),
+            structs: HashMap::new(),
+            enums: HashMap::new(),
+            unions: HashMap::new(),
+            errors: HashMap::new(),
+            functions: HashMap::new(),
+            consts: HashMap::new(),
+            variables: HashMap::new(),
+            mutables: HashMap::new(),
+            parameters: HashMap::new(),
+            narrowed: HashMap::new(),
+            bindings: HashMap::new(),
+        }
+    }
+
+    pub fn push(&mut self) {
+        let parent: Scope = std::mem::replace(self, Scope::new(None));
+        *self = Scope::new(Some(parent));
+    }
+
+    pub fn pop(&mut self) {
+        if let Some(parent) = self.parent.take() {
+            *self = parent;
+        }
+    }
+
+    pub fn qualify(&self, name: &str) -> String {
+        let mut segments: Vec<String> = self.path.clone();
+        segments.push(name.to_string());
+        segments.join("::")
+    }
+
+    pub fn contains(&self, identifier: &str) -> bool {
+        self.namespaces.contains_key(identifier)
+            || self.aliases.contains_key(identifier)
+            || self.structs.contains_key(identifier)
+            || self.enums.contains_key(identifier)
+            || self.unions.contains_key(identifier)
+            || self.errors.contains_key(identifier)
+            || self.functions.contains_key(identifier)
+            || self.consts.contains_key(identifier)
+            || self.variables.contains_key(identifier)
+            || self.mutables.contains_key(identifier)
+            || self.parameters.contains_key(identifier)
+    }
+
+    pub fn locate(&self, identifier: String, span: Span) -> Option<(Type, Expression)> {
+        for (const_id, (ty, expr)) in self.consts.clone() {
+            if const_id == identifier {
+                return Some((ty, expr));
+            }
+        }
+        for (var_id, (ty, expr)) in self.variables.clone() {
+            if var_id == identifier {
+                return Some((ty, expr));
+            }
+        }
+        for (mut_id, (ty, expr)) in self.mutables.clone() {
+            if mut_id == identifier {
+                return Some((ty, expr));
+            }
+        }
+        for (param_id, (ty, _, _)) in self.parameters.clone() {
+            if param_id == identifier {
+                return Some((ty, Expression::Identifier(identifier, span)));
+            }
+        }
+        if let Some(ty) = self.narrowed.get(&identifier) {
+            return Some((ty.clone(), Expression::Identifier(identifier, span)));
+        }
+        if let Some(parent) = &*self.parent {
+            return parent.locate(identifier, span);
+        }
+        None
+    }
+
+    // Whether the name is an optional that a condition has checked against `null`, rather than a variable declared
+    // in a scope nested in that of the condition.
+    pub fn is_narrowed(&self, identifier: &str) -> bool {
+        if self.consts.contains_key(identifier)
+            || self.variables.contains_key(identifier)
+            || self.mutables.contains_key(identifier)
+            || self.parameters.contains_key(identifier) {
+            return false;
+        }
+        if self.narrowed.contains_key(identifier) {
+            return true;
+        }
+        match &*self.parent {
+            Some(parent) => parent.is_narrowed(identifier),
+            None => false,
+        }
+    }
+
+    // Stops treating the name as checked against `null`, in every scope up to the one that declares it.
+    pub fn unnarrow(&mut self, identifier: &str) {
+        if self.consts.contains_key(identifier)
+            || self.variables.contains_key(identifier)
+            || self.mutables.contains_key(identifier)
+            || self.parameters.contains_key(identifier) {
+            return;
+        }
+        self.narrowed.remove(identifier);
+        if let Some(parent) = &mut *self.parent {
+            parent.unnarrow(identifier);
+        }
+    }
+
+    pub fn binding(&self, identifier: &str) -> Option<&Binding> {
+        match self.bindings.get(identifier) {
+            Some(binding) => Some(binding),
+            None => self.parent.as_ref().as_ref().and_then(|parent| parent.binding(identifier)),
+        }
+    }
+
+    pub fn binding_mut(&mut self, identifier: &str) -> Option<&mut Binding> {
+        if self.bindings.contains_key(identifier) {
+            return self.bindings.get_mut(identifier);
+        }
+        self.parent.as_mut().as_mut().and_then(|parent| parent.binding_mut(identifier))
+    }
+
+    pub fn is_mutable(&self, identifier: &str) -> Option<bool> {
+        if self.mutables.contains_key(identifier) {
+            return Some(true);
+        }
+        if self.consts.contains_key(identifier)
+            || self.variables.contains_key(identifier)
+            || self.parameters.contains_key(identifier) {
+            return Some(false);
+        }
+        match &*self.parent {
+            Some(parent) => parent.is_mutable(identifier),
+            None => None,
+        }
+    }
+
+    pub fn get_type(&self, identifier: String, span: Span) -> Option<Type> {
+        for (struct_id, _) in self.structs.clone() {
+            if struct_id == identifier {
+                return Some(Type::Struct(self.qualify(&struct_id), span));
+            }
+        }
+        for (enum_id, _) in self.enums.clone() {
+            if enum_id == identifier {
+                return Some(Type::Enum(self.qualify(&enum_id), span));
+            }
+        }
+        for (union_id, _) in self.unions.clone() {
+            if union_id == identifier {
+                return Some(Type::Union(self.qualify(&union_id), span));
+            }
+        }
+        if self.errors.contains_key(&identifier) {
+            return Some(Type::ErrorSet(Some(self.qualify(&identifier)), span));
+        }
+        None
+    }
 }
\ No newline at end of file
@@ -1,6 +1,8 @@
-#[derive(Debug, Clone, PartialEq)]
-pub struct Span {
-    pub filename: String,
-    pub start: usize,
-    pub end: usize,
+pub type FileId = usize;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Span {
+    pub file: FileId,
+    pub start: usize,
+    pub end: usize,
 }
\ No newline at end of file
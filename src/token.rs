@@ -1,100 +1,253 @@
-use crate::span::Span;
-
-#[derive(Debug, Clone)] pub struct Token {
-    pub kind: TokenKind,
-    pub literal: Option<String>,
-    pub span: Span,
-}
-
-#[derive(Debug, Clone, PartialEq)] pub enum TokenKind {
-    // Keywords
-    Namespace,
-    Fn,
-    Return,
-    Enum,
-    Union,
-    Struct,
-    SelfKeyword,
-    While,
-    Mut,
-    If,
-    Else,
-    Import,
-    Comptime,
-    Try,
-    Null,
-
-    // Types
-    I8,
-    I16,
-    I32,
-    I64,
-    U8,
-    U16,
-    U32,
-    U64,
-    F32,
-    F64,
-    Char,
-    Bool,
-    Void,
-    Type,
-
-    // Literals
-    Identifier,
-    StringLiteral,
-    CharLiteral,
-    IntegerLiteral,
-    FloatLiteral,
-    HexadecimalLiteral,
-    BinaryLiteral,
-    OctalLiteral,
-
-    // Operators
-    Equal,
-    EqualEqual,
-    BangEqual,
-    Greater,
-    GreaterEqual,
-    Less,
-    LessEqual,
-
-    // Punctuation
-    OpenParenthesis,
-    CloseParenthesis,
-    OpenBrace,
-    CloseBrace,
-    OpenBracket,
-    CloseBracket,
-    Comma,
-    Dot,
-    DoubleDot,
-    Elipsis, // ...
-    Colon,
-    DoubleColon,
-    Semicolon,
-    Arrow,
-    QuestionMark,
-    Bang,
-    Ampersand,
-    Dollar,
-
-    // Arithmetic
-    Plus,
-    Minus,
-    Asterisk,
-    Slash,
-    Percent,
-
-    // Assignment
-    ColonEquals,
-    PlusEquals,
-    MinusEquals,
-    AsteriskEquals,
-    SlashEquals,
-    PercentEquals,
-
-    // Misc
-    // Newline,
-    EndOfFile,
+use crate::span::Span;
+
+#[derive(Debug, Clone)] pub struct Token {
+    pub kind: TokenKind,
+    pub literal: Option<String>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)] pub enum TokenKind {
+    // Keywords
+    Namespace,
+    Fn,
+    Return,
+    Enum,
+    Error,
+    Union,
+    Struct,
+    SelfKeyword,
+    While,
+    For,
+    In,
+    Break,
+    Continue,
+    Defer,
+    Match,
+    Mut,
+    If,
+    Else,
+    Import,
+    Comptime,
+    Try,
+    Catch,
+    Orelse,
+    Null,
+    True,
+    False,
+
+    // Types
+    I8,
+    I16,
+    I32,
+    I64,
+    U8,
+    U16,
+    U32,
+    U64,
+    F32,
+    F64,
+    Char,
+    Str,
+    Bool,
+    Void,
+    Type,
+
+    // Literals
+    Identifier,
+    StringLiteral,
+    CharLiteral,
+    IntegerLiteral,
+    FloatLiteral,
+    HexadecimalLiteral,
+    BinaryLiteral,
+    OctalLiteral,
+
+    // Operators
+    Equal,
+    EqualEqual,
+    BangEqual,
+    Greater,
+    GreaterEqual,
+    Less,
+    LessEqual,
+
+    // Punctuation
+    OpenParenthesis,
+    CloseParenthesis,
+    OpenBrace,
+    CloseBrace,
+    OpenBracket,
+    CloseBracket,
+    Comma,
+    Dot,
+    DoubleDot,
+    Elipsis, // ...
+    Colon,
+    DoubleColon,
+    Semicolon,
+    Arrow,
+    FatArrow,
+    QuestionMark,
+    Bang,
+    Ampersand,
+    Dollar,
+    Hash,
+
+    // Arithmetic
+    Plus,
+    Minus,
+    Asterisk,
+    Slash,
+    Percent,
+
+    // Logical and bitwise
+    AmpersandAmpersand,
+    PipePipe,
+    Pipe,
+    Caret,
+    Tilde,
+    LessLess,
+    GreaterGreater,
+
+    // Assignment
+    ColonEquals,
+    PlusEquals,
+    MinusEquals,
+    AsteriskEquals,
+    SlashEquals,
+    PercentEquals,
+    AmpersandEquals,
+    PipeEquals,
+    CaretEquals,
+    LessLessEquals,
+    GreaterGreaterEquals,
+
+    // Misc
+    DocComment,
+    // Newline,
+    EndOfFile,
+}
+impl TokenKind {
+    // How the token is written, or what it is for the tokens that are not always written the same way.
+    pub fn spelling(&self) -> &'static str {
+        match self {
+            TokenKind::Namespace => "namespace",
+            TokenKind::Fn => "fn",
+            TokenKind::Return => "return",
+            TokenKind::Enum => "enum",
+            TokenKind::Error => "error",
+            TokenKind::Union => "union",
+            TokenKind::Struct => "struct",
+            TokenKind::SelfKeyword => "self",
+            TokenKind::While => "while",
+            TokenKind::For => "for",
+            TokenKind::In => "in",
+            TokenKind::Break => "break",
+            TokenKind::Continue => "continue",
+            TokenKind::Defer => "defer",
+            TokenKind::Match => "match",
+            TokenKind::Mut => "mut",
+            TokenKind::If => "if",
+            TokenKind::Else => "else",
+            TokenKind::Import => "import",
+            TokenKind::Comptime => "comptime",
+            TokenKind::Try => "try",
+            TokenKind::Catch => "catch",
+            TokenKind::Orelse => "orelse",
+            TokenKind::Null => "null",
+            TokenKind::True => "true",
+            TokenKind::False => "false",
+            TokenKind::I8 => "i8",
+            TokenKind::I16 => "i16",
+            TokenKind::I32 => "i32",
+            TokenKind::I64 => "i64",
+            TokenKind::U8 => "u8",
+            TokenKind::U16 => "u16",
+            TokenKind::U32 => "u32",
+            TokenKind::U64 => "u64",
+            TokenKind::F32 => "f32",
+            TokenKind::F64 => "f64",
+            TokenKind::Char => "char",
+            TokenKind::Str => "str",
+            TokenKind::Bool => "bool",
+            TokenKind::Void => "void",
+            TokenKind::Type => "type",
+            TokenKind::Identifier => "a name",
+            TokenKind::StringLiteral => "a string",
+            TokenKind::CharLiteral => "a character",
+            TokenKind::IntegerLiteral | TokenKind::FloatLiteral | TokenKind::HexadecimalLiteral
+            | TokenKind::BinaryLiteral | TokenKind::OctalLiteral => "a number",
+            TokenKind::Equal => "=",
+            TokenKind::EqualEqual => "==",
+            TokenKind::BangEqual => "!=",
+            TokenKind::Greater => ">",
+            TokenKind::GreaterEqual => ">=",
+            TokenKind::Less => "<",
+            TokenKind::LessEqual => "<=",
+            TokenKind::OpenParenthesis => "(",
+            TokenKind::CloseParenthesis => ")",
+            TokenKind::OpenBrace => "{",
+            TokenKind::CloseBrace => "}",
+            TokenKind::OpenBracket => "[",
+            TokenKind::CloseBracket => "]",
+            TokenKind::Comma => ",",
+            TokenKind::Dot => ".",
+            TokenKind::DoubleDot => "..",
+            TokenKind::Elipsis => "...",
+            TokenKind::Colon => ":",
+            TokenKind::DoubleColon => "::",
+            TokenKind::Semicolon => ";",
+            TokenKind::Arrow => "->",
+            TokenKind::FatArrow => "=>",
+            TokenKind::QuestionMark => "?",
+            TokenKind::Bang => "!",
+            TokenKind::Ampersand => "&",
+            TokenKind::Dollar => "$",
+            TokenKind::Hash => "#",
+            TokenKind::Plus => "+",
+            TokenKind::Minus => "-",
+            TokenKind::Asterisk => "*",
+            TokenKind::Slash => "/",
+            TokenKind::Percent => "%",
+            TokenKind::AmpersandAmpersand => "&&",
+            TokenKind::PipePipe => "||",
+            TokenKind::Pipe => "|",
+            TokenKind::Caret => "^",
+            TokenKind::Tilde => "~",
+            TokenKind::LessLess => "<<",
+            TokenKind::GreaterGreater => ">>",
+            TokenKind::ColonEquals => ":=",
+            TokenKind::PlusEquals => "+=",
+            TokenKind::MinusEquals => "-=",
+            TokenKind::AsteriskEquals => "*=",
+            TokenKind::SlashEquals => "/=",
+            TokenKind::PercentEquals => "%=",
+            TokenKind::AmpersandEquals => "&=",
+            TokenKind::PipeEquals => "|=",
+            TokenKind::CaretEquals => "^=",
+            TokenKind::LessLessEquals => "<<=",
+            TokenKind::GreaterGreaterEquals => ">>=",
+            TokenKind::DocComment => "a doc comment",
+            TokenKind::EndOfFile => "the end of the file",
+        }
+    }
+    // The token as diagnostics mention it, like `'}'` or `a name`.
+    pub fn description(&self) -> String {
+        match self {
+            TokenKind::Identifier | TokenKind::StringLiteral | TokenKind::CharLiteral | TokenKind::IntegerLiteral
+            | TokenKind::FloatLiteral | TokenKind::HexadecimalLiteral | TokenKind::BinaryLiteral | TokenKind::OctalLiteral
+            | TokenKind::DocComment | TokenKind::EndOfFile => self.spelling().to_string(),
+            kind => format!("'{}'", kind.spelling()),
+        }
+    }
+}
+
+impl Token {
+    // Like `TokenKind::description`, with the name or number as it is written, like `'count'`.
+    pub fn description(&self) -> String {
+        match (&self.kind, &self.literal) {
+            (TokenKind::Identifier | TokenKind::IntegerLiteral | TokenKind::FloatLiteral, Some(literal)) => format!("'{}'", literal),
+            (kind, _) => kind.description(),
+        }
+    }
 }
\ No newline at end of file
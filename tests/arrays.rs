@@ -0,0 +1,104 @@
+mod common;
+
+use std::process::Output;
+
+use common::{errors, run, stderr, stdout, Project};
+
+const OUT_OF_BOUNDS: &str = "
+main :: fn() -> i32 {
+    numbers: [3]i32 = [1, 2, 3];
+    mut index: u64 = 0;
+    while index < 5 { index += 1; }
+    return numbers[index];
+}
+";
+
+#[test]
+fn indexes_and_slices_arrays() {
+    let source: &str = "
+Grid :: struct { cells: [3][2]i32, name: str }
+
+sum :: fn(values: []i32) -> i32 {
+    mut total: i32 = 0;
+    for value in values {
+        total += value;
+    }
+    return total;
+}
+
+make :: fn() -> [3]i32 {
+    return [4, 5, 6];
+}
+
+first :: fn(values: []i32) -> ?i32 {
+    if values.len == 0 { return null; }
+    return values[0];
+}
+
+main :: fn() -> i32 {
+    mut numbers: [4]i32 = [1, 2, 3, 4];
+    numbers[1] = 10;
+    all := numbers[..];
+    middle := numbers[1..3];
+    mut grid: Grid = Grid{ [[1, 2], [3, 4], [5, 6]], \"grid\" };
+    grid.cells[2][1] = 60;
+    p := &grid;
+    total := sum(all) + sum(middle[..1]) + make()[2] + p.cells[2][1] + grid.cells[1][0];
+    tail := middle[1..];
+    mut count: u64 = numbers.len + all.len + tail.len + grid.name.len;
+    for row in grid.cells {
+        for cell in row {
+            if cell > 4 { count += 1; }
+        }
+    }
+    opt: [2]?i32 = [null, 7];
+    x := first(numbers[..0]) orelse 100;
+    if count != 15 { return 1; }
+    return total + opt[1].? + x;
+}
+";
+    // 18 + 10 + 6 + 60 + 3 from the sums and indexes, 7 from the optional and 100 from the empty slice.
+    assert_eq!(run(source), 204);
+}
+
+#[test]
+fn traps_on_out_of_bounds_indexes_and_slices() {
+    let output: Output = Project::new().file("main.bl", OUT_OF_BOUNDS).execute();
+    assert!(output.status.code().is_none(), "{:?}", output.status);
+    assert!(stderr(&output).contains("main.bl:6: index 5 is out of bounds for length 3"), "{}", stderr(&output));
+    let output: Output = Project::new().file("main.bl", "get :: fn(values: []i64, end: u64) -> []i64 { return values[1..end]; }\nmain :: fn() -> i32 {\n    numbers := [1, 2, 3];\n    s := get(numbers[..], 2);\n    t := get(s, 4);\n    if t.len == 0 { return 1; }\n    return 0;\n}\n").execute();
+    assert!(output.status.code().is_none(), "{:?}", output.status);
+    assert!(stderr(&output).contains("main.bl:1: slice 1..4 is out of bounds for length 1"), "{}", stderr(&output));
+}
+
+#[test]
+fn checks_bounds_only_in_debug_builds() {
+    let project: Project = Project::new().file("main.bl", OUT_OF_BOUNDS);
+    let debug: String = stdout(&project.blaze(&["emit", "main.bl"]));
+    assert!(debug.contains("numbers.items[__blaze_index(index, 3, \"main.bl\", 6)]"), "{}", debug);
+    let release: String = stdout(&project.blaze(&["emit", "--release", "main.bl"]));
+    assert!(!release.contains("__blaze_index("), "{}", release);
+}
+
+#[test]
+fn checks_array_types_and_indexes() {
+    let errors: String = errors("
+main :: fn() -> i32 {
+    numbers: [3]i32 = [1, 2];
+    x: i32 = numbers[true];
+    y: i32 = numbers.size;
+    z := 5[0];
+    w: [2]i32 = [1, true];
+    return 0;
+}
+");
+    for expected in [
+        "error[E0031]: expected 3 elements for '[3]i32', but the array literal has 2\n --> main.bl:3:23",
+        "error[E0031]: array index must be an integer, found 'bool'\n --> main.bl:4:22",
+        "error[E0038]: cannot access field 'size' on a value of type '[3]i32'\n --> main.bl:5:14",
+        "error[E0032]: cannot index into a value of type 'i64'\n --> main.bl:6:10",
+        "error[E0031]: mismatched types: expected 'i32', found 'bool'\n --> main.bl:7:21",
+    ] {
+        assert!(errors.contains(expected), "{}\n{}", expected, errors);
+    }
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use common::{errors, run};
+
+#[test]
+fn chains_else_if() {
+    let source: &str = "
+sign :: fn(x: i32) -> i32 {
+    if x < 0 {
+        return 1;
+    } else if x == 0 {
+        return 2;
+    } else if x < 10 {
+        return 3;
+    } else {
+        return 4;
+    }
+}
+
+main :: fn() -> i32 {
+    if sign(-5) * 1000 + sign(0) * 100 + sign(5) * 10 + sign(50) == 1234 {
+        return 1;
+    }
+    return 0;
+}
+";
+    assert_eq!(run(source), 1);
+}
+
+#[test]
+fn scopes_bindings_to_blocks() {
+    let source: &str = "
+main :: fn() -> i32 {
+    value: i32 = 1;
+    {
+        inner: i32 = value + 2;
+        value: i32 = inner * 10;
+        if value != 30 { return 1; }
+    }
+    return value;
+}
+";
+    assert_eq!(run(source), 1);
+    let errors: String = errors("
+main :: fn() -> i32 {
+    {
+        inner: i32 = 1;
+    }
+    return inner;
+}
+");
+    assert!(errors.contains("error[E0037]: undefined name 'inner'\n --> main.bl:6:12"), "{}", errors);
+}
+
+#[test]
+fn runs_defers_in_reverse_order_at_block_exit() {
+    let source: &str = "
+mut log: i64 = 0;
+
+push :: fn(digit: i64) {
+    log = log * 10 + digit;
+}
+
+sign :: fn(x: i64) -> i64 {
+    defer push(9);
+    if x < 0 {
+        return 0 - 1;
+    } else if x < 10 {
+        defer push(8);
+        return 1;
+    }
+    return 2;
+}
+
+main :: fn() -> i32 {
+    {
+        defer push(2);
+        defer { push(3); }
+        push(4);
+    }
+    for i in 0..5 {
+        defer push(5);
+        if i == 1 { continue; }
+        if i == 2 { break; }
+    }
+    _s := sign(5);
+    if log == 43255589 { return 1; }
+    return 0;
+}
+";
+    // The block pushes 4, 3 and 2, each of the three iterations 5, and the early return 8 before 9.
+    assert_eq!(run(source), 1);
+}
@@ -0,0 +1,166 @@
+mod common;
+
+use common::{errors, run, stdout, Project};
+
+#[test]
+fn calls_functions_and_namespaced_functions() {
+    let source: &str = "
+math :: namespace {
+    square :: fn(n: i32) -> i32 {
+        return n * n;
+    }
+}
+
+add :: fn(a: i32, b: i32) -> i32 {
+    return a + b;
+}
+
+main :: fn() -> i32 {
+    return add(math::square(3), math :: square(2));
+}
+";
+    assert_eq!(run(source), 13);
+}
+
+#[test]
+fn calls_methods_on_values_and_pointers() {
+    let source: &str = "
+Counter :: struct { count: i32 }
+
+bump :: fn(self: *Counter, by: i32) {
+    self.count += by;
+}
+
+get :: fn(self: *Counter) -> i32 {
+    return self.count;
+}
+
+main :: fn() -> i32 {
+    mut counter: Counter = Counter{ 1 };
+    counter.bump(4);
+    pointer: *Counter = &counter;
+    pointer.bump(2);
+    return counter.get();
+}
+";
+    assert_eq!(run(source), 7);
+}
+
+#[test]
+fn calls_pointer_methods_on_call_results() {
+    let source: &str = "
+P :: struct { x: i32 }
+
+get :: fn(self: *P) -> i32 {
+    return self.x;
+}
+
+make :: fn(x: i32) -> P {
+    return P{ x };
+}
+
+main :: fn() -> i32 {
+    total: i32 = make(2).get() + make(3).get();
+    if make(1).get() == 1 && make(4).get() == 4 {
+        return total + P{ 10 }.get();
+    }
+    return 0;
+}
+";
+    assert_eq!(run(source), 15);
+}
+
+#[test]
+fn calls_variadic_functions() {
+    let source: &str = "
+first :: fn(value: i32, _rest: ...) -> i32 {
+    return value;
+}
+
+main :: fn() -> i32 {
+    return first(1, 2, 3) + first(3) + blaze::strlen(\"four\") - 4;
+}
+";
+    assert_eq!(run(source), 4);
+}
+
+#[test]
+fn calls_extern_functions_by_their_c_name() {
+    let source: &str = "
+#extern
+abs :: fn(value: i32) -> i32;
+
+math :: namespace {
+    /// The C library's, like `abs`.
+    #extern
+    atoi :: fn(text: *char) -> i32;
+}
+
+main :: fn() -> i32 {
+    return abs(-3) + math::atoi(\"4\");
+}
+";
+    let project: Project = Project::new().file("main.bl", source);
+    assert_eq!(project.run(), 7);
+    let c: String = stdout(&project.blaze(&["emit", "main.bl"]));
+    assert!(c.contains("return abs(-3) + atoi(\"4\");"), "{}", c);
+    assert!(!c.contains("int32_t abs("), "{}", c);
+}
+
+#[test]
+fn reports_misused_extern_attributes() {
+    let misused: String = errors("
+#extern(abs)
+abs :: fn(value: i32) -> i32;
+
+#extern
+limit :: 10;
+
+main :: fn() -> i32 {
+    return 0;
+}
+");
+    assert!(misused.contains("error[E0025]: 'extern' takes no arguments\n --> main.bl:2:1"), "{}", misused);
+    assert!(misused.contains("error[E0025]: only functions can be 'extern'\n --> main.bl:5:1"), "{}", misused);
+    let generic: String = errors("
+#extern
+first :: fn(value: $T) -> $T;
+");
+    assert!(generic.contains("error[E0025]: an 'extern' function cannot be generic\n --> main.bl:2:1"), "{}", generic);
+}
+
+#[test]
+fn reports_calls_to_values() {
+    let source: &str = "
+main :: fn() -> i32 {
+    value: i32 = 3;
+    return value(1);
+}
+";
+    let errors: String = errors(source);
+    assert!(errors.contains("error[E0041]: 'value' is not a function"), "{}", errors);
+    assert!(errors.contains("main.bl:4:12"), "{}", errors);
+}
+
+#[test]
+fn tells_constants_from_namespaced_calls() {
+    let source: &str = "
+counter :: namespace {
+    base :: 3i32;
+
+    bump :: fn(value: *i32) {
+        *value += 1;
+    }
+}
+
+limit :: counter::base;
+
+main :: fn() -> i32 {
+    mut x: i32 = 0;
+    counter :: bump(&x);
+    step :: 5i32;
+    return x + step + limit;
+}
+";
+    assert_eq!(run(source), 9);
+}
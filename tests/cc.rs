@@ -0,0 +1,86 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+use blaze::compiler::Compiler;
+use blaze::diagnostic::SourceFile;
+use blaze::error::BlazeError;
+use blaze::span::Span;
+
+const SOURCE: &str = "
+main :: fn() -> i32 {
+    return 42;
+}
+";
+
+// A compiler for `main.bl` in a fresh directory, which is also used as the build directory.
+fn compiler(name: &str) -> (Compiler, PathBuf) {
+    let dir: PathBuf = std::env::temp_dir().join(format!("blaze-cc-{}-{}", std::process::id(), name));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("main.bl"), SOURCE).unwrap();
+    let mut compiler: Compiler = Compiler::new();
+    compiler.add_file(dir.join("main.bl").to_string_lossy().to_string());
+    compiler.build_dir = Some(dir.clone());
+    (compiler, dir)
+}
+
+#[test]
+fn builds_an_executable() {
+    let (mut compiler, dir) = compiler("executable");
+    compiler.output_file = Some(dir.join("main"));
+    compiler.build().unwrap();
+    assert!(compiler.errors.is_empty(), "{:?}", compiler.errors);
+    assert_eq!(compiler.artifact, Some(dir.join("main")));
+    assert_eq!(Command::new(dir.join("main")).status().unwrap().code(), Some(42));
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn builds_an_object_file() {
+    let (mut compiler, dir) = compiler("object");
+    compiler.output_file = Some(dir.join("main.o"));
+    compiler.object_only = true;
+    compiler.build().unwrap();
+    assert!(dir.join("main.o").exists());
+    assert!(!dir.join("main").exists());
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn reports_a_missing_compiler() {
+    let (mut compiler, dir) = compiler("missing");
+    compiler.cc = Some("blaze-test-no-such-cc".to_string());
+    let error: BlazeError = compiler.build().unwrap_err();
+    assert!(error.to_string().contains("no C compiler found (tried blaze-test-no-such-cc)"), "{}", error);
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn reports_a_failing_compiler() {
+    let (mut compiler, dir) = compiler("failing");
+    compiler.cc = Some("false".to_string());
+    let error: BlazeError = compiler.build().unwrap_err();
+    assert!(error.to_string().contains("C compiler failed"), "{}", error);
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+// The column of a C compiler error is one of the generated C, so the error covers the Blaze line it maps to.
+#[cfg(unix)]
+#[test]
+fn maps_c_compiler_errors_to_blaze_lines() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let (mut compiler, dir) = compiler("diagnostics");
+    let script: PathBuf = dir.join("cc.sh");
+    let error: String = format!("{}:3:80: error: expected ';' before '}}' token", dir.join("main.bl").display());
+    std::fs::write(&script, format!("#!/bin/sh\necho \"{}\" >&2\nexit 1\n", error)).unwrap();
+    std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+    compiler.cc = Some(script.to_string_lossy().to_string());
+    compiler.build().unwrap();
+    assert_eq!(compiler.errors.len(), 1, "{:?}", compiler.errors);
+    let span: Span = compiler.errors[0].span().unwrap();
+    let source: &SourceFile = compiler.sources.get(span.file).unwrap();
+    assert_eq!(source.location(span.start), (3, 5));
+    assert_eq!(source.location(span.end), (3, 15));
+    let _ = std::fs::remove_dir_all(&dir);
+}
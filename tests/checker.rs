@@ -0,0 +1,84 @@
+mod common;
+
+use common::{errors, run};
+
+#[test]
+fn infers_the_types_of_locals() {
+    let source: &str = "
+Point :: struct { x: i32, y: i32 }
+
+main :: fn() -> i32 {
+    point := Point{ 2, 3 };
+    sum := point.x + point.y;
+    return sum;
+}
+";
+    assert_eq!(run(source), 5);
+}
+
+#[test]
+fn reports_mismatched_types_at_the_value() {
+    let source: &str = "
+main :: fn() -> i32 {
+    flag: i32 = true;
+    return flag;
+}
+";
+    let errors: String = errors(source);
+    assert!(errors.contains("error[E0031]: mismatched types: expected 'i32', found 'bool'"), "{}", errors);
+    assert!(errors.contains("--> main.bl:3:17"), "{}", errors);
+}
+
+#[test]
+fn reports_undefined_names() {
+    let errors: String = errors("main :: fn() -> i32 { return missing; }\n");
+    assert!(errors.contains("error[E0037]: undefined name 'missing'"), "{}", errors);
+}
+
+#[test]
+fn reports_wrong_argument_counts() {
+    let source: &str = "
+add :: fn(a: i32, b: i32) -> i32 {
+    return a + b;
+}
+
+main :: fn() -> i32 {
+    return add(1);
+}
+";
+    let errors: String = errors(source);
+    assert!(errors.contains("error[E0042]: 'add' expects 2 arguments, but got 1"), "{}", errors);
+}
+
+#[test]
+fn names_types_as_they_are_written() {
+    let source: &str = "
+main :: fn() -> i32 {
+    values: [3]i32 = [1, 2, 3];
+    slice: []i32 = values[0..2];
+    maybe: ?*i32 = null;
+    flag: bool = slice;
+    other: bool = maybe;
+    return 0;
+}
+";
+    let errors: String = errors(source);
+    assert!(errors.contains("expected 'bool', found '[]i32'"), "{}", errors);
+    assert!(errors.contains("expected 'bool', found '?*i32'"), "{}", errors);
+}
+
+#[test]
+fn does_not_report_the_uses_of_a_local_with_errors() {
+    let source: &str = "
+Point :: struct { x: i32 }
+
+main :: fn() -> i32 {
+    point := missing;
+    x := point.x + 1;
+    return x;
+}
+";
+    let errors: String = errors(source);
+    assert!(errors.contains("undefined name 'missing'"), "{}", errors);
+    assert_eq!(errors.matches("error[").count(), 1, "{}", errors);
+}
@@ -0,0 +1,83 @@
+mod common;
+
+use std::process::Output;
+
+use common::{stderr, stdout, Project};
+
+const SOURCE: &str = "
+main :: fn(argc: i32, _argv: **char) -> i32 {
+    return argc + 10;
+}
+";
+
+#[test]
+fn run_forwards_arguments_and_the_exit_code() {
+    let project: Project = Project::new().file("main.bl", SOURCE);
+    let output: Output = project.blaze(&["run", "main.bl", "--", "a", "b"]);
+    assert_eq!(output.status.code(), Some(13), "{}", stderr(&output));
+}
+
+#[test]
+fn run_starts_a_relative_output_from_the_working_directory() {
+    let project: Project = Project::new().file("main.bl", SOURCE);
+    let output: Output = project.blaze(&["run", "main.bl", "-o", "program", "--", "a"]);
+    assert_eq!(output.status.code(), Some(12), "{}", stderr(&output));
+    assert!(project.dir.join("program").exists());
+}
+
+#[test]
+fn build_writes_the_named_executable() {
+    let project: Project = Project::new().file("main.bl", SOURCE);
+    let output: Output = project.blaze(&["build", "main.bl", "--release", "-o", "program"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(project.dir.join("program").exists());
+}
+
+#[test]
+fn check_does_not_build() {
+    let project: Project = Project::new().file("main.bl", SOURCE);
+    let output: Output = project.blaze(&["check", "main.bl"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(!project.dir.join("main").exists());
+}
+
+#[test]
+fn emit_prints_each_stage() {
+    let project: Project = Project::new().file("main.bl", SOURCE);
+    let tokens: String = stdout(&project.blaze(&["emit", "--stage", "tokens", "main.bl"]));
+    assert!(tokens.contains("DoubleColon"), "{}", tokens);
+    let ast: String = stdout(&project.blaze(&["emit", "--stage", "ast", "main.bl"]));
+    assert!(ast.contains("Function("), "{}", ast);
+    let c: String = stdout(&project.blaze(&["emit", "--stage", "c", "main.bl"]));
+    assert!(c.contains("int32_t main(int32_t argc, char** _argv) {"), "{}", c);
+}
+
+#[test]
+fn fails_when_there_are_errors() {
+    let project: Project = Project::new().file("main.bl", "main :: fn() -> i32 { return missing; }\n");
+    for command in ["check", "build", "run", "emit"] {
+        let output: Output = project.blaze(&[command, "main.bl"]);
+        assert_eq!(output.status.code(), Some(1), "{}", command);
+        assert!(stderr(&output).contains("undefined name 'missing'"), "{}", stderr(&output));
+    }
+}
+
+#[test]
+fn reports_a_program_without_main() {
+    let project: Project = Project::new().file("main.bl", "add :: fn(a: i32, b: i32) -> i32 {\n    return a + b;\n}\n");
+    for command in ["check", "build"] {
+        let output: Output = project.blaze(&[command, "main.bl"]);
+        assert_eq!(output.status.code(), Some(1), "{}", command);
+        assert!(stderr(&output).contains("error[E0051]: the program has no 'main' function\n --> main.bl:1:1"), "{}", stderr(&output));
+    }
+    // An object file is linked into a program that declares `main` elsewhere.
+    let output: Output = project.blaze(&["build", "main.bl", "-c", "-o", "add.o"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+}
+
+#[test]
+fn rejects_unknown_commands() {
+    let output: Output = Project::new().blaze(&["bogus", "main.bl"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("unknown command 'bogus'"), "{}", stderr(&output));
+}
@@ -0,0 +1,177 @@
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+use blaze::compiler::Compiler;
+
+// Lowers a program to C with the compiler library, then builds and runs the C with the system compiler.
+struct Program {
+    dir: PathBuf,
+    c: String,
+}
+
+impl Program {
+    fn new(name: &str, source: &str) -> Program {
+        let dir: PathBuf = std::env::temp_dir().join(format!("blaze-codegen-{}-{}", std::process::id(), name));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path: PathBuf = dir.join("main.bl");
+        std::fs::write(&path, source).unwrap();
+        let mut compiler: Compiler = Compiler::new();
+        compiler.add_file(path.to_string_lossy().to_string());
+        compiler.compile().unwrap();
+        assert!(compiler.errors.is_empty(), "{:?}", compiler.errors);
+        Program { dir, c: compiler.output }
+    }
+
+    fn run(&self) -> i32 {
+        std::fs::write(self.dir.join("main.c"), &self.c).unwrap();
+        let output: Output = Command::new("cc").args(["-std=c99", "main.c", "-o", "main"]).current_dir(&self.dir).output().unwrap();
+        assert!(output.status.success(), "{}\n{}", String::from_utf8_lossy(&output.stderr), self.c);
+        Command::new(self.dir.join("main")).status().unwrap().code().unwrap()
+    }
+}
+
+impl Drop for Program {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+#[test]
+fn lowers_structs_and_functions() {
+    let source: &str = "
+Point :: struct { x: i32, y: i32 }
+
+length :: fn(point: *Point) -> i32 {
+    return point.x;
+}
+
+main :: fn() -> i32 {
+    point: Point = Point{ 3, 4 };
+    return point.y;
+}
+";
+    let program: Program = Program::new("structs", source);
+    assert!(program.c.contains("int32_t length(Point* point)"), "{}", program.c);
+    assert_eq!(program.run(), 4);
+}
+
+#[test]
+fn flattens_namespaces_with_a_prefix() {
+    let source: &str = "
+geometry :: namespace {
+    Point :: struct { x: i32, y: i32 }
+
+    origin :: fn() -> Point {
+        return Point{ 0, 0 };
+    }
+}
+
+main :: fn() -> i32 {
+    return 5;
+}
+";
+    let program: Program = Program::new("namespaces", source);
+    assert!(program.c.contains("struct geometry__Point"), "{}", program.c);
+    assert!(program.c.contains("geometry__origin(void)"), "{}", program.c);
+    assert_eq!(program.run(), 5);
+}
+
+#[test]
+fn escapes_names_that_c_reserves() {
+    let source: &str = "
+FILE :: struct { default: i32, register: i32 }
+
+mut stdin: i32 = 1;
+
+exit :: fn(int: i32) -> i32 {
+    return int + stdin;
+}
+
+main :: fn() -> i32 {
+    file: FILE = FILE{ default: 2, register: 3 };
+    int: i32 = exit(file.default);
+    int_ := 4i32;
+    for double in 0i32..2 {
+        stdin += double;
+    }
+    return int + int_ + file.register + stdin;
+}
+";
+    let program: Program = Program::new("reserved", source);
+    assert!(program.c.contains("int32_t exit_(int32_t int_) {"), "{}", program.c);
+    assert!(program.c.contains("(FILE_){ .default_ = 2, .register_ = 3 }"), "{}", program.c);
+    assert!(program.c.contains("int32_t int_1 = 4;"), "{}", program.c);
+    assert_eq!(program.run(), 12);
+}
+
+#[test]
+fn keeps_mangled_names_apart() {
+    let source: &str = "
+a :: namespace {
+    b_c :: fn() -> i32 {
+        return 1;
+    }
+}
+
+a__b_c :: fn() -> i32 {
+    return 2;
+}
+
+opt_i32 :: struct { value: i32 }
+
+main :: fn() -> i32 {
+    maybe: ?i32 = 4;
+    plain: opt_i32 = opt_i32{ 8 };
+    return a::b_c() + a__b_c() + (maybe orelse 0) + plain.value;
+}
+";
+    let program: Program = Program::new("mangling", source);
+    assert!(program.c.contains("int32_t a__b_c(void) {"), "{}", program.c);
+    assert!(program.c.contains("int32_t a_1_b_c(void) {"), "{}", program.c);
+    assert!(program.c.contains("struct __blz_opt_i32 {"), "{}", program.c);
+    assert_eq!(program.run(), 15);
+}
+
+#[test]
+fn lowers_enums_and_unions() {
+    let source: &str = "
+Kind :: enum(u8) {
+    Null = 0,
+    Identifier = 4
+}
+
+Null :: struct { }
+Identifier :: struct { length: i32 }
+
+Data :: union { Null, Identifier }
+
+Node :: struct {
+    kind: Kind,
+    data: Data
+}
+
+main :: fn() -> i32 {
+    node: Node = Node{ Kind::Identifier, Identifier{ 3 } };
+    return 3;
+}
+";
+    let program: Program = Program::new("enums", source);
+    assert!(program.c.contains("union Data"), "{}", program.c);
+    assert!(program.c.contains("Kind__Identifier"), "{}", program.c);
+    assert_eq!(program.run(), 3);
+}
+
+#[test]
+fn lowers_globals_and_loops() {
+    let source: &str = "
+mut counter: i32 = 1;
+
+main :: fn() -> i32 {
+    while counter < 100 {
+        counter *= 3;
+    }
+    return counter;
+}
+";
+    assert_eq!(Program::new("globals", source).run(), 243);
+}
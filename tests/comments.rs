@@ -0,0 +1,43 @@
+mod common;
+
+use common::{errors, run, stdout, Project};
+
+#[test]
+fn skips_line_and_nested_block_comments() {
+    let source: &str = "
+// A line comment.
+main :: fn() -> i32 { // After code.
+    /* A block comment /* with a nested one */ still in the comment */
+    value: i32 = 4 /* between tokens */ * 2;
+    return value; // The end.
+}
+";
+    assert_eq!(run(source), 8);
+}
+
+#[test]
+fn attaches_doc_comments_to_declarations() {
+    let source: &str = "
+/// Adds one.
+/// Twice over.
+increment :: fn(n: i32) -> i32 {
+    return n + 1;
+}
+
+main :: fn() -> i32 {
+    return increment(1);
+}
+";
+    let project: Project = Project::new().file("main.bl", source);
+    let ast: String = stdout(&project.blaze(&["emit", "--stage", "ast", "main.bl"]));
+    assert!(ast.contains("Documented("), "{}", ast);
+    assert!(ast.contains("Adds one.\\nTwice over."), "{}", ast);
+    assert_eq!(project.run(), 2);
+}
+
+#[test]
+fn reports_unterminated_block_comments() {
+    let errors: String = errors("main :: fn() -> i32 { return 0; }\n/* /* */\n");
+    assert!(errors.contains("error[E0002]: unterminated block comment"), "{}", errors);
+    assert!(errors.contains("--> main.bl:2:1"), "{}", errors);
+}
@@ -0,0 +1,91 @@
+// Helpers shared by the integration tests, which run the blaze binary on programs written to a temporary directory.
+#![allow(dead_code)]
+
+use std::path::PathBuf;
+use std::process::{Command, Output};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static PROJECTS: AtomicUsize = AtomicUsize::new(0);
+
+pub struct Project {
+    pub dir: PathBuf,
+}
+
+impl Project {
+    pub fn new() -> Project {
+        let id: usize = PROJECTS.fetch_add(1, Ordering::SeqCst);
+        let dir: PathBuf = std::env::temp_dir().join(format!("blaze-test-{}-{}", std::process::id(), id));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        Project { dir }
+    }
+
+    pub fn file(self, path: &str, source: &str) -> Project {
+        let path: PathBuf = self.dir.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, source).unwrap();
+        self
+    }
+
+    pub fn blaze(&self, args: &[&str]) -> Output {
+        Command::new(env!("CARGO_BIN_EXE_blaze")).args(args).current_dir(&self.dir).output().unwrap()
+    }
+
+    // Builds `main.bl` into an executable and runs it.
+    pub fn execute(&self) -> Output {
+        let output: Output = self.blaze(&["build", "main.bl", "-o", "main"]);
+        assert!(output.status.success(), "build failed:\n{}", stderr(&output));
+        Command::new(self.dir.join("main")).current_dir(&self.dir).output().unwrap()
+    }
+
+    pub fn run(&self) -> i32 {
+        self.execute().status.code().expect("the program was stopped by a signal")
+    }
+}
+
+impl Drop for Project {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+// Builds and runs a program, returning its exit code.
+pub fn run(source: &str) -> i32 {
+    Project::new().file("main.bl", source).run()
+}
+
+// Checks a program that has errors, returning the diagnostics printed for them.
+pub fn errors(source: &str) -> String {
+    let output: Output = Project::new().file("main.bl", source).blaze(&["check", "main.bl"]);
+    assert!(!output.status.success(), "expected errors, found none:\n{}", stderr(&output));
+    stderr(&output)
+}
+
+// Checks a program without errors, returning the warnings printed for it.
+pub fn warnings(source: &str) -> String {
+    let output: Output = Project::new().file("main.bl", source).blaze(&["check", "main.bl"]);
+    assert!(output.status.success(), "expected no errors:\n{}", stderr(&output));
+    stderr(&output)
+}
+
+// What the command printed to stderr, without colors.
+pub fn stderr(output: &Output) -> String {
+    strip_colors(&String::from_utf8_lossy(&output.stderr))
+}
+
+pub fn stdout(output: &Output) -> String {
+    strip_colors(&String::from_utf8_lossy(&output.stdout))
+}
+
+fn strip_colors(text: &str) -> String {
+    let mut out: String = String::new();
+    let mut chars: std::str::Chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            chars.by_ref().find(|c| c.is_ascii_alphabetic());
+            continue;
+        }
+        out.push(c);
+    }
+    out
+}
@@ -0,0 +1,96 @@
+mod common;
+
+use std::process::Output;
+
+use common::{errors, stderr, warnings, Project};
+
+#[test]
+fn renders_the_source_line_with_labels() {
+    let source: &str = "
+answer :: fn() -> i32 {
+    return true;
+}
+";
+    let errors: String = errors(source);
+    let expected: &str = "error[E0031]: mismatched types: expected 'i32', found 'bool'
+ --> main.bl:3:12
+  |
+2 | answer :: fn() -> i32 {
+  |                   --- expected because of this return type
+3 |     return true;
+  |            ^^^^
+  |
+";
+    assert!(errors.contains(expected), "{}", errors);
+}
+
+#[test]
+fn underlines_the_whole_binary_expression() {
+    let source: &str = "
+main :: fn() -> i32 {
+    flag: bool = true;
+    return (flag + 1) * 2;
+}
+";
+    let errors: String = errors(source);
+    let expected: &str = "error[E0032]: cannot apply '+' to a value of type 'bool'
+ --> main.bl:4:13
+  |
+4 |     return (flag + 1) * 2;
+  |             ^^^^^^^^
+  |
+";
+    assert!(errors.contains(expected), "{}", errors);
+}
+
+#[test]
+fn renders_notes_and_help() {
+    let source: &str = "
+add :: fn(a: i32, b: i32) -> i32 {
+    return a + b;
+}
+
+main :: fn() -> i32 {
+    count: i32 = add(1);
+    count = 2;
+    return count;
+}
+";
+    let errors: String = errors(source);
+    assert!(errors.contains("error[E0042]: 'add' expects 2 arguments, but got 1"), "{}", errors);
+    assert!(errors.contains("  = note: 'add' is declared as 'fn(a: i32, b: i32)'"), "{}", errors);
+    assert!(errors.contains("error[E0036]: cannot assign to immutable 'count'"), "{}", errors);
+    assert!(errors.contains("  = help: declare 'count' with 'mut' to make it mutable"), "{}", errors);
+}
+
+#[test]
+fn renders_suggested_fixes() {
+    let warnings: String = warnings("main :: fn() -> i32 {\n    unused := 3;\n    return 0;\n}\n");
+    let expected: &str = "help: if this is intentional, prefix it with an underscore
+  |
+2 |     _unused := 3;
+  |     ~~~~~~~
+";
+    assert!(warnings.contains(expected), "{}", warnings);
+}
+
+#[test]
+fn gives_each_kind_of_error_its_own_code() {
+    let source: &str = "
+value :: fn() -> i32 { return 1; }
+value :: fn() -> i32 { return 2; }
+";
+    let duplicate: String = errors(source);
+    assert!(duplicate.contains("error[E0015]: 'value' is already defined\n --> main.bl:3:1"), "{}", duplicate);
+    let undefined: String = errors("main :: fn() -> i32 { return missing; }\n");
+    assert!(undefined.contains("error[E0037]: undefined name 'missing'"), "{}", undefined);
+}
+
+#[test]
+fn reports_io_errors_without_a_location() {
+    let output: Output = Project::new().blaze(&["check", "missing.bl"]);
+    let errors: String = stderr(&output);
+    assert!(!output.status.success());
+    assert!(errors.starts_with("error[E0050]: cannot read 'missing.bl'"), "{}", errors);
+    assert!(!errors.contains("-->"), "{}", errors);
+}
@@ -0,0 +1,159 @@
+mod common;
+
+use common::{errors, run, stdout, Project};
+
+const SOURCE: &str = "
+Expr :: enum {
+    Identifier(name: *char),
+    Number(value: i32),
+    Group(expr: *Expr),
+    Empty,
+}
+
+eval :: fn(e: *Expr) -> i32 {
+    match e {
+        Expr::Identifier(_) => { return 1; }
+        Expr::Number(value) => return value;
+        Expr::Group(inner) => {
+            return eval(inner) * 2;
+        }
+        Expr::Empty => return 0;
+    }
+    return 0;
+}
+
+kind :: fn(e: Expr) -> i32 {
+    return match e {
+        Expr::Number(v) => v + 100,
+        Expr::Group(_) => 2,
+        _ => 3,
+    };
+}
+
+main :: fn() -> i32 {
+    n := Expr::Number(5);
+    g := Expr::Group(&n);
+    gg := Expr::Group(&g);
+    empty := Expr::Empty;
+    return eval(&gg) + kind(n) + kind(empty);
+}
+";
+
+#[test]
+fn matches_on_variants_and_binds_payloads() {
+    // 20 from the nested groups, 105 for the number and 3 for the empty variant.
+    assert_eq!(run(SOURCE), 128);
+}
+
+#[test]
+fn lowers_enums_to_a_tag_and_a_union() {
+    let c: String = stdout(&Project::new().file("main.bl", SOURCE).blaze(&["emit", "main.bl"]));
+    for expected in [
+        "typedef enum __blz_kind_Expr {\n    Expr__Identifier,\n    Expr__Number,\n    Expr__Group,\n    Expr__Empty,\n} __blz_kind_Expr;",
+        "struct __blz_payload_Expr__Number {\n    int32_t value;\n};",
+        "union __blz_data_Expr {\n    __blz_payload_Expr__Identifier _0;\n    __blz_payload_Expr__Number _1;\n    __blz_payload_Expr__Group _2;\n};",
+        "struct Expr {\n    __blz_kind_Expr kind;\n    __blz_data_Expr data;\n};",
+    ] {
+        assert!(c.contains(expected), "{}\n{}", expected, c);
+    }
+}
+
+#[test]
+fn runs_the_layout_in_rust_enum_c() {
+    // The enum described at the top of tests/rust_enum.c, with string payloads passed as pointers.
+    let source: &str = "
+Expr :: enum {
+    Identifier(name: *char),
+    Group(expr: *Expr),
+}
+
+length :: fn(e: *Expr) -> i32 {
+    match e {
+        Expr::Identifier(name) => return blaze::strlen(name);
+        Expr::Group(inner) => return length(inner) + 10;
+    }
+    return 0;
+}
+
+main :: fn() -> i32 {
+    name: *char = \"abc\";
+    x := Expr::Identifier(name);
+    y := Expr::Identifier(\"hello\");
+    group := Expr::Group(&x);
+    return length(&group) + length(&y);
+}
+";
+    let project: Project = Project::new().file("main.bl", source);
+    assert_eq!(project.run(), 18);
+    let c: String = stdout(&project.blaze(&["emit", "main.bl"]));
+    assert!(c.contains("struct __blz_payload_Expr__Identifier {\n    char* name;\n};"), "{}", c);
+}
+
+#[test]
+fn matches_unit_enums_as_expressions() {
+    let source: &str = "
+Color :: enum { Red, Green, Blue }
+
+main :: fn() -> i32 {
+    c := Color::Green;
+    return match c {
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Blue => 3,
+    };
+}
+";
+    assert_eq!(run(source), 2);
+}
+
+#[test]
+fn reports_non_exhaustive_matches() {
+    let errors: String = errors("
+Expr :: enum {
+    Number(value: i32),
+    Group(expr: *Expr),
+    Empty,
+}
+
+value :: fn(e: Expr) -> i32 {
+    match e {
+        Expr::Number(v) => return v;
+    }
+    return 0;
+}
+");
+    assert!(errors.contains("error[E0034]: non-exhaustive match: 'Expr::Group', 'Expr::Empty' are not covered"), "{}", errors);
+}
+
+#[test]
+fn checks_patterns_and_payloads() {
+    let errors: String = errors("
+Expr :: enum {
+    Number(value: i32),
+    Empty,
+}
+Color :: enum { Red, Green }
+
+value :: fn(e: Expr) -> i32 {
+    match e {
+        Expr::Number(a, b) => return a;
+        _ => { }
+    }
+    match e {
+        Color::Red => return 1;
+        _ => { }
+    }
+    number := Expr::Number(true);
+    empty := Expr::Empty(1);
+    return 0;
+}
+");
+    for expected in [
+        "error[E0042]: 'Expr::Number' has 1 payload field, but the pattern binds 2",
+        "error[E0031]: mismatched types: expected a variant of 'Expr', found 'Color'",
+        "error[E0031]: mismatched types: expected 'i32', found 'bool'",
+        "error[E0042]: 'Expr::Empty' has no payload",
+    ] {
+        assert!(errors.contains(expected), "{}\n{}", expected, errors);
+    }
+}
@@ -0,0 +1,235 @@
+mod common;
+
+use common::{errors, run, stdout, Project};
+
+#[test]
+fn propagates_errors_with_try_and_replaces_them_with_catch() {
+    let source: &str = "
+ParseError :: error { Empty, TooBig }
+
+parse :: fn(n: i32) -> ParseError!i32 {
+    if n == 0 {
+        return ParseError::Empty;
+    }
+    if n > 100 {
+        return ParseError::TooBig;
+    }
+    return n * 2;
+}
+
+twice :: fn(n: i32) -> !i32 {
+    a := try parse(n);
+    b: i32 = try parse(a);
+    return a + b;
+}
+
+code :: fn(n: i32) -> i32 {
+    return parse(n) catch |e| if_error(e);
+}
+
+if_error :: fn(e: ParseError) -> i32 {
+    if e == ParseError::Empty {
+        return -1;
+    }
+    return -2;
+}
+
+main :: fn() -> !i32 {
+    mut total: i32 = twice(3) catch 0;
+    total += twice(0) catch 100;
+    total += code(0) + code(500) + code(4);
+    return total - 120;
+}
+";
+    // 18 + 100 - 1 - 2 + 8 - 120.
+    assert_eq!(run(source), 3);
+}
+
+#[test]
+fn exits_with_one_when_main_returns_an_error() {
+    let source: &str = "
+Fail :: error { Bad }
+
+main :: fn() -> !i32 {
+    return Fail::Bad;
+}
+";
+    assert_eq!(run(source), 1);
+}
+
+#[test]
+fn binds_catch_between_comparisons_and_arithmetic() {
+    let source: &str = "
+Fail :: error { Bad }
+
+parse :: fn(n: i32) -> !i32 {
+    if n < 0 {
+        return Fail::Bad;
+    }
+    return n;
+}
+
+main :: fn() -> i32 {
+    a: bool = parse(-1) catch 5 == 5;
+    b: i32 = parse(-1) catch 0 + 1;
+    if a && b == 1 {
+        return 1;
+    }
+    return 0;
+}
+";
+    assert_eq!(run(source), 1);
+}
+
+#[test]
+fn evaluates_try_and_catch_only_when_reached() {
+    let source: &str = "
+Fail :: error { Bad }
+
+parse :: fn(n: i32) -> !i32 {
+    if n < 0 {
+        return Fail::Bad;
+    }
+    return n;
+}
+
+find :: fn(n: i32) -> ?i32 {
+    if n > 0 {
+        return n;
+    }
+    return null;
+}
+
+Shape :: enum {
+    Circle(r: i32),
+    Square(side: i32),
+}
+
+count :: fn(limit: i32) -> !i32 {
+    mut i: i32 = 0;
+    while try parse(limit - i) > 0 {
+        i += 1;
+    }
+    return i;
+}
+
+main :: fn() -> !i32 {
+    mut total: i32 = try count(3);
+    if total == 3 && try parse(2) == 2 {
+        total += 1;
+    }
+    if total == 0 && try parse(-1) == 0 {
+        total += 100;
+    }
+    if total == 4 || find(0) orelse 0 == 9 {
+        total += 1;
+    }
+    a: i32 = parse(-1) catch (parse(5) catch 0);
+    b: i32 = find(0) orelse (find(6) orelse 0);
+    c: i32 = find(7) orelse find(0).?;
+    shape: Shape = Shape::Square(2);
+    d: i32 = match shape {
+        Shape::Circle(r) => parse(r) catch 0,
+        Shape::Square(side) => try parse(side * 10),
+    };
+    mut e: i32 = 0;
+    while find(3 - e) orelse 0 > 0 {
+        e += 1;
+    }
+    return total + a + b + c + d + e;
+}
+";
+    // 5 + 5 + 6 + 7 + 20 + 3: the right of `&&`, the fallbacks and the other arm are never evaluated.
+    assert_eq!(run(source), 46);
+}
+
+#[test]
+fn runs_defers_and_continues_loops_with_try_in_the_condition() {
+    let source: &str = "
+Fail :: error { Bad }
+
+parse :: fn(n: i32) -> !i32 {
+    if n < 0 {
+        return Fail::Bad;
+    }
+    return n;
+}
+
+mut cleaned: i32 = 0;
+
+run :: fn(n: i32) -> !i32 {
+    defer cleaned += 1;
+    mut i: i32 = 0;
+    mut skipped: i32 = 0;
+    outer: while try parse(n - i) > 0 {
+        i += 1;
+        if i == 2 {
+            skipped += 1;
+            continue outer;
+        }
+    }
+    return i + skipped * 10;
+}
+
+main :: fn() -> i32 {
+    a: i32 = run(3) catch 0;
+    b: i32 = run(-1) catch 50;
+    return a + b + cleaned * 100;
+}
+";
+    // Both calls run their defer, so 13 + 50 + 200, truncated to a byte.
+    assert_eq!(run(source), (13 + 50 + 200) % 256);
+}
+
+#[test]
+fn lowers_error_unions_to_a_result_struct() {
+    let source: &str = "
+parse :: fn(n: i32) -> !i32 {
+    return n;
+}
+
+main :: fn() -> i32 {
+    return parse(1) catch 0;
+}
+";
+    let c: String = stdout(&Project::new().file("main.bl", source).blaze(&["emit", "main.bl"]));
+    assert!(c.contains("struct __blz_result_i32 {\n    uint16_t error;\n    int32_t value;\n};"), "{}", c);
+}
+
+#[test]
+fn checks_error_sets_and_try() {
+    let errors: String = errors("
+E :: error { A, B }
+F :: error { X }
+
+f :: fn() -> E!i32 { return E::C; }
+g :: fn() -> i32 {
+    x := try f();
+    return x;
+}
+h :: fn() -> F!i32 {
+    f();
+    defer try f();
+    y := try f();
+    if f() == f() { }
+    return y;
+}
+k :: fn() -> E!i32 { return F::X; }
+m :: fn() -> i32 { return 1 catch 0; }
+main :: fn() -> i32 { return 0; }
+");
+    for expected in [
+        "error[E0038]: error set 'E' has no error named 'C'\n --> main.bl:5:29",
+        "error[E0033]: 'try' can only be used in a function that returns an error union\n --> main.bl:7:10",
+        "error[E0024]: the error from this call is ignored\n  --> main.bl:11:5",
+        "error[E0022]: cannot use 'try' in a deferred statement\n  --> main.bl:12:11",
+        "error[E0033]: 'try' cannot return 'E' from a function that returns 'F'\n  --> main.bl:13:10",
+        "error[E0032]: cannot compare values of 'E!i32'\n  --> main.bl:14:8",
+        "error[E0031]: mismatched types: expected 'E!i32', found 'F'\n  --> main.bl:17:29",
+        "error[E0031]: expected an error union before 'catch', found 'i32'\n  --> main.bl:18:27",
+    ] {
+        assert!(errors.contains(expected), "{}\n{}", expected, errors);
+    }
+    // `x` is still declared after its initializer fails, so using it is not reported again.
+    assert!(errors.contains("8 errors found!"), "{}", errors);
+}
@@ -0,0 +1,102 @@
+mod common;
+
+use common::{errors, run, stdout, Project};
+
+const SOURCE: &str = "
+Box :: struct<$T> { value: $T }
+Pair :: struct<$A, $B> { first: $A, second: $B }
+
+unwrap :: fn(box: Box<$T>) -> $T {
+    return box.value;
+}
+
+first :: fn(pair: Pair<$A, $B>) -> $A {
+    return pair.first;
+}
+
+main :: fn() -> i32 {
+    a: Box<i32> = Box{ 7 };
+    b := Box{ 8i32 };
+    c: Box<bool> = Box{ true };
+    p: Pair<i32, bool> = Pair{ 3, false };
+    mut total: i32 = unwrap(a) + unwrap(b) + first(p);
+    if unwrap(c) {
+        total += 1;
+    }
+    return total;
+}
+";
+
+#[test]
+fn instantiates_generic_structs_and_functions() {
+    assert_eq!(run(SOURCE), 19);
+}
+
+#[test]
+fn emits_each_instance_once_with_a_mangled_name() {
+    let c: String = stdout(&Project::new().file("main.bl", SOURCE).blaze(&["emit", "main.bl"]));
+    for (expected, count) in [
+        ("struct Box__3i32 {", 1),
+        ("struct Box__4bool {", 1),
+        ("struct Pair__3i32__4bool {", 1),
+        ("int32_t unwrap__3i32(Box__3i32 box) {", 1),
+        ("bool unwrap__4bool(Box__4bool box) {", 1),
+        ("int32_t first__3i32__4bool(Pair__3i32__4bool pair) {", 1),
+    ] {
+        assert_eq!(c.matches(expected).count(), count, "{}\n{}", expected, c);
+    }
+    assert!(c.contains("(unwrap__3i32(a) + unwrap__3i32(b)) + first__3i32__4bool(p)"), "{}", c);
+}
+
+#[test]
+fn nests_generic_arguments() {
+    let source: &str = "
+Box :: struct<$T> { value: $T }
+Pair :: struct<$A, $B> { first: $A, second: $B }
+
+main :: fn() -> i32 {
+    inner: Box<i32> = Box{ 5 };
+    outer: Box<Box<i32>> = Box{ inner };
+    pair: Pair<i32, Box<Box<i32>>>= Pair{ 1, outer };
+    return outer.value.value + pair.second.value.value + pair.first;
+}
+";
+    let project: Project = Project::new().file("main.bl", source);
+    assert_eq!(project.run(), 11);
+    let c: String = stdout(&project.blaze(&["emit", "main.bl"]));
+    assert!(c.contains("struct Box__9Box__3i32 {"), "{}", c);
+}
+
+#[test]
+fn infers_generic_arguments_consistently() {
+    let errors: String = errors("
+same :: fn(a: $T, b: $T) -> $T {
+    return a;
+}
+
+main :: fn() -> i32 {
+    return same(1i32, true);
+}
+");
+    assert!(errors.contains("error[E0031]: mismatched types: expected 'i32', found 'bool'\n --> main.bl:7:23"), "{}", errors);
+}
+
+#[test]
+fn checks_bodies_for_each_instance() {
+    let errors: String = errors("
+negate :: fn(x: $T) -> $T {
+    return -x;
+}
+
+main :: fn() -> i32 {
+    flag: bool = negate(true);
+    if flag {
+        return negate(1i32);
+    }
+    return 0;
+}
+");
+    assert!(errors.contains("error[E0032]: cannot apply '-' to a value of type 'bool'"), "{}", errors);
+    assert!(errors.contains("= note: in 'negate' instantiated with $T = bool"), "{}", errors);
+    assert_eq!(errors.matches("error[").count(), 1, "{}", errors);
+}
@@ -0,0 +1,47 @@
+mod common;
+
+use std::process::Output;
+
+use common::{stderr, stdout, Project};
+
+fn check(source: &str) -> (Output, Vec<String>) {
+    let output: Output = Project::new().file("main.bl", source).blaze(&["check", "--error-format", "json", "main.bl"]);
+    let lines: Vec<String> = stderr(&output).lines().map(|line| line.to_string()).collect();
+    (output, lines)
+}
+
+#[test]
+fn prints_one_object_per_diagnostic() {
+    let source: &str = "main :: fn() -> i32 {\n    unused := 1;\n    flag: i32 = true;\n    return 0;\n}\n";
+    let (output, lines) = check(source);
+    assert!(!output.status.success());
+    assert!(stdout(&output).is_empty());
+    assert_eq!(lines.len(), 2, "{:?}", lines);
+    assert!(lines.iter().all(|line| line.starts_with('{') && line.ends_with('}')), "{:?}", lines);
+    assert!(lines[0].starts_with(r#"{"severity":"warning","code":"W0001","message":"unused variable 'unused'","file":"main.bl","start":26,"end":32,"line":2,"column":5,"end_line":2,"end_column":11,"#), "{}", lines[0]);
+    assert!(lines[0].contains(r#""suggestions":[{"file":"main.bl","start":26,"end":32,"line":2,"column":5,"end_line":2,"end_column":11,"replacement":"_unused","message":"if this is intentional, prefix it with an underscore"}]"#), "{}", lines[0]);
+    assert!(lines[1].starts_with(r#"{"severity":"error","code":"E0031","message":"mismatched types: expected 'i32', found 'bool'","file":"main.bl","#), "{}", lines[1]);
+    assert!(lines[1].contains(r#""message":"expected due to this type","primary":false"#), "{}", lines[1]);
+}
+
+#[test]
+fn includes_notes_and_help() {
+    let source: &str = "add :: fn(a: i32) -> i32 {\n    return a;\n}\n\nmain :: fn() -> i32 {\n    return add();\n}\n";
+    let (_, lines) = check(source);
+    assert_eq!(lines.len(), 1, "{:?}", lines);
+    assert!(lines[0].contains(r#""notes":["'add' is declared as 'fn(a: i32)'"],"help":[]"#), "{}", lines[0]);
+}
+
+#[test]
+fn escapes_strings() {
+    let (_, lines) = check("main :: fn() -> i32 {\n    text := \"a\\q\";\n    return 0;\n}\n");
+    assert!(lines[0].contains(r#""message":"invalid escape sequence: '\\q'""#), "{:?}", lines);
+}
+
+#[test]
+fn prints_errors_without_a_location() {
+    let output: Output = Project::new().blaze(&["check", "--error-format", "json", "missing.bl"]);
+    let errors: String = stderr(&output);
+    assert!(errors.starts_with(r#"{"severity":"error","code":"E0050","message":"cannot read 'missing.bl'"#), "{}", errors);
+    assert!(errors.contains(r#""file":null,"start":null,"end":null,"line":null,"column":null,"end_line":null,"end_column":null,"labels":[]"#), "{}", errors);
+}
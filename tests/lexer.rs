@@ -0,0 +1,35 @@
+mod common;
+
+use std::process::Output;
+use std::time::{Duration, Instant};
+
+use common::{errors, stderr, stdout, Project};
+
+#[test]
+fn spans_are_byte_offsets() {
+    let source: &str = "main :: fn() -> i32 {\n    größe: i32 = 1;\n    return größe;\n}\n";
+    let tokens: String = stdout(&Project::new().file("main.bl", source).blaze(&["emit", "--stage", "tokens", "main.bl"]));
+    assert!(tokens.contains("main.bl:26..33 Identifier größe"), "{}", tokens);
+    assert!(tokens.contains("main.bl:33..34 Colon"), "{}", tokens);
+}
+
+#[test]
+fn reports_columns_after_non_ascii_text() {
+    let source: &str = "main :: fn() -> i32 {\n    s := \"héllo 🔥\"; größe: bool = s.len;\n    return 0;\n}\n";
+    let errors: String = errors(source);
+    assert!(errors.contains("error[E0031]: mismatched types: expected 'bool', found 'u64'\n --> main.bl:2:35"), "{}", errors);
+}
+
+#[test]
+fn lexes_large_files_in_linear_time() {
+    let mut source: String = String::new();
+    for index in 0..20000 {
+        source.push_str(format!("// Function number {}.\nf{} :: fn(a: i32) -> i32 {{ return a + {}; }}\n", index, index, index).as_str());
+    }
+    source.push_str("main :: fn() -> i32 { return f7(1); }\n");
+    let project: Project = Project::new().file("main.bl", &source);
+    let start: Instant = Instant::now();
+    let output: Output = project.blaze(&["emit", "--stage", "tokens", "main.bl"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(start.elapsed() < Duration::from_secs(20));
+}
@@ -0,0 +1,42 @@
+mod common;
+
+use std::process::Output;
+
+use common::{errors, stderr, Project};
+
+#[test]
+fn reports_every_lexical_error_in_a_file() {
+    let source: &str = "main :: fn() -> i32 {
+    a := \"abc\\q\";
+    b := 'xy';
+    c := \"open
+    return 0;
+}
+ident";
+    let errors: String = errors(source);
+    assert!(errors.contains("error[E0005]: invalid escape sequence: '\\q'\n --> main.bl:2:14"), "{}", errors);
+    assert!(errors.contains("error[E0003]: character literal may only contain one character\n --> main.bl:3:10"), "{}", errors);
+    assert!(errors.contains("error[E0001]: unterminated string literal\n --> main.bl:4:10"), "{}", errors);
+    assert!(!errors.contains("panicked"), "{}", errors);
+}
+
+#[test]
+fn rejects_characters_that_do_not_fit_in_a_char() {
+    let errors: String = errors("main :: fn() -> i32 {\n    c: char = 'é';\n    return 0;\n}\n");
+    assert!(errors.contains("error[E0003]: character literal must be ASCII\n --> main.bl:2:15"), "{}", errors);
+}
+
+#[test]
+fn reports_declarations_cut_off_at_the_end_of_the_file() {
+    for source in ["main :: fn() -> i32 { return 0; }\nx :: 12", "main :: fn() -> i32 { return 0; }\nname", "main :: fn() -> i32 { return 0"] {
+        let errors: String = errors(source);
+        assert!(errors.contains("error[E0007]: unexpected end of file"), "{}", errors);
+        assert!(!errors.contains("panicked"), "{}", errors);
+    }
+}
+
+#[test]
+fn accepts_an_empty_file() {
+    let output: Output = Project::new().file("main.bl", "").blaze(&["check", "-c", "main.bl"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+}
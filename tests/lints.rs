@@ -0,0 +1,83 @@
+mod common;
+
+use std::process::Output;
+
+use common::{stderr, warnings, Project};
+
+const SOURCE: &str = "
+util :: import \"lib/util\"
+
+helper :: fn(unused: i32) -> i32 {
+    return 1;
+    return 2;
+}
+
+main :: fn() -> i32 {
+    mut never: i32 = 1;
+    value: i32 = 1;
+    if true {
+        value: i32 = 2;
+        return value + never;
+    }
+    x: i32 = 5;
+    return helper(value);
+}
+";
+
+fn project() -> Project {
+    Project::new().file("main.bl", SOURCE).file("lib/util.bl", "one :: fn() -> i32 { return 1; }\n")
+}
+
+#[test]
+fn warns_about_every_lint() {
+    let output: Output = project().blaze(&["check", "main.bl"]);
+    let warnings: String = stderr(&output);
+    assert!(output.status.success(), "{}", warnings);
+    for expected in [
+        "warning[W0001]: unused variable 'x'",
+        "warning[W0002]: unused parameter 'unused'",
+        "warning[W0003]: unused import 'util'",
+        "warning[W0004]: unreachable statement",
+        "warning[W0005]: 'value' shadows a binding from an outer scope",
+        "warning[W0006]: 'never' is declared 'mut' but never mutated",
+        "6 warnings found!",
+    ] {
+        assert!(warnings.contains(expected), "{}\n{}", expected, warnings);
+    }
+}
+
+#[test]
+fn sets_lint_levels_from_the_command_line() {
+    let output: Output = project().blaze(&["check", "-D", "unused_mut", "-A", "unused_variables", "main.bl"]);
+    let errors: String = stderr(&output);
+    assert!(!output.status.success());
+    assert!(errors.contains("error[W0006]: 'never' is declared 'mut' but never mutated"), "{}", errors);
+    assert!(!errors.contains("unused variable"), "{}", errors);
+    assert!(errors.contains("4 warnings found!"), "{}", errors);
+}
+
+#[test]
+fn names_every_lint_with_warnings() {
+    let output: Output = project().blaze(&["check", "-A", "warnings", "-W", "shadowing", "main.bl"]);
+    let warnings: String = stderr(&output);
+    assert!(output.status.success(), "{}", warnings);
+    assert!(warnings.contains("warning[W0005]"), "{}", warnings);
+    assert!(warnings.contains("1 warnings found!"), "{}", warnings);
+}
+
+#[test]
+fn sets_lint_levels_with_attributes() {
+    let source: &str = "
+#allow(unused_parameters)
+log :: fn(message: str) -> void { }
+
+main :: fn() -> i32 {
+    #allow(unused_variables)
+    quiet: i32 = 1;
+    log(\"hello\");
+    return 0;
+}
+";
+    let warnings: String = warnings(source);
+    assert!(!warnings.contains("warning["), "{}", warnings);
+}
@@ -0,0 +1,106 @@
+mod common;
+
+use common::{errors, run};
+
+#[test]
+fn parses_radix_literals_with_separators() {
+    let source: &str = "
+main :: fn() -> i32 {
+    if 1_000_000 != 1000000 {
+        return 1;
+    }
+    return 0x1F + 0b1010 + 0o17 + 0x_10;
+}
+";
+    assert_eq!(run(source), 72);
+}
+
+#[test]
+fn evaluates_bool_and_float_literals() {
+    let source: &str = "
+main :: fn() -> i32 {
+    half: f32 = 0.5;
+    scaled: f64 = 1.5e2;
+    if true && !false && half * 4.0 == 2.0 && scaled == 150.0 && 2.5f32 > 2.0 {
+        return 1;
+    }
+    return 0;
+}
+";
+    assert_eq!(run(source), 1);
+}
+
+#[test]
+fn types_literals_by_their_suffix() {
+    let source: &str = "
+main :: fn() -> i32 {
+    small := 200u8;
+    wrapped: u8 = small + 100u8;
+    if wrapped == 44u8 {
+        return 44i32;
+    }
+    return 0;
+}
+";
+    assert_eq!(run(source), 44);
+}
+
+#[test]
+fn computes_literals_in_their_inferred_width() {
+    let source: &str = "
+main :: fn() -> i32 {
+    c: i64 = 1 << 40;
+    d: i64 = 100000 * 100000;
+    e: u32 = 1 << 31;
+    f: u64 = 3 << 62;
+    if c == 1099511627776 && d == 10000000000 && e == 2147483648 && f > 9000000000000000000 {
+        return 1;
+    }
+    return 0;
+}
+";
+    assert_eq!(run(source), 1);
+}
+
+#[test]
+fn reports_literals_out_of_range() {
+    let source: &str = "
+main :: fn() -> i32 {
+    a: u8 = 256;
+    b := 300u8;
+    c: i32 = 0x1_0000_0000;
+    return 0;
+}
+";
+    let errors: String = errors(source);
+    assert!(errors.contains("error[E0029]: integer literal '256' does not fit in 'u8' (range 0..=255)"), "{}", errors);
+    assert!(errors.contains("error[E0029]: integer literal '300' does not fit in 'u8' (range 0..=255)"), "{}", errors);
+    assert!(errors.contains("integer literal '4294967296' does not fit in 'i32'"), "{}", errors);
+}
+
+#[test]
+fn points_at_the_literal_that_made_a_name_an_i64() {
+    let source: &str = "
+main :: fn() -> i32 {
+    x := 5;
+    return x;
+}
+";
+    let errors: String = errors(source);
+    let expected: &str = "error[E0031]: mismatched types: expected 'i32', found 'i64'
+ --> main.bl:4:12
+  |
+2 | main :: fn() -> i32 {
+  |                 --- expected because of this return type
+3 |     x := 5;
+  |          - this literal has no suffix, so it is an 'i64'
+4 |     return x;
+  |            ^
+  |
+help: give the literal the type 'i32'
+  |
+3 |     x := 5i32;
+  |           +++
+";
+    assert!(errors.contains(expected), "{}", errors);
+}
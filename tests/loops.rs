@@ -0,0 +1,80 @@
+mod common;
+
+use common::{errors, run};
+
+#[test]
+fn iterates_over_ranges() {
+    let source: &str = "
+count :: fn() -> i32 { return 4; }
+
+main :: fn() -> i32 {
+    mut total: i32 = 0;
+    for i in 0..count() {
+        total += i;
+    }
+    for _ in 0..3 {
+        total += 10;
+    }
+    return total;
+}
+";
+    assert_eq!(run(source), 36);
+}
+
+#[test]
+fn iterates_over_arrays_and_slices() {
+    let source: &str = "
+main :: fn() -> i32 {
+    values: [4]i32 = [1, 2, 3, 4];
+    mut total: i32 = 0;
+    for value in values {
+        total += value;
+    }
+    for value in values[1..3] {
+        total += value * 10;
+    }
+    return total;
+}
+";
+    assert_eq!(run(source), 60);
+}
+
+#[test]
+fn breaks_and_continues_labeled_loops() {
+    let source: &str = "
+main :: fn() -> i32 {
+    mut total: i32 = 0;
+    outer: for i in 0..10 {
+        mut j: i64 = 0;
+        inner: while j < 10 {
+            j += 1;
+            if j == 3 { continue inner; }
+            if j > i { continue outer; }
+            if i == 5 { break outer; }
+            if j == 4 { break; }
+            total += 1;
+        }
+    }
+    return total;
+}
+";
+    // Rows 1 to 4 count their columns up to the row or the fourth, skipping the third: 1 + 2 + 2 + 2.
+    assert_eq!(run(source), 7);
+}
+
+#[test]
+fn rejects_break_and_continue_outside_of_loops() {
+    let errors: String = errors("
+main :: fn() -> i32 {
+    break;
+    continue;
+    outer: for i in 0..3 {
+        break inner;
+    }
+    return 0;
+}
+");
+    assert!(errors.contains("error[E0022]: 'break' outside of a loop\n --> main.bl:3:5"), "{}", errors);
+    assert!(errors.contains("error[E0022]: 'continue' outside of a loop\n --> main.bl:4:5"), "{}", errors);
+    assert!(errors.contains("error[E0022]: undefined label 'inner'\n --> main.bl:6:9"), "{}", errors);
+}
@@ -0,0 +1,131 @@
+mod common;
+
+use std::process::Output;
+
+use common::{stderr, Project};
+
+#[test]
+fn binds_imports_as_namespaces() {
+    let project: Project = Project::new()
+        .file("main.bl", "
+math :: import \"lib/math\"
+again :: import \"lib/math.bl\"
+
+main :: fn() -> i32 {
+    point: math::Point = math::Point{ 3, 4 };
+    return math::add(point.x, point.y) + again::ORIGIN;
+}
+")
+        .file("lib/math.bl", "
+Point :: struct { x: i32, y: i32 }
+
+ORIGIN :: 10i32;
+
+add :: fn(a: i32, b: i32) -> i32 {
+    return a + b;
+}
+");
+    assert_eq!(project.run(), 17);
+}
+
+#[test]
+fn loads_each_module_once() {
+    let project: Project = Project::new()
+        .file("main.bl", "
+counter :: import \"lib/counter\"
+left :: import \"lib/left\"
+right :: import \"lib/right\"
+
+main :: fn() -> i32 {
+    return left::bump() + right::bump() + counter::count;
+}
+")
+        .file("lib/counter.bl", "mut count: i32 = 0;\n")
+        .file("lib/left.bl", "counter :: import \"lib/counter\"\n\nbump :: fn() -> i32 {\n    counter::count += 1;\n    return counter::count;\n}\n")
+        .file("lib/right.bl", "counter :: import \"lib/counter\"\n\nbump :: fn() -> i32 {\n    counter::count += 10;\n    return counter::count;\n}\n");
+    // Both modules change the same counter: 1 + 11 + 11.
+    assert_eq!(project.run(), 23);
+}
+
+#[test]
+fn loads_std_modules_from_the_std_root() {
+    let project: Project = Project::new()
+        .file("main.bl", "io :: import \"std/io\"\n\nmain :: fn() -> i32 {\n    return io::code();\n}\n")
+        .file("stdlib/prelude.bl", "")
+        .file("stdlib/io.bl", "code :: fn() -> i32 {\n    return 21;\n}\n");
+    let output: Output = project.blaze(&["build", "main.bl", "--std", "stdlib", "-o", "main"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let status: Option<i32> = std::process::Command::new(project.dir.join("main")).status().unwrap().code();
+    assert_eq!(status, Some(21));
+}
+
+#[test]
+fn loads_generic_types_from_std_vec() {
+    let project: Project = Project::new().file("main.bl", "
+vec :: import \"std/vec\"
+
+main :: fn() -> i32 {
+    a: vec::Vec2<i32> = vec::Vec2{ x: 1, y: 2 };
+    b := vec::Vec3{ x: 1.5, y: 2.0, z: 3.0 };
+    if b.z > b.x {
+        return a.x + a.y;
+    }
+    return 0;
+}
+");
+    assert_eq!(project.run(), 3);
+}
+
+#[test]
+fn keeps_modules_with_the_same_path_apart() {
+    let project: Project = Project::new()
+        .file("main.bl", "
+math :: import \"lib/math\"
+other :: import \"lib/math_1\"
+
+main :: fn() -> i32 {
+    return math::value() + other::value() + blaze::value();
+}
+")
+        .file("lib/math.bl", "value :: fn() -> i32 {\n    return 1;\n}\n")
+        .file("lib/math_1.bl", "value :: fn() -> i32 {\n    return 2;\n}\n")
+        .file("stdlib/prelude.bl", "blaze :: namespace {\n\nmath :: import \"lib/math\"\n\nvalue :: fn() -> i32 {\n    return math::value();\n}\n\n}\n")
+        .file("stdlib/lib/math.bl", "value :: fn() -> i32 {\n    return 4;\n}\n");
+    // The prelude's `lib/math` is loaded first, so the project's gets the namespace `lib/math/1`.
+    let output: Output = project.blaze(&["build", "main.bl", "--std", "stdlib", "-o", "main"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let status: Option<i32> = std::process::Command::new(project.dir.join("main")).status().unwrap().code();
+    assert_eq!(status, Some(7));
+}
+
+#[test]
+fn reports_import_cycles() {
+    let project: Project = Project::new()
+        .file("main.bl", "a :: import \"cycle/a\"\n\nmain :: fn() -> i32 {\n    return a::f();\n}\n")
+        .file("cycle/a.bl", "b :: import \"cycle/b\"\n\nf :: fn() -> i32 {\n    return 1;\n}\n")
+        .file("cycle/b.bl", "a :: import \"cycle/a\"\n\ng :: fn() -> i32 {\n    return 2;\n}\n");
+    let output: Output = project.blaze(&["check", "main.bl"]);
+    let errors: String = stderr(&output);
+    assert!(!output.status.success());
+    assert!(errors.contains("error[E0014]: import cycle: cycle/a -> cycle/b -> cycle/a"), "{}", errors);
+}
+
+#[test]
+fn reports_missing_modules() {
+    let project: Project = Project::new().file("main.bl", "m :: import \"missing\"\n\nmain :: fn() -> i32 {\n    return 0;\n}\n");
+    let output: Output = project.blaze(&["check", "main.bl"]);
+    let errors: String = stderr(&output);
+    assert!(errors.contains("error[E0012]: cannot find module 'missing'"), "{}", errors);
+    assert!(errors.contains("= note: looked for 'missing.bl'"), "{}", errors);
+}
+
+#[test]
+fn assigns_only_to_mutable_globals_of_a_module() {
+    let project: Project = Project::new()
+        .file("main.bl", "config :: import \"config\"\n\nmain :: fn() -> i32 {\n    config::limit = 3;\n    config::count = 4;\n    return 0;\n}\n")
+        .file("config.bl", "limit: i32 = 1;\nmut count: i32 = 0;\n");
+    let output: Output = project.blaze(&["check", "main.bl"]);
+    let errors: String = stderr(&output);
+    assert!(errors.contains("error[E0036]: cannot assign to immutable 'config::limit'\n --> main.bl:4:5"), "{}", errors);
+    assert_eq!(errors.matches("error[").count(), 1, "{}", errors);
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use common::{errors, run};
+
+#[test]
+fn computes_bitwise_and_shift_operators() {
+    let source: &str = "
+main :: fn() -> i32 {
+    mut bits: i32 = 12 & 10 | 1;
+    bits ^= 3;
+    bits <<= 2;
+    bits >>= 1;
+    bits |= ~0 & 64;
+    return bits + (1 << 3 >> 1) + (6 ^ 5);
+}
+";
+    assert_eq!(run(source), 91);
+}
+
+#[test]
+fn short_circuits_logical_operators() {
+    let source: &str = "
+mut calls: i32 = 0;
+
+touch :: fn(result: bool) -> bool {
+    calls += 1;
+    return result;
+}
+
+main :: fn() -> i32 {
+    if false && touch(true) {
+        return 100;
+    }
+    if true || touch(false) {
+        calls += 10;
+    }
+    if !(touch(false) || touch(true)) {
+        return 100;
+    }
+    return calls;
+}
+";
+    assert_eq!(run(source), 12);
+}
+
+#[test]
+fn rejects_logical_operators_on_integers() {
+    let errors: String = errors("main :: fn() -> i32 { if 1 && 2 { return 1; } return 0; }\n");
+    assert!(errors.contains("error[E0031]: mismatched types: expected 'bool', found 'i64'"), "{}", errors);
+}
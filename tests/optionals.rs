@@ -0,0 +1,155 @@
+mod common;
+
+use std::process::Output;
+
+use common::{errors, run, stderr, stdout, Project};
+
+const SOURCE: &str = "
+Node :: struct { value: i32, next: ?*Node }
+
+find :: fn(head: *Node, wanted: i32) -> ?i32 {
+    mut i: i32 = 0;
+    mut node: ?*Node = head;
+    while node != null {
+        if node.value == wanted { return i; }
+        node = node.next;
+        i += 1;
+    }
+    return null;
+}
+
+sum :: fn(head: ?*Node) -> i32 {
+    mut total: i32 = 0;
+    mut node: ?*Node = head;
+    while node |n| {
+        total += n.value;
+        node = n.next;
+    }
+    return total;
+}
+
+first :: fn(head: ?*Node) -> i32 {
+    if head != null {
+        return head.value;
+    } else if head == null {
+        return -1;
+    }
+    return 0;
+}
+
+global: ?i32 = null;
+
+main :: fn() -> i32 {
+    mut c: Node = Node{ 3, null };
+    mut b: Node = Node{ 2, &c };
+    a: Node = Node{ 1, &b };
+    mut result: i32 = sum(&a);
+    at: ?i32 = find(&a, 3);
+    if at |index| { result += index * 10; }
+    missing: ?i32 = find(&a, 9);
+    if missing != null { result += 1000; }
+    result += missing orelse 100;
+    result += find(&a, 2) orelse 7;
+    result += find(&a, 2).?;
+    if at != null && at > 1 { result += 5000; }
+    if global == null { result += first(null); }
+    mut opt: ?i32 = null;
+    opt = 4;
+    result += opt.?;
+    if opt != null {
+        opt = opt + 1;
+        result += opt orelse 0;
+        opt = null;
+    }
+    result += opt orelse 20;
+    return result;
+}
+";
+
+#[test]
+fn narrows_unwraps_and_defaults_optionals() {
+    assert_eq!(run(SOURCE), (6 + 20 + 100 + 1 + 1 + 5000 - 1 + 4 + 5 + 20) % 256);
+}
+
+#[test]
+fn lowers_optional_pointers_to_nullable_pointers() {
+    let c: String = stdout(&Project::new().file("main.bl", SOURCE).blaze(&["emit", "main.bl"]));
+    assert!(c.contains("struct Node {\n    int32_t value;\n    Node* next;\n};"), "{}", c);
+    assert!(c.contains("struct __blz_opt_i32 {\n    bool present;\n    int32_t value;\n};"), "{}", c);
+}
+
+#[test]
+fn binds_orelse_between_comparisons_and_arithmetic() {
+    let source: &str = "
+find :: fn(n: i32) -> ?i32 {
+    if n > 0 {
+        return n;
+    }
+    return null;
+}
+
+main :: fn() -> i32 {
+    a: bool = find(0) orelse 5 == 5;
+    b: i32 = find(0) orelse 0 + 1;
+    if a && b == 1 {
+        return 1;
+    }
+    return 0;
+}
+";
+    assert_eq!(run(source), 1);
+}
+
+#[test]
+fn traps_when_unwrapping_null() {
+    let project: Project = Project::new().file("main.bl", "main :: fn() -> i32 {\n    value: ?i32 = null;\n    return value.?;\n}\n");
+    let output: Output = project.execute();
+    assert!(output.status.code().is_none(), "{:?}", output.status);
+    assert!(stderr(&output).contains("main.bl:3: unwrapped a null optional"), "{}", stderr(&output));
+}
+
+#[test]
+fn requires_narrowing_before_using_optionals() {
+    let errors: String = errors("
+Node :: struct { value: i32, next: ?*Node }
+main :: fn() -> i32 {
+    p: *Node = null;
+    q: ?*Node = null;
+    x: i32 = q.value;
+    y: i32 = 5 orelse 3;
+    z: ?i32 = 4;
+    if z == 4 { }
+    w: i32 = z;
+    if x |v| { }
+    return *q;
+}
+");
+    for expected in [
+        "error[E0031]: 'null' is not a value of the non-optional type '*Node'\n --> main.bl:4:16",
+        "error[E0044]: cannot access field 'value' on an optional '?*Node' that may be null\n --> main.bl:6:14",
+        "error[E0031]: expected an optional before 'orelse', found 'i32'\n --> main.bl:7:14",
+        "error[E0032]: cannot compare optional '?i32' with a value\n --> main.bl:9:8",
+        "error[E0031]: mismatched types: expected 'i32', found '?i32'\n  --> main.bl:10:14",
+        "error[E0031]: expected an optional to unwrap in 'if', found 'i32'\n  --> main.bl:11:8",
+        "error[E0044]: cannot dereference an optional '?*Node' that may be null\n  --> main.bl:12:12",
+    ] {
+        assert!(errors.contains(expected), "{}\n{}", expected, errors);
+    }
+}
+
+#[test]
+fn stops_narrowing_an_optional_assigned_to() {
+    let errors: String = errors("
+Node :: struct { value: i32, next: ?*Node }
+main :: fn() -> i32 {
+    mut node: ?*Node = null;
+    if node != null {
+        node = node.next;
+        return node.value;
+    }
+    return 0;
+}
+");
+    assert!(errors.contains("error[E0044]: cannot access field 'value' on an optional '?*Node' that may be null\n --> main.bl:7:16"), "{}", errors);
+    assert_eq!(errors.matches("error[").count(), 1, "{}", errors);
+}
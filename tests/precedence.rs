@@ -0,0 +1,54 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn multiplies_before_adding_and_compares_last() {
+    let source: &str = "
+main :: fn() -> i32 {
+    if 1 + 2 * 3 == 7 && 2 * 3 + 1 == 7 {
+        return 1 + 2 * 3 - 8 / 4 % 3;
+    }
+    return 0;
+}
+";
+    assert_eq!(run(source), 5);
+}
+
+#[test]
+fn associates_arithmetic_to_the_left() {
+    let source: &str = "
+main :: fn() -> i32 {
+    return 100 - 10 - 5 + 100 / 10 / 5;
+}
+";
+    assert_eq!(run(source), 87);
+}
+
+#[test]
+fn associates_assignments_to_the_right() {
+    let source: &str = "
+main :: fn() -> i32 {
+    mut a: i32 = 1;
+    mut b: i32 = 2;
+    a = b = 5;
+    a += b -= 1;
+    return a * 10 + b;
+}
+";
+    assert_eq!(run(source), 94);
+}
+
+#[test]
+fn binds_member_access_and_unary_operators_tightest() {
+    let source: &str = "
+Point :: struct { x: i32, y: i32 }
+
+main :: fn() -> i32 {
+    point: Point = Point{ 3, 4 };
+    pointer: *Point = &point;
+    return -point.x * 2 + *&pointer.y * point.y;
+}
+";
+    assert_eq!(run(source), 10);
+}
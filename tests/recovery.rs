@@ -0,0 +1,53 @@
+mod common;
+
+use common::errors;
+
+#[test]
+fn reports_every_syntax_error_in_a_file() {
+    let source: &str = "
+first :: fn() -> i32 {
+    a: i32 = 1 +;
+    return 0;
+}
+
+Broken :: struct { x: i32 y: i32 }
+
+shapes :: namespace {
+    inner :: fn() -> i32 { return ); }
+}
+
+main :: fn() -> i32 {
+    return 0;
+}
+";
+    let errors: String = errors(source);
+    assert!(errors.contains("error[E0006]: expected an expression, found ';'\n --> main.bl:3:17"), "{}", errors);
+    assert!(errors.contains("error[E0006]: expected '}', found 'y'\n --> main.bl:7:27"), "{}", errors);
+    assert!(errors.contains("error[E0006]: expected an expression, found ')'\n  --> main.bl:10:35"), "{}", errors);
+    assert_eq!(errors.matches("error[").count(), 3, "{}", errors);
+}
+
+#[test]
+fn checks_the_rest_of_the_file_after_syntax_errors() {
+    let source: &str = "
+first :: fn() -> i32 {
+    a: i32 = 1 +;
+    b: i32 = true;
+    return 0;
+}
+
+main :: fn() -> i32 {
+    return missing;
+}
+";
+    let errors: String = errors(source);
+    assert!(errors.contains("expected an expression, found ';'"), "{}", errors);
+    assert!(errors.contains("error[E0031]: mismatched types: expected 'i32', found 'bool'\n --> main.bl:4:14"), "{}", errors);
+    assert!(errors.contains("error[E0037]: undefined name 'missing'\n --> main.bl:9:12"), "{}", errors);
+}
+
+#[test]
+fn reports_an_unexpected_end_of_file_once() {
+    let errors: String = errors("main :: fn() -> i32 {\n    if true {\n        return 1;\n");
+    assert_eq!(errors.matches("unexpected end of file").count(), 1, "{}", errors);
+}
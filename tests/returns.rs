@@ -0,0 +1,84 @@
+mod common;
+
+use common::{errors, run, stdout, Project};
+
+const SOURCE: &str = "
+Point :: struct { x: i32, y: i32 }
+
+divmod :: fn(a: i32, b: i32) -> i32, i32 {
+    return a / b, a % b;
+}
+
+split :: fn(p: Point) -> i32, i32 {
+    return p.x, p.y;
+}
+
+forward :: fn(a: i32, b: i32) -> i32, i32 {
+    return divmod(a, b);
+}
+
+safe :: fn(a: i32, b: i32) -> !i32, ?i32 {
+    if b == 0 { return 0, null; }
+    return a / b, a % b;
+}
+
+pair :: fn(value: $T) -> $T, $T {
+    return value, value;
+}
+
+main :: fn() -> i32 {
+    q, r := divmod(17, 5);
+    _, y := split(Point{ 1, 2 });
+    mut a, b := forward(9, 4);
+    a += 1;
+    c, d := safe(7, 2);
+    e, f := pair(q);
+    divmod(1, 1);
+    return q * 1000 + r * 100 + y * 10 + a + b + (c catch 0) + (d orelse 0) + e + f;
+}
+";
+
+#[test]
+fn destructures_multiple_return_values() {
+    assert_eq!(run(SOURCE), (3000 + 200 + 20 + 3 + 1 + 3 + 1 + 3 + 3) % 256);
+}
+
+#[test]
+fn lowers_multiple_return_values_to_structs() {
+    let c: String = stdout(&Project::new().file("main.bl", SOURCE).blaze(&["emit", "main.bl"]));
+    assert!(c.contains("struct __blz_returns__3i32__3i32 {\n    int32_t _0;\n    int32_t _1;\n};"), "{}", c);
+    assert!(c.contains("struct __blz_returns__16__blz_result_i32__13__blz_opt_i32 {\n    __blz_result_i32 _0;\n    __blz_opt_i32 _1;\n};"), "{}", c);
+    assert_eq!(c.matches("struct __blz_returns__3i32__3i32 {").count(), 1, "{}", c);
+}
+
+#[test]
+fn checks_the_number_and_types_of_values() {
+    let errors: String = errors("
+two :: fn() -> i32, bool {
+    return 1;
+}
+three :: fn() -> i32, i32, i32 {
+    return two();
+}
+four :: fn() -> i32, bool {
+    return 1, 2;
+}
+main :: fn() -> i32 {
+    a, b, c := two();
+    x: i32 = two();
+    y, z := 5;
+    return two() + 1;
+}
+");
+    for expected in [
+        "error[E0023]: expected 2 return values, but got 1\n --> main.bl:3:5",
+        "error[E0023]: expected 3 return values, but got 2\n --> main.bl:6:12",
+        "error[E0031]: mismatched types: expected 'bool', found 'i64'\n --> main.bl:9:15",
+        "error[E0023]: expected 3 values to destructure, but got 2\n  --> main.bl:12:16",
+        "error[E0023]: 'two' returns 2 values, which cannot be used as one value\n  --> main.bl:13:14",
+        "error[E0023]: expected 2 values to destructure, but got 1\n  --> main.bl:14:13",
+        "error[E0023]: 'two' returns 2 values, which cannot be used as one value\n  --> main.bl:15:12",
+    ] {
+        assert!(errors.contains(expected), "{}\n{}", expected, errors);
+    }
+}
@@ -0,0 +1,23 @@
+mod common;
+
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+use common::stderr;
+
+#[test]
+fn builds_every_sample() {
+    let samples: PathBuf = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("samples");
+    let output_dir: PathBuf = std::env::temp_dir().join(format!("blaze-samples-{}", std::process::id()));
+    std::fs::create_dir_all(&output_dir).unwrap();
+    for entry in std::fs::read_dir(&samples).unwrap() {
+        let path: PathBuf = entry.unwrap().path();
+        if path.extension().is_none_or(|extension| extension != "bl") {
+            continue;
+        }
+        let executable: PathBuf = output_dir.join(path.file_stem().unwrap());
+        let output: Output = Command::new(env!("CARGO_BIN_EXE_blaze")).arg("build").arg(&path).arg("-o").arg(&executable).current_dir(&samples).output().unwrap();
+        assert!(output.status.success(), "{}:\n{}", path.display(), stderr(&output));
+    }
+    let _ = std::fs::remove_dir_all(&output_dir);
+}
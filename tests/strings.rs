@@ -0,0 +1,141 @@
+mod common;
+
+use common::{errors, run, stdout, Project};
+
+#[test]
+fn supports_escapes_raw_strings_and_the_runtime() {
+    let source: &str = r##"
+string :: import "std/string"
+
+count :: fn(s: str, c: char) -> i32 {
+    mut n: i32 = 0;
+    for x in s {
+        if x == c { n += 1; }
+    }
+    return n;
+}
+
+main :: fn() -> i32 {
+    greeting := "héllo, \u{1F525}!";
+    multi := "a
+b";
+    joined := "one \
+               two";
+    raw := r"C:\path\n";
+    hashed := r#"say "hi""#;
+    mut total: i32 = 0;
+    if greeting.len == 13 { total += 1; }
+    if multi.len == 3 && multi[1] == '\n' { total += 2; }
+    if string::equals(joined, "one two") { total += 4; }
+    if raw.len == 9 && raw[2] == '\\' { total += 8; }
+    if string::equals(hashed, "say \"hi\"") { total += 16; }
+    if string::compare("abc", "abd") < 0 && string::compare("b", "a") > 0 && string::compare("ab", "ab") == 0 { total += 32; }
+    if string::compare("z", "é") < 0 && string::compare("ab", "abc") < 0 { total += 64; }
+    if string::starts_with(greeting, "hé") && string::ends_with(greeting, "!") && !string::starts_with("a", "ab") { total += 128; }
+    mut buffer: [16]char = ['\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0'];
+    both := string::concat(buffer[..], "foo", "bar");
+    if string::equals(both, "foobar") && both.len == 6 { total += 256; }
+    world: str = greeting[0..5];
+    if string::equals(world, "héll") { total += 512; }
+    if count("banana", 'a') == 3 && blaze::strlen("abc") == 3 { total += 1024; }
+    buffer[0] = 'x';
+    return total - 2047;
+}
+"##;
+    // Each check adds its own bit to a total that reaches 2047 when all of them pass.
+    assert_eq!(run(source), 0);
+}
+
+#[test]
+fn passes_literals_as_slices_or_c_strings() {
+    let source: &str = "
+string :: import \"std/string\"
+
+is_command :: fn(line: str) -> bool {
+    return string::starts_with(line, \"/\") && !string::equals(line, \"/\");
+}
+
+main :: fn() -> i32 {
+    length := blaze::strlen(\"abc\");
+    if is_command(\"/quit\") && !is_command(\"/\") { return length; }
+    return 0;
+}
+";
+    let project: Project = Project::new().file("main.bl", source);
+    assert_eq!(project.run(), 3);
+    let c: String = stdout(&project.blaze(&["emit", "main.bl"]));
+    assert!(c.contains("int32_t length = blaze__strlen(\"abc\");"), "{}", c);
+    assert!(c.contains("is_command((__blz_slice_char){ \"/quit\", 5 })"), "{}", c);
+}
+
+#[test]
+fn prints_strings_with_std_io() {
+    let source: &str = "
+io :: import \"std/io\"
+
+main :: fn() -> i32 {
+    io::println(\"hello\");
+    io::print(\"a\");
+    io::println(\"b\"[0..1]);
+    return 0;
+}
+";
+    assert_eq!(stdout(&Project::new().file("main.bl", source).execute()), "hello\nab\n");
+}
+
+#[test]
+fn passes_only_literals_as_c_strings() {
+    let source: &str = "
+Fail :: error { Bad }
+
+name :: fn(ok: bool) -> Fail!*char {
+    if ok {
+        return \"named\";
+    }
+    return Fail::Bad;
+}
+
+main :: fn() -> i32 {
+    p: ?*char = \"abc\";
+    mut total: i32 = 0;
+    if p |s| {
+        total += blaze::strlen(s);
+    }
+    q: *char = name(false) catch \"xy\";
+    r: *char = name(true) catch \"-\";
+    return total + blaze::strlen(q) * 10 + blaze::strlen(r);
+}
+";
+    assert_eq!(run(source), 28);
+    let errors: String = errors("
+first :: fn(s: str) -> i32 {
+    return blaze::strlen(s);
+}
+
+second :: fn() -> i32 {
+    return blaze::strlen(\"hello\"[0..2]);
+}
+");
+    assert!(errors.contains("error[E0031]: mismatched types: expected '*char', found 'str'\n --> main.bl:3:26"), "{}", errors);
+    assert!(errors.contains("error[E0031]: mismatched types: expected '*char', found 'str'\n --> main.bl:7:27"), "{}", errors);
+}
+
+#[test]
+fn keeps_strings_immutable_and_distinct_from_char_slices() {
+    let errors: String = errors("
+main :: fn() -> i32 {
+    s := \"abc\";
+    s[0] = 'x';
+    if s == \"abc\" { return 1; }
+    p: []char = \"no\";
+    return 0;
+}
+");
+    for expected in [
+        "error[E0036]: cannot assign to a character of a 'str'\n --> main.bl:4:5",
+        "error[E0032]: cannot compare strings with operators\n --> main.bl:5:8",
+        "error[E0031]: mismatched types: expected '[]char', found 'str'\n --> main.bl:6:18",
+    ] {
+        assert!(errors.contains(expected), "{}\n{}", expected, errors);
+    }
+}